@@ -0,0 +1,176 @@
+//! Human-readable DTC codes
+//!
+//! SAE J2012-DA renders the 3-byte DTC as a system letter, four hex digits and the failure
+//! type byte, e.g. `P0420-00` or `U0100-87`. The system letter comes from the top two bits
+//! of the high byte and the first digit from the next two, so that digit is always `0`–`3`.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{DtcFormatIdentifier, DtcRecord, Error, J1939Dtc};
+
+/// System letters, indexed by the top two bits of the DTC high byte.
+const SYSTEM_LETTERS: [char; 4] = ['P', 'C', 'B', 'U'];
+
+impl DtcRecord {
+    /// The failure type byte (FTB): the low byte of the DTC.
+    #[must_use]
+    pub fn failure_type(&self) -> u8 {
+        (u32::from(*self) & 0xFF) as u8
+    }
+
+    /// Render this DTC in the notation of `format`.
+    ///
+    /// The SAE J2012-DA formats render as a J2012 code (see the [`Display`](fmt::Display)
+    /// impl), the SAE J1939-73 format as a [`J1939Dtc`]; every other format renders the raw
+    /// 3-byte value in hex.
+    ///
+    /// ```
+    /// use uds_protocol::{DtcFormatIdentifier, DtcRecord};
+    /// let dtc = DtcRecord::new(0xC1, 0x00, 0x87);
+    /// assert_eq!(dtc.to_string(), "U0100-87");
+    /// assert_eq!(
+    ///     dtc.display(DtcFormatIdentifier::Iso14229_1DtcFormat).to_string(),
+    ///     "0xC10087"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn display(&self, format: DtcFormatIdentifier) -> DtcRecordDisplay {
+        DtcRecordDisplay {
+            record: *self,
+            format,
+        }
+    }
+}
+
+/// A [`DtcRecord`] paired with the [`DtcFormatIdentifier`] to render it in; see
+/// [`DtcRecord::display`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DtcRecordDisplay {
+    record: DtcRecord,
+    format: DtcFormatIdentifier,
+}
+
+impl fmt::Display for DtcRecordDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            DtcFormatIdentifier::SaeJ2012DaDtcFormat00
+            | DtcFormatIdentifier::SaeJ2012DaDtcFormat04 => fmt::Display::fmt(&self.record, f),
+            DtcFormatIdentifier::SaeJ1939_73DtcFormat => {
+                fmt::Display::fmt(&J1939Dtc::from(self.record), f)
+            }
+            _ => write!(f, "{:#08X}", u32::from(self.record)),
+        }
+    }
+}
+
+impl fmt::Display for DtcRecord {
+    /// Render as a SAE J2012-DA code with failure type byte, e.g. `P0420-00`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = u32::from(*self);
+        let system = SYSTEM_LETTERS[(value >> 22) as usize & 0x03];
+        let code = (value >> 8) & 0x3FFF;
+        write!(f, "{system}{code:04X}-{:02X}", self.failure_type())
+    }
+}
+
+impl FromStr for DtcRecord {
+    type Err = Error;
+
+    /// Parse a SAE J2012-DA code, with or without the failure type byte: `P0420`, `p0420` and
+    /// `P0420-00` all give the same record.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (code, failure_type) = match s.split_once('-') {
+            Some((code, failure_type)) => (code, parse_hex(failure_type, 2)?),
+            None => (s, 0),
+        };
+        let mut chars = code.chars();
+        let system = chars
+            .next()
+            .and_then(|letter| {
+                SYSTEM_LETTERS
+                    .iter()
+                    .position(|&l| l == letter.to_ascii_uppercase())
+            })
+            .ok_or(Error::InvalidDtcCode)?;
+        let digits = parse_hex(chars.as_str(), 4)?;
+        if digits > 0x3FFF {
+            return Err(Error::InvalidDtcCode);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Self::from(
+            ((system as u32) << 22) | (digits << 8) | failure_type,
+        ))
+    }
+}
+
+/// Parse exactly `len` hex digits.
+fn parse_hex(digits: &str, len: usize) -> Result<u32, Error> {
+    if digits.len() != len || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::InvalidDtcCode);
+    }
+    u32::from_str_radix(digits, 16).map_err(|_| Error::InvalidDtcCode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn renders_j2012_codes() {
+        assert_eq!(DtcRecord::new(0x04, 0x20, 0x00).to_string(), "P0420-00");
+        assert_eq!(DtcRecord::new(0x5A, 0xBC, 0x11).to_string(), "C1ABC-11");
+        assert_eq!(DtcRecord::new(0x93, 0x00, 0x4B).to_string(), "B1300-4B");
+        assert_eq!(DtcRecord::new(0xC1, 0x00, 0x87).to_string(), "U0100-87");
+    }
+
+    #[test]
+    fn parses_j2012_codes() {
+        assert_eq!(
+            "P0420".parse::<DtcRecord>().unwrap(),
+            DtcRecord::new(0x04, 0x20, 0x00)
+        );
+        assert_eq!(
+            "u0100-87".parse::<DtcRecord>().unwrap(),
+            DtcRecord::new(0xC1, 0x00, 0x87)
+        );
+        for value in [0x00_0000, 0x3F_FF00, 0x7A_BC12, 0xFF_FFFF] {
+            let dtc = DtcRecord::from(value);
+            assert_eq!(dtc.to_string().parse::<DtcRecord>().unwrap(), dtc);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        for code in [
+            "", "P", "X0420", "P420", "P04200", "P4420", "P0420-", "P0420-8", "P0420-GG", "P+420",
+            "P0420-+8",
+        ] {
+            assert!(
+                matches!(code.parse::<DtcRecord>(), Err(Error::InvalidDtcCode)),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_follows_format_identifier() {
+        let dtc = DtcRecord::new(0x04, 0x20, 0x00);
+        assert_eq!(
+            dtc.display(DtcFormatIdentifier::SaeJ2012DaDtcFormat04)
+                .to_string(),
+            "P0420-00"
+        );
+        assert_eq!(
+            dtc.display(DtcFormatIdentifier::Iso14229_1DtcFormat)
+                .to_string(),
+            "0x042000"
+        );
+        assert_eq!(
+            dtc.display(DtcFormatIdentifier::SaeJ1939_73DtcFormat)
+                .to_string(),
+            "SPN 8196 FMI 0"
+        );
+    }
+}
@@ -0,0 +1,388 @@
+//! SAE J1939-73 interpretation of DTC records
+//!
+//! With [`DtcFormatIdentifier::SaeJ1939_73DtcFormat`](crate::DtcFormatIdentifier), the 3-byte
+//! DTC carries a 19-bit suspect parameter number (SPN) and a 5-bit failure mode identifier
+//! (FMI), laid out as in the J1939-73 DM1 message: the high and middle bytes hold SPN bits
+//! 7–0 and 15–8, and the low byte holds SPN bits 18–16 above the FMI.
+
+use core::fmt;
+
+use crate::{DtcRecord, Error};
+
+/// Largest suspect parameter number that fits in 19 bits.
+const SPN_MAX: u32 = 0x7_FFFF;
+
+/// Bits of the low DTC byte that carry the failure mode identifier.
+const FMI_MASK: u8 = 0x1F;
+
+/// SAE J1939-73 failure mode identifier (FMI)
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FailureModeIdentifier {
+    /// 0: Data valid but above normal operational range, most severe level
+    AboveNormalMostSevere,
+    /// 1: Data valid but below normal operational range, most severe level
+    BelowNormalMostSevere,
+    /// 2: Data erratic, intermittent or incorrect
+    DataErratic,
+    /// 3: Voltage above normal, or shorted to high source
+    VoltageAboveNormal,
+    /// 4: Voltage below normal, or shorted to low source
+    VoltageBelowNormal,
+    /// 5: Current below normal or open circuit
+    CurrentBelowNormal,
+    /// 6: Current above normal or grounded circuit
+    CurrentAboveNormal,
+    /// 7: Mechanical system not responding or out of adjustment
+    MechanicalSystemNotResponding,
+    /// 8: Abnormal frequency or pulse width or period
+    AbnormalFrequency,
+    /// 9: Abnormal update rate
+    AbnormalUpdateRate,
+    /// 10: Abnormal rate of change
+    AbnormalRateOfChange,
+    /// 11: Root cause not known
+    RootCauseNotKnown,
+    /// 12: Bad intelligent device or component
+    BadDevice,
+    /// 13: Out of calibration
+    OutOfCalibration,
+    /// 14: Special instructions
+    SpecialInstructions,
+    /// 15: Data valid but above normal operating range, least severe level
+    AboveNormalLeastSevere,
+    /// 16: Data valid but above normal operating range, moderately severe level
+    AboveNormalModeratelySevere,
+    /// 17: Data valid but below normal operating range, least severe level
+    BelowNormalLeastSevere,
+    /// 18: Data valid but below normal operating range, moderately severe level
+    BelowNormalModeratelySevere,
+    /// 19: Received network data in error
+    ReceivedNetworkDataInError,
+    /// 20: Data drifted high
+    DataDriftedHigh,
+    /// 21: Data drifted low
+    DataDriftedLow,
+    /// 31: Condition exists
+    ConditionExists,
+    /// 22–30: reserved by SAE J1939-73.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    Reserved(u8),
+}
+
+impl FailureModeIdentifier {
+    /// The SAE J1939-73 description of this failure mode.
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::AboveNormalMostSevere => {
+                "Data valid but above normal operational range - most severe level"
+            }
+            Self::BelowNormalMostSevere => {
+                "Data valid but below normal operational range - most severe level"
+            }
+            Self::DataErratic => "Data erratic, intermittent or incorrect",
+            Self::VoltageAboveNormal => "Voltage above normal, or shorted to high source",
+            Self::VoltageBelowNormal => "Voltage below normal, or shorted to low source",
+            Self::CurrentBelowNormal => "Current below normal or open circuit",
+            Self::CurrentAboveNormal => "Current above normal or grounded circuit",
+            Self::MechanicalSystemNotResponding => {
+                "Mechanical system not responding or out of adjustment"
+            }
+            Self::AbnormalFrequency => "Abnormal frequency or pulse width or period",
+            Self::AbnormalUpdateRate => "Abnormal update rate",
+            Self::AbnormalRateOfChange => "Abnormal rate of change",
+            Self::RootCauseNotKnown => "Root cause not known",
+            Self::BadDevice => "Bad intelligent device or component",
+            Self::OutOfCalibration => "Out of calibration",
+            Self::SpecialInstructions => "Special instructions",
+            Self::AboveNormalLeastSevere => {
+                "Data valid but above normal operating range - least severe level"
+            }
+            Self::AboveNormalModeratelySevere => {
+                "Data valid but above normal operating range - moderately severe level"
+            }
+            Self::BelowNormalLeastSevere => {
+                "Data valid but below normal operating range - least severe level"
+            }
+            Self::BelowNormalModeratelySevere => {
+                "Data valid but below normal operating range - moderately severe level"
+            }
+            Self::ReceivedNetworkDataInError => "Received network data in error",
+            Self::DataDriftedHigh => "Data drifted high",
+            Self::DataDriftedLow => "Data drifted low",
+            Self::ConditionExists => "Condition exists",
+            Self::Reserved(_) => "Reserved",
+        }
+    }
+}
+
+impl From<FailureModeIdentifier> for u8 {
+    fn from(value: FailureModeIdentifier) -> Self {
+        match value {
+            FailureModeIdentifier::AboveNormalMostSevere => 0,
+            FailureModeIdentifier::BelowNormalMostSevere => 1,
+            FailureModeIdentifier::DataErratic => 2,
+            FailureModeIdentifier::VoltageAboveNormal => 3,
+            FailureModeIdentifier::VoltageBelowNormal => 4,
+            FailureModeIdentifier::CurrentBelowNormal => 5,
+            FailureModeIdentifier::CurrentAboveNormal => 6,
+            FailureModeIdentifier::MechanicalSystemNotResponding => 7,
+            FailureModeIdentifier::AbnormalFrequency => 8,
+            FailureModeIdentifier::AbnormalUpdateRate => 9,
+            FailureModeIdentifier::AbnormalRateOfChange => 10,
+            FailureModeIdentifier::RootCauseNotKnown => 11,
+            FailureModeIdentifier::BadDevice => 12,
+            FailureModeIdentifier::OutOfCalibration => 13,
+            FailureModeIdentifier::SpecialInstructions => 14,
+            FailureModeIdentifier::AboveNormalLeastSevere => 15,
+            FailureModeIdentifier::AboveNormalModeratelySevere => 16,
+            FailureModeIdentifier::BelowNormalLeastSevere => 17,
+            FailureModeIdentifier::BelowNormalModeratelySevere => 18,
+            FailureModeIdentifier::ReceivedNetworkDataInError => 19,
+            FailureModeIdentifier::DataDriftedHigh => 20,
+            FailureModeIdentifier::DataDriftedLow => 21,
+            FailureModeIdentifier::ConditionExists => 31,
+            FailureModeIdentifier::Reserved(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for FailureModeIdentifier {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        Ok(match value {
+            0 => Self::AboveNormalMostSevere,
+            1 => Self::BelowNormalMostSevere,
+            2 => Self::DataErratic,
+            3 => Self::VoltageAboveNormal,
+            4 => Self::VoltageBelowNormal,
+            5 => Self::CurrentBelowNormal,
+            6 => Self::CurrentAboveNormal,
+            7 => Self::MechanicalSystemNotResponding,
+            8 => Self::AbnormalFrequency,
+            9 => Self::AbnormalUpdateRate,
+            10 => Self::AbnormalRateOfChange,
+            11 => Self::RootCauseNotKnown,
+            12 => Self::BadDevice,
+            13 => Self::OutOfCalibration,
+            14 => Self::SpecialInstructions,
+            15 => Self::AboveNormalLeastSevere,
+            16 => Self::AboveNormalModeratelySevere,
+            17 => Self::BelowNormalLeastSevere,
+            18 => Self::BelowNormalModeratelySevere,
+            19 => Self::ReceivedNetworkDataInError,
+            20 => Self::DataDriftedHigh,
+            21 => Self::DataDriftedLow,
+            22..=30 => Self::Reserved(value),
+            31 => Self::ConditionExists,
+            _ => return Err(Error::InvalidFailureModeIdentifier(value)),
+        })
+    }
+}
+
+/// A DTC in SAE J1939-73 terms: suspect parameter number and failure mode identifier
+///
+/// ```
+/// use uds_protocol::{DtcAndStatusIter, FailureModeIdentifier, J1939Dtc};
+/// // SPN 100 (engine oil pressure), FMI 1, status 0x08
+/// let records = [0x64, 0x00, 0x01, 0x08];
+/// for record in DtcAndStatusIter::new(&records) {
+///     let (dtc, _status) = record.unwrap();
+///     let dtc = J1939Dtc::from(dtc);
+///     assert_eq!(dtc.spn(), 100);
+///     assert_eq!(dtc.fmi(), FailureModeIdentifier::BelowNormalMostSevere);
+///     assert_eq!(dtc.to_string(), "SPN 100 FMI 1");
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "J1939DtcFields"))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct J1939Dtc {
+    spn: u32,
+    fmi: FailureModeIdentifier,
+}
+
+/// Unvalidated [`J1939Dtc`] fields, so deserialization goes through [`J1939Dtc::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct J1939DtcFields {
+    spn: u32,
+    fmi: FailureModeIdentifier,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<J1939DtcFields> for J1939Dtc {
+    type Error = Error;
+
+    fn try_from(fields: J1939DtcFields) -> Result<Self, Error> {
+        Self::new(fields.spn, fields.fmi)
+    }
+}
+
+impl J1939Dtc {
+    /// Create a `J1939Dtc`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSuspectParameterNumber`] if `spn` does not fit in 19 bits, and
+    /// [`Error::InvalidFailureModeIdentifier`] if `fmi` is a
+    /// [`Reserved`](FailureModeIdentifier::Reserved) value outside 22–30.
+    pub fn new(spn: u32, fmi: FailureModeIdentifier) -> Result<Self, Error> {
+        if spn > SPN_MAX {
+            return Err(Error::InvalidSuspectParameterNumber(spn));
+        }
+        if let FailureModeIdentifier::Reserved(value) = fmi {
+            if !(22..=30).contains(&value) {
+                return Err(Error::InvalidFailureModeIdentifier(value));
+            }
+        }
+        Ok(Self { spn, fmi })
+    }
+
+    /// The 19-bit suspect parameter number.
+    #[must_use]
+    pub const fn spn(&self) -> u32 {
+        self.spn
+    }
+
+    /// The failure mode identifier.
+    #[must_use]
+    pub const fn fmi(&self) -> FailureModeIdentifier {
+        self.fmi
+    }
+}
+
+impl From<DtcRecord> for J1939Dtc {
+    fn from(value: DtcRecord) -> Self {
+        let [_, dtc_high, dtc_middle, dtc_low] = u32::from(value).to_be_bytes();
+        let spn =
+            u32::from(dtc_high) | (u32::from(dtc_middle) << 8) | (u32::from(dtc_low >> 5) << 16);
+        // A 5-bit value is always a valid FMI.
+        let fmi = FailureModeIdentifier::try_from(dtc_low & FMI_MASK)
+            .unwrap_or(FailureModeIdentifier::Reserved(dtc_low & FMI_MASK));
+        Self { spn, fmi }
+    }
+}
+
+impl From<J1939Dtc> for DtcRecord {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(value: J1939Dtc) -> Self {
+        let spn = value.spn;
+        DtcRecord::new(
+            spn as u8,
+            (spn >> 8) as u8,
+            ((spn >> 16) as u8) << 5 | (u8::from(value.fmi) & FMI_MASK),
+        )
+    }
+}
+
+impl fmt::Display for J1939Dtc {
+    /// Render as `SPN <spn> FMI <fmi>`, both in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SPN {} FMI {}", self.spn, u8::from(self.fmi))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn fmi_round_trips_all_values() {
+        for i in 0..=u8::MAX {
+            match FailureModeIdentifier::try_from(i) {
+                Ok(fmi) => assert_eq!(u8::from(fmi), i),
+                Err(e) => {
+                    assert!(i > 31);
+                    assert!(matches!(e, Error::InvalidFailureModeIdentifier(v) if v == i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn splits_spn_and_fmi() {
+        // SPN 0x7ABCD, FMI 3
+        let record = DtcRecord::new(0xCD, 0xAB, 0b1110_0011);
+        let dtc = J1939Dtc::from(record);
+        assert_eq!(dtc.spn(), 0x7_ABCD);
+        assert_eq!(dtc.fmi(), FailureModeIdentifier::VoltageAboveNormal);
+        assert_eq!(DtcRecord::from(dtc), record);
+        assert_eq!(dtc.to_string(), "SPN 502733 FMI 3");
+    }
+
+    #[test]
+    fn every_record_round_trips() {
+        for value in [0x00_0000, 0x12_3456, 0xFF_FFFF, 0x00_001F, 0x00_0016] {
+            let record = DtcRecord::from(value);
+            assert_eq!(DtcRecord::from(J1939Dtc::from(record)), record);
+        }
+    }
+
+    #[test]
+    fn rejects_wide_spn() {
+        assert!(J1939Dtc::new(SPN_MAX, FailureModeIdentifier::ConditionExists).is_ok());
+        assert!(matches!(
+            J1939Dtc::new(SPN_MAX + 1, FailureModeIdentifier::ConditionExists),
+            Err(Error::InvalidSuspectParameterNumber(0x8_0000))
+        ));
+    }
+
+    #[test]
+    fn rejects_reserved_fmi_outside_reserved_range() {
+        assert!(J1939Dtc::new(100, FailureModeIdentifier::Reserved(25)).is_ok());
+        for value in [5, 31, 200] {
+            assert!(matches!(
+                J1939Dtc::new(100, FailureModeIdentifier::Reserved(value)),
+                Err(Error::InvalidFailureModeIdentifier(v)) if v == value
+            ));
+        }
+    }
+
+    #[test]
+    fn fmi_does_not_overwrite_spn_bits() {
+        // Bypasses `new` to build an out-of-range FMI.
+        let dtc = J1939Dtc {
+            spn: 0x7_0000,
+            fmi: FailureModeIdentifier::Reserved(200),
+        };
+        let record = DtcRecord::from(dtc);
+        assert_eq!(J1939Dtc::from(record).spn(), 0x7_0000);
+        assert_eq!(record, DtcRecord::new(0x00, 0x00, 0b1110_1000));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_spn_is_validated() {
+        let fields = |spn| J1939DtcFields {
+            spn,
+            fmi: FailureModeIdentifier::DataErratic,
+        };
+        assert_eq!(
+            J1939Dtc::try_from(fields(0x7_FFFF)).unwrap().spn(),
+            0x7_FFFF
+        );
+        assert!(matches!(
+            J1939Dtc::try_from(fields(0x8_0000)),
+            Err(Error::InvalidSuspectParameterNumber(0x8_0000))
+        ));
+    }
+
+    #[test]
+    fn describes_failure_modes() {
+        assert_eq!(
+            FailureModeIdentifier::VoltageBelowNormal.description(),
+            "Voltage below normal, or shorted to low source"
+        );
+        assert_eq!(
+            FailureModeIdentifier::Reserved(25).description(),
+            "Reserved"
+        );
+    }
+}
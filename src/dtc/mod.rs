@@ -8,3 +8,9 @@ pub use snapshot::*;
 
 mod ext_data;
 pub use ext_data::*;
+
+mod display;
+pub use display::*;
+
+mod j1939;
+pub use j1939::*;
@@ -3,6 +3,8 @@ use automotive_wire_codec::{
 };
 use thiserror::Error;
 
+use crate::{ResponseMismatch, UdsServiceType};
+
 /// Errors that can occur during UDS message encoding, decoding, or validation.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -75,6 +77,82 @@ pub enum Error {
     /// The DTC-setting byte is not a valid [`DtcSettingType`](crate::DtcSettingType) value.
     #[error("Invalid DTC Setting: {0}")]
     InvalidDtcSetting(u8),
+    /// The sub-function byte is not a valid [`AuthenticationType`](crate::AuthenticationType).
+    #[error("Invalid Authentication Type: {0}")]
+    InvalidAuthenticationType(u8),
+    /// The sub-function byte is not a valid [`DynamicDefinitionType`](crate::DynamicDefinitionType).
+    #[error("Invalid Dynamic Definition Type: {0}")]
+    InvalidDynamicDefinitionType(u8),
+    /// The data identifier is outside the periodic range (`0xF200–0xF2FF`) and cannot be
+    /// used as a [`PeriodicIdentifier`](crate::PeriodicIdentifier).
+    #[error("Invalid Periodic Data Identifier: {0:#06X}")]
+    InvalidPeriodicDataIdentifier(u16),
+    /// The sub-function byte is not a valid [`EventType`](crate::EventType).
+    #[error("Invalid Event Type: {0}")]
+    InvalidEventType(u8),
+    /// The sub-function byte is not a valid [`LinkControlType`](crate::LinkControlType).
+    #[error("Invalid Link Control Type: {0}")]
+    InvalidLinkControlType(u8),
+    /// The baudrate does not fit the 3-byte `linkRecord` of a `LinkControl` request.
+    #[error("Invalid Baudrate: {0}")]
+    InvalidBaudrate(u32),
+    /// The sub-function byte is not a valid
+    /// [`TimingParameterAccessType`](crate::TimingParameterAccessType).
+    #[error("Invalid Timing Parameter Access Type: {0}")]
+    InvalidTimingParameterAccessType(u8),
+    /// The string is not a SAE J2012 DTC code such as `P0420` or `U0100-87`.
+    #[error("Invalid DTC code")]
+    InvalidDtcCode,
+    /// The value does not fit the 19-bit SAE J1939 suspect parameter number.
+    #[error("Invalid J1939 Suspect Parameter Number: {0}")]
+    InvalidSuspectParameterNumber(u32),
+    /// The value does not fit the 5-bit SAE J1939 failure mode identifier.
+    #[error("Invalid J1939 Failure Mode Identifier: {0}")]
+    InvalidFailureModeIdentifier(u8),
+    /// The value is not a 17-character Vehicle Identification Number.
+    #[error("Invalid VIN")]
+    InvalidVin,
+    /// The value is not a valid BCD or ASCII `YYMMDD` identification date.
+    #[error("Invalid identification date")]
+    InvalidDate,
+    /// The data identifier is missing from the [`DidRegistry`](crate::DidRegistry), so the
+    /// length of its data record is unknown.
+    #[error("Unknown Data Identifier: {0:#06X}")]
+    UnknownDataIdentifier(u16),
+    /// The `DTCExtDataRecordNumber` is missing from the
+    /// [`DtcExtDataRegistry`](crate::DtcExtDataRegistry), so the size of its record is unknown.
+    #[error("Unknown DTC Extended Data Record Number: {0:#04X}")]
+    UnknownExtDataRecordNumber(u8),
+    /// The data identifier was already added to the request.
+    #[error("Duplicate Data Identifier: {0:#06X}")]
+    DuplicateDataIdentifier(u16),
+    /// More data identifiers than allowed in one request.
+    #[error("Too many Data Identifiers (limit: {0})")]
+    TooManyDataIdentifiers(usize),
+    /// The response for this data identifier alone would exceed the maximum PDU size.
+    ///
+    /// Corresponds to NRC 0x14 (`responseTooLong`).
+    #[error("Response too long for Data Identifier: {0:#06X}")]
+    ResponseTooLong(u16),
+    /// The response does not answer the request it was checked against.
+    #[error("Unexpected response: {0}")]
+    UnexpectedResponse(ResponseMismatch),
+    /// The service has no sub-function, so it cannot carry the suppress-positive-response bit.
+    #[error("Suppress positive response not supported by {0:?}")]
+    SuppressPositiveResponseNotSupported(UdsServiceType),
+    /// A builder already holds as many data identifiers as it has room for.
+    #[error("Data Identifier capacity exceeded (capacity: {0})")]
+    DataIdentifierCapacityExceeded(usize),
+    /// A limit of data identifiers per request must be at least 1.
+    #[error("Invalid Data Identifier limit: {0}")]
+    InvalidDataIdentifierLimit(usize),
+    /// A request needs at least one data identifier, but got none.
+    #[error("No Data Identifiers given")]
+    NoDataIdentifiers,
+    /// A dynamic data identifier definition needs at least one source data identifier or
+    /// memory area, but got none.
+    #[error("No definition sources given")]
+    NoDefinitionSources,
     /// The value is reserved for legislative use and must not be used.
     #[error("Reserved for legislative use: {0}")]
     ReservedForLegislativeUse(u8),
@@ -95,6 +173,12 @@ impl From<embedded_io::ErrorKind> for Error {
     }
 }
 
+impl From<ResponseMismatch> for Error {
+    fn from(mismatch: ResponseMismatch) -> Self {
+        Self::UnexpectedResponse(mismatch)
+    }
+}
+
 impl From<Incomplete> for Error {
     fn from(frag: Incomplete) -> Self {
         Self::InsufficientData(frag)
@@ -0,0 +1,722 @@
+//! Correlating a [`Request`] with the [`Response`] that answers it
+use core::{convert::Infallible, fmt};
+
+use crate::{
+    AccessTimingParametersRequest, AuthenticationRequest, ClearDiagnosticInfoRequest,
+    CommunicationControlRequest, ControlDtcSettingRequest, DiagnosticSessionControlRequest,
+    DynamicallyDefinedDataIdentifierRequest, EcuResetRequest, Encode,
+    InputOutputControlByIdentifierRequest, LinkControlRequest, NegativeResponseCode,
+    ReadDataByIdentifierRequest, ReadDataByPeriodicIdentifierRequest, ReadDtcInfoRequest,
+    ReadMemoryByAddressRequest, ReadScalingDataByIdentifierRequest, Request,
+    RequestDownloadRequest, RequestFileTransferRequest, RequestTransferExitRequest,
+    RequestUploadRequest, Response, ResponseOnEventRequest, RoutineControlRequest,
+    SecuredDataTransmissionRequest, SecurityAccessRequest, TesterPresentRequest,
+    TransferDataRequest, UdsServiceType, WriteDataByIdentifierRequest, WriteMemoryByAddressRequest,
+    shared::SPRMIB_VALUE_MASK,
+};
+
+/// Bit set in a request SID to form its positive response SID.
+const RESPONSE_SID_BIT: u8 = 0x40;
+
+/// Bytes after the SID that a response may echo: sub-function plus a 2-byte identifier.
+const MAX_ECHO_LEN: usize = 3;
+
+/// Negative response codes a server may send for any service (ISO 14229-1:2020, Annex A.1),
+/// in addition to the service's own `allowed_nack_codes()`.
+const GENERAL_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 7] = [
+    NegativeResponseCode::GeneralReject,
+    NegativeResponseCode::ServiceNotSupported,
+    NegativeResponseCode::BusyRepeatRequest,
+    NegativeResponseCode::AuthenticationRequired,
+    NegativeResponseCode::RequestCorrectlyReceivedResponsePending,
+    NegativeResponseCode::SubFunctionNotSupportedInActiveSession,
+    NegativeResponseCode::ServiceNotSupportedInActiveSession,
+];
+
+/// Negative response codes a server does not send for a functionally addressed request
+/// (ISO 14229-1:2020, 7.5).
+const FUNCTIONALLY_SUPPRESSED_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 5] = [
+    NegativeResponseCode::ServiceNotSupported,
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SubFunctionNotSupportedInActiveSession,
+    NegativeResponseCode::ServiceNotSupportedInActiveSession,
+];
+
+/// Which request parameters the positive response echoes after its SID.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Echo {
+    None,
+    SubFunction,
+    Identifier,
+    SubFunctionAndIdentifier,
+    BlockSequenceCounter,
+}
+
+impl Echo {
+    fn for_service(service: UdsServiceType) -> Self {
+        match service {
+            UdsServiceType::DiagnosticSessionControl
+            | UdsServiceType::EcuReset
+            | UdsServiceType::SecurityAccess
+            | UdsServiceType::CommunicationControl
+            | UdsServiceType::Authentication
+            | UdsServiceType::TesterPresent
+            | UdsServiceType::AccessTimingParameters
+            | UdsServiceType::ControlDtcSetting
+            | UdsServiceType::ResponseOnEvent
+            | UdsServiceType::LinkControl
+            | UdsServiceType::DynamicallyDefinedDataIdentifier
+            | UdsServiceType::ReadDtcInfo
+            | UdsServiceType::RequestFileTransfer => Self::SubFunction,
+            UdsServiceType::ReadDataByIdentifier
+            | UdsServiceType::WriteDataByIdentifier
+            | UdsServiceType::InputOutputControlByIdentifier
+            | UdsServiceType::ReadScalingDataByIdentifier => Self::Identifier,
+            UdsServiceType::RoutineControl => Self::SubFunctionAndIdentifier,
+            UdsServiceType::TransferData => Self::BlockSequenceCounter,
+            _ => Self::None,
+        }
+    }
+
+    const fn len(self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::SubFunction | Self::BlockSequenceCounter => 1,
+            Self::Identifier => 2,
+            Self::SubFunctionAndIdentifier => 3,
+        }
+    }
+
+    const fn has_sub_function(self) -> bool {
+        matches!(self, Self::SubFunction | Self::SubFunctionAndIdentifier)
+    }
+}
+
+/// Keeps the first `N` bytes written and counts the rest, so only the echoed parameters of a
+/// message are materialized.
+struct PrefixWriter<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> PrefixWriter<N> {
+    const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    fn prefix(&self) -> &[u8] {
+        &self.buf[..self.len.min(N)]
+    }
+}
+
+impl<const N: usize> embedded_io::ErrorType for PrefixWriter<N> {
+    type Error = Infallible;
+}
+
+impl<const N: usize> embedded_io::Write for PrefixWriter<N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+        for &b in buf {
+            if let Some(slot) = self.buf.get_mut(self.len) {
+                *slot = b;
+            }
+            self.len += 1;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The leading SID and echo bytes of an encoded message, or `None` if it cannot be encoded.
+fn encoded_prefix(message: &impl Encode) -> Option<PrefixWriter<{ 1 + MAX_ECHO_LEN }>> {
+    let mut writer = PrefixWriter::new();
+    message.encode(&mut writer).ok()?;
+    Some(writer)
+}
+
+/// Why a [`Response`] does not answer a [`Request`]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResponseMismatch {
+    /// The positive response SID is not the request SID + 0x40.
+    ServiceIdentifier {
+        /// Expected response SID.
+        expected: u8,
+        /// Response SID on the wire.
+        actual: u8,
+    },
+    /// The echoed sub-function, such as the session, reset or `DtcSettingType`, differs.
+    SubFunction {
+        /// Sub-function of the request, without the suppress-positive-response bit.
+        expected: u8,
+        /// Echoed sub-function.
+        actual: u8,
+    },
+    /// The echoed data identifier or routine identifier differs.
+    Identifier {
+        /// Identifier of the request.
+        expected: u16,
+        /// Echoed identifier.
+        actual: u16,
+    },
+    /// The echoed `TransferData` block sequence counter differs.
+    BlockSequenceCounter {
+        /// Counter of the request.
+        expected: u8,
+        /// Echoed counter.
+        actual: u8,
+    },
+    /// The response ends before the parameters it must echo.
+    MissingEcho,
+    /// The negative response names another request service.
+    NegativeResponseService {
+        /// SID of the request.
+        expected: u8,
+        /// Request SID echoed by the negative response.
+        actual: u8,
+    },
+    /// The negative response code is not one the request's service may send.
+    NegativeResponseCode(NegativeResponseCode),
+}
+
+impl fmt::Display for ResponseMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServiceIdentifier { expected, actual } => {
+                write!(f, "response SID {actual:#04X}, expected {expected:#04X}")
+            }
+            Self::SubFunction { expected, actual } => {
+                write!(f, "sub-function {actual:#04X}, expected {expected:#04X}")
+            }
+            Self::Identifier { expected, actual } => {
+                write!(f, "identifier {actual:#06X}, expected {expected:#06X}")
+            }
+            Self::BlockSequenceCounter { expected, actual } => {
+                write!(f, "block sequence counter {actual}, expected {expected}")
+            }
+            Self::MissingEcho => f.write_str("response is missing echoed parameters"),
+            Self::NegativeResponseService { expected, actual } => write!(
+                f,
+                "negative response for service {actual:#04X}, expected {expected:#04X}"
+            ),
+            Self::NegativeResponseCode(nrc) => {
+                write!(
+                    f,
+                    "negative response code {:#04X} not allowed",
+                    u8::from(*nrc)
+                )
+            }
+        }
+    }
+}
+
+/// What a response must look like to answer a particular [`Request`]
+///
+/// Built with [`Request::response_expectation`]. A positive response must carry the request
+/// SID + 0x40 and echo the request's sub-function (without the suppress-positive-response
+/// bit), data or routine identifier, or `TransferData` block sequence counter. A negative
+/// response must name the request's service and use one of the service's
+/// `allowed_nack_codes()` or an NRC that any service may send, such as
+/// `requestCorrectlyReceivedResponsePending`.
+///
+/// A `ReadDataByIdentifier` response may leave out DIDs the server does not support, so it
+/// must start with any one of the requested DIDs rather than the first.
+///
+/// Suppressing the positive response (SPRMIB) does not suppress negative responses; see
+/// [`is_response_sent`](Self::is_response_sent) and
+/// [`awaits_final_response`](Self::awaits_final_response).
+///
+/// ```
+/// use uds_protocol::{Decode, EcuResetRequest, Request, ResetType, Response, ResponseMismatch};
+/// let request = Request::EcuReset(EcuResetRequest::new(false, ResetType::HardReset));
+/// let (hard_reset, _) = Response::decode(&[0x51, 0x01])?;
+/// let (soft_reset, _) = Response::decode(&[0x51, 0x03])?;
+/// assert!(request.matches_response(&hard_reset));
+/// assert_eq!(
+///     request.response_expectation().check(&soft_reset),
+///     Err(ResponseMismatch::SubFunction { expected: 0x01, actual: 0x03 })
+/// );
+/// # Ok::<(), uds_protocol::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResponseExpectation<'a> {
+    request_sid: u8,
+    echo: Echo,
+    echo_bytes: [u8; MAX_ECHO_LEN],
+    read_data_request: Option<ReadDataByIdentifierRequest<'a>>,
+    allowed_nack_codes: Option<&'static [NegativeResponseCode]>,
+    suppress_positive_response: bool,
+}
+
+impl<'a> ResponseExpectation<'a> {
+    fn new(request: &Request<'a>) -> Self {
+        let prefix = encoded_prefix(request);
+        let request_sid = match request {
+            Request::Other { sid, .. } => *sid,
+            other => other.service().to_request_sid(),
+        };
+        let mut echo = Echo::for_service(request.service());
+        let mut echo_bytes = [0; MAX_ECHO_LEN];
+        match prefix.as_ref().and_then(|p| p.prefix().get(1..=echo.len())) {
+            Some(bytes) => echo_bytes[..bytes.len()].copy_from_slice(bytes),
+            None => echo = Echo::None,
+        }
+        if echo.has_sub_function() {
+            echo_bytes[0] &= SPRMIB_VALUE_MASK;
+        }
+        Self {
+            request_sid,
+            echo,
+            echo_bytes,
+            read_data_request: match request {
+                Request::ReadDataByIdentifier(read) => Some(*read),
+                _ => None,
+            },
+            allowed_nack_codes: service_nack_codes(request),
+            suppress_positive_response: request.suppress_positive_response(),
+        }
+    }
+
+    /// The SID of the request.
+    #[must_use]
+    pub const fn request_sid(&self) -> u8 {
+        self.request_sid
+    }
+
+    /// The SID of a positive response: the request SID + 0x40.
+    #[must_use]
+    pub const fn response_sid(&self) -> u8 {
+        self.request_sid | RESPONSE_SID_BIT
+    }
+
+    /// The sub-function the response must echo, without the suppress-positive-response bit.
+    #[must_use]
+    pub const fn sub_function(&self) -> Option<u8> {
+        if self.echo.has_sub_function() {
+            Some(self.echo_bytes[0])
+        } else {
+            None
+        }
+    }
+
+    /// The data or routine identifier the response must echo; the first requested DID for
+    /// `ReadDataByIdentifier`, whose response may start with any requested DID.
+    #[must_use]
+    pub const fn identifier(&self) -> Option<u16> {
+        match self.echo {
+            Echo::Identifier => Some(u16::from_be_bytes([self.echo_bytes[0], self.echo_bytes[1]])),
+            Echo::SubFunctionAndIdentifier => {
+                Some(u16::from_be_bytes([self.echo_bytes[1], self.echo_bytes[2]]))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `TransferData` block sequence counter the response must echo.
+    #[must_use]
+    pub const fn block_sequence_counter(&self) -> Option<u8> {
+        match self.echo {
+            Echo::BlockSequenceCounter => Some(self.echo_bytes[0]),
+            _ => None,
+        }
+    }
+
+    /// The service-specific negative response codes, or `None` for an unrecognized service,
+    /// whose negative responses are not restricted.
+    #[must_use]
+    pub const fn allowed_nack_codes(&self) -> Option<&'static [NegativeResponseCode]> {
+        self.allowed_nack_codes
+    }
+
+    /// Whether the request suppresses its positive response (SPRMIB).
+    #[must_use]
+    pub const fn suppress_positive_response(&self) -> bool {
+        self.suppress_positive_response
+    }
+
+    /// Whether the server sends a response when the request ends in `outcome`: `None` for
+    /// success, or the negative response code it fails with.
+    ///
+    /// A positive response is sent unless suppressed. A negative response is sent even when
+    /// the positive response is suppressed, except that a functionally addressed request gets
+    /// no `serviceNotSupported`, `subFunctionNotSupported`, `requestOutOfRange`,
+    /// `subFunctionNotSupportedInActiveSession` or `serviceNotSupportedInActiveSession`.
+    /// NRCs such as `requestCorrectlyReceivedResponsePending`, `busyRepeatRequest` and
+    /// `conditionsNotCorrect` are always sent.
+    ///
+    /// `response_pending` is whether the server has already sent
+    /// `requestCorrectlyReceivedResponsePending` (see
+    /// [`awaits_final_response`](Self::awaits_final_response)). The final response then
+    /// always follows, including a positive response that was asked to be suppressed.
+    #[must_use]
+    pub fn is_response_sent(
+        &self,
+        outcome: Option<NegativeResponseCode>,
+        functionally_addressed: bool,
+        response_pending: bool,
+    ) -> bool {
+        if response_pending {
+            return true;
+        }
+        match outcome {
+            None => !self.suppress_positive_response,
+            Some(nrc) => {
+                !functionally_addressed
+                    || !FUNCTIONALLY_SUPPRESSED_NEGATIVE_RESPONSE_CODES.contains(&nrc)
+            }
+        }
+    }
+
+    /// Whether another response to the request follows `response`.
+    ///
+    /// True for `requestCorrectlyReceivedResponsePending` (0x78) naming the request's service:
+    /// the server then sends a final response, even if the positive response is suppressed.
+    #[must_use]
+    pub fn awaits_final_response(&self, response: &Response<'_>) -> bool {
+        matches!(
+            response,
+            Response::NegativeResponse(negative)
+                if negative.request_service_sid() == self.request_sid
+                    && negative.nrc() == NegativeResponseCode::RequestCorrectlyReceivedResponsePending
+        )
+    }
+
+    /// Whether `response` answers the request.
+    #[must_use]
+    pub fn matches(&self, response: &Response<'_>) -> bool {
+        self.check(response).is_ok()
+    }
+
+    /// Check that `response` answers the request.
+    ///
+    /// # Errors
+    /// Returns the first [`ResponseMismatch`] found.
+    pub fn check(&self, response: &Response<'_>) -> Result<(), ResponseMismatch> {
+        if let Response::NegativeResponse(negative) = response {
+            return self.check_negative(negative.request_service_sid(), negative.nrc());
+        }
+        let prefix = encoded_prefix(response).ok_or(ResponseMismatch::MissingEcho)?;
+        let (&actual_sid, echoed) = prefix
+            .prefix()
+            .split_first()
+            .ok_or(ResponseMismatch::MissingEcho)?;
+        if actual_sid != self.response_sid() {
+            return Err(ResponseMismatch::ServiceIdentifier {
+                expected: self.response_sid(),
+                actual: actual_sid,
+            });
+        }
+        let echoed = echoed
+            .get(..self.echo.len())
+            .ok_or(ResponseMismatch::MissingEcho)?;
+        let mut identifier_at = 0;
+        if self.echo.has_sub_function() {
+            let actual = echoed[0] & SPRMIB_VALUE_MASK;
+            if actual != self.echo_bytes[0] {
+                return Err(ResponseMismatch::SubFunction {
+                    expected: self.echo_bytes[0],
+                    actual,
+                });
+            }
+            identifier_at = 1;
+        }
+        if let Some(expected) = self.identifier() {
+            let actual = u16::from_be_bytes([echoed[identifier_at], echoed[identifier_at + 1]]);
+            let requested = self.read_data_request.map_or(actual == expected, |read| {
+                read.dids().any(|did| did == actual)
+            });
+            if !requested {
+                return Err(ResponseMismatch::Identifier { expected, actual });
+            }
+        }
+        if let Some(expected) = self.block_sequence_counter() {
+            if echoed[0] != expected {
+                return Err(ResponseMismatch::BlockSequenceCounter {
+                    expected,
+                    actual: echoed[0],
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_negative(
+        &self,
+        actual: u8,
+        nrc: NegativeResponseCode,
+    ) -> Result<(), ResponseMismatch> {
+        if actual != self.request_sid {
+            return Err(ResponseMismatch::NegativeResponseService {
+                expected: self.request_sid,
+                actual,
+            });
+        }
+        let allowed = self.allowed_nack_codes.is_none_or(|codes| {
+            codes.contains(&nrc) || GENERAL_NEGATIVE_RESPONSE_CODES.contains(&nrc)
+        });
+        if !allowed {
+            return Err(ResponseMismatch::NegativeResponseCode(nrc));
+        }
+        Ok(())
+    }
+}
+
+fn service_nack_codes(request: &Request<'_>) -> Option<&'static [NegativeResponseCode]> {
+    Some(match request {
+        Request::AccessTimingParameters(_) => AccessTimingParametersRequest::allowed_nack_codes(),
+        Request::Authentication(_) => AuthenticationRequest::allowed_nack_codes(),
+        Request::ClearDiagnosticInfo(_) => ClearDiagnosticInfoRequest::allowed_nack_codes(),
+        Request::CommunicationControl(_) => CommunicationControlRequest::allowed_nack_codes(),
+        Request::ControlDtcSetting(_) => ControlDtcSettingRequest::allowed_nack_codes(),
+        Request::DiagnosticSessionControl(_) => {
+            DiagnosticSessionControlRequest::allowed_nack_codes()
+        }
+        Request::DynamicallyDefinedDataIdentifier(_) => {
+            DynamicallyDefinedDataIdentifierRequest::allowed_nack_codes()
+        }
+        Request::EcuReset(_) => EcuResetRequest::allowed_nack_codes(),
+        Request::InputOutputControlByIdentifier(_) => {
+            InputOutputControlByIdentifierRequest::allowed_nack_codes()
+        }
+        Request::LinkControl(_) => LinkControlRequest::allowed_nack_codes(),
+        Request::ReadDataByIdentifier(_) => ReadDataByIdentifierRequest::allowed_nack_codes(),
+        Request::ReadDataByIdentifierPeriodic(_) => {
+            ReadDataByPeriodicIdentifierRequest::allowed_nack_codes()
+        }
+        Request::ReadDtcInfo(_) => ReadDtcInfoRequest::allowed_nack_codes(),
+        Request::ReadMemoryByAddress(_) => ReadMemoryByAddressRequest::allowed_nack_codes(),
+        Request::ReadScalingDataByIdentifier(_) => {
+            ReadScalingDataByIdentifierRequest::allowed_nack_codes()
+        }
+        Request::RequestDownload(_) => RequestDownloadRequest::allowed_nack_codes(),
+        Request::RequestFileTransfer(_) => RequestFileTransferRequest::allowed_nack_codes(),
+        Request::RequestTransferExit(_) => RequestTransferExitRequest::allowed_nack_codes(),
+        Request::RequestUpload(_) => RequestUploadRequest::allowed_nack_codes(),
+        Request::ResponseOnEvent(_) => ResponseOnEventRequest::allowed_nack_codes(),
+        Request::RoutineControl(_) => RoutineControlRequest::allowed_nack_codes(),
+        Request::SecuredDataTransmission(_) => SecuredDataTransmissionRequest::allowed_nack_codes(),
+        Request::SecurityAccess(_) => SecurityAccessRequest::allowed_nack_codes(),
+        Request::TesterPresent(_) => TesterPresentRequest::allowed_nack_codes(),
+        Request::TransferData(_) => TransferDataRequest::allowed_nack_codes(),
+        Request::WriteDataByIdentifier(_) => WriteDataByIdentifierRequest::allowed_nack_codes(),
+        Request::WriteMemoryByAddress(_) => WriteMemoryByAddressRequest::allowed_nack_codes(),
+        Request::Other { .. } => return None,
+    })
+}
+
+impl<'a> Request<'a> {
+    /// What a response must look like to answer this request.
+    #[must_use]
+    pub fn response_expectation(&self) -> ResponseExpectation<'a> {
+        ResponseExpectation::new(self)
+    }
+
+    /// Whether `response` answers this request; see [`ResponseExpectation`].
+    #[must_use]
+    pub fn matches_response(&self, response: &Response<'_>) -> bool {
+        self.response_expectation().matches(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Decode, DtcSettingType, NegativeResponse, ResetType, RoutineControlSubFunction};
+
+    fn response(frame: &[u8]) -> Response<'_> {
+        Response::decode_exact(frame).unwrap()
+    }
+
+    #[test]
+    fn checks_response_sid() {
+        let request = Request::EcuReset(EcuResetRequest::new(false, ResetType::HardReset));
+        assert_eq!(
+            request
+                .response_expectation()
+                .check(&response(&[0x50, 0x01, 0x00, 0x32, 0x01, 0xF4])),
+            Err(ResponseMismatch::ServiceIdentifier {
+                expected: 0x51,
+                actual: 0x50
+            })
+        );
+    }
+
+    #[test]
+    fn sub_function_echo_ignores_suppress_bit() {
+        let request =
+            Request::ControlDtcSetting(ControlDtcSettingRequest::new(true, DtcSettingType::Off));
+        let expectation = request.response_expectation();
+        assert_eq!(expectation.sub_function(), Some(0x02));
+        assert!(request.matches_response(&response(&[0xC5, 0x02])));
+        assert_eq!(
+            expectation.check(&response(&[0xC5, 0x01])),
+            Err(ResponseMismatch::SubFunction {
+                expected: 0x02,
+                actual: 0x01
+            })
+        );
+    }
+
+    #[test]
+    fn checks_echoed_identifiers() {
+        let request =
+            Request::ReadDataByIdentifier(ReadDataByIdentifierRequest::new(&[0xF190, 0xF186]));
+        assert_eq!(request.response_expectation().identifier(), Some(0xF190));
+        assert!(request.matches_response(&response(&[0x62, 0xF1, 0x90, b'W'])));
+        assert_eq!(
+            request
+                .response_expectation()
+                .check(&response(&[0x62, 0xF1, 0x87, 0x01])),
+            Err(ResponseMismatch::Identifier {
+                expected: 0xF190,
+                actual: 0xF187
+            })
+        );
+        assert_eq!(
+            request.response_expectation().check(&response(&[0x62])),
+            Err(ResponseMismatch::MissingEcho)
+        );
+
+        let routine = Request::RoutineControl(RoutineControlRequest::new(
+            false,
+            RoutineControlSubFunction::StartRoutine,
+            0xFF00,
+            &[],
+        ));
+        let expectation = routine.response_expectation();
+        assert_eq!(expectation.sub_function(), Some(0x01));
+        assert_eq!(expectation.identifier(), Some(0xFF00));
+        assert!(expectation.matches(&response(&[0x71, 0x01, 0xFF, 0x00])));
+        assert!(!expectation.matches(&response(&[0x71, 0x02, 0xFF, 0x00])));
+        assert!(!expectation.matches(&response(&[0x71, 0x01, 0xFF, 0x01])));
+    }
+
+    #[test]
+    fn read_data_response_may_omit_unsupported_dids() {
+        let request =
+            Request::ReadDataByIdentifier(ReadDataByIdentifierRequest::new(&[0xF190, 0xF186]));
+        // 0xF190 is unsupported and left out; the response starts with 0xF186.
+        assert_eq!(
+            request
+                .response_expectation()
+                .check(&response(&[0x62, 0xF1, 0x86, 0x01])),
+            Ok(())
+        );
+        assert!(request.matches_response(&response(&[0x62, 0xF1, 0x86, 0x01])));
+    }
+
+    #[test]
+    fn checks_block_sequence_counter() {
+        let request = Request::TransferData(TransferDataRequest::new(0x81, &[0xAA; 16]));
+        let expectation = request.response_expectation();
+        assert_eq!(expectation.block_sequence_counter(), Some(0x81));
+        assert!(expectation.matches(&response(&[0x76, 0x81])));
+        assert_eq!(
+            expectation.check(&response(&[0x76, 0x01])),
+            Err(ResponseMismatch::BlockSequenceCounter {
+                expected: 0x81,
+                actual: 0x01
+            })
+        );
+    }
+
+    #[test]
+    fn checks_negative_response_service_and_code() {
+        let request = Request::TesterPresent(TesterPresentRequest::new(false));
+        let expectation = request.response_expectation();
+        let nrc = |service, nrc| Response::NegativeResponse(NegativeResponse::new(service, nrc));
+        assert!(expectation.matches(&nrc(
+            UdsServiceType::TesterPresent,
+            NegativeResponseCode::SubFunctionNotSupported
+        )));
+        assert!(expectation.matches(&nrc(
+            UdsServiceType::TesterPresent,
+            NegativeResponseCode::RequestCorrectlyReceivedResponsePending
+        )));
+        assert_eq!(
+            expectation.check(&nrc(
+                UdsServiceType::TesterPresent,
+                NegativeResponseCode::InvalidKey
+            )),
+            Err(ResponseMismatch::NegativeResponseCode(
+                NegativeResponseCode::InvalidKey
+            ))
+        );
+        assert_eq!(
+            expectation.check(&nrc(
+                UdsServiceType::EcuReset,
+                NegativeResponseCode::SubFunctionNotSupported
+            )),
+            Err(ResponseMismatch::NegativeResponseService {
+                expected: 0x3E,
+                actual: 0x11
+            })
+        );
+    }
+
+    #[test]
+    fn suppressed_request_still_gets_negative_responses() {
+        let request = Request::TesterPresent(TesterPresentRequest::new(true));
+        let expectation = request.response_expectation();
+        assert!(expectation.suppress_positive_response());
+        assert!(!expectation.is_response_sent(None, false, false));
+        for nrc in [
+            NegativeResponseCode::RequestCorrectlyReceivedResponsePending,
+            NegativeResponseCode::BusyRepeatRequest,
+            NegativeResponseCode::ConditionsNotCorrect,
+        ] {
+            assert!(expectation.is_response_sent(Some(nrc), false, false));
+            assert!(expectation.is_response_sent(Some(nrc), true, false));
+        }
+        let sfns = Some(NegativeResponseCode::SubFunctionNotSupported);
+        assert!(expectation.is_response_sent(sfns, false, false));
+        assert!(!expectation.is_response_sent(sfns, true, false));
+
+        let not_suppressed = Request::TesterPresent(TesterPresentRequest::new(false));
+        assert!(
+            not_suppressed
+                .response_expectation()
+                .is_response_sent(None, true, false)
+        );
+    }
+
+    #[test]
+    fn response_pending_awaits_final_response() {
+        let request = Request::RoutineControl(RoutineControlRequest::new(
+            true,
+            RoutineControlSubFunction::StartRoutine,
+            0xFF00,
+            &[],
+        ));
+        let expectation = request.response_expectation();
+        assert!(expectation.awaits_final_response(&response(&[0x7F, 0x31, 0x78])));
+        assert!(!expectation.awaits_final_response(&response(&[0x7F, 0x31, 0x22])));
+        assert!(!expectation.awaits_final_response(&response(&[0x7F, 0x10, 0x78])));
+        assert!(!expectation.awaits_final_response(&response(&[0x71, 0x01, 0xFF, 0x00])));
+
+        // Once the server has sent 0x78, the final response follows despite SPRMIB.
+        assert!(!expectation.is_response_sent(None, false, false));
+        assert!(expectation.is_response_sent(None, false, true));
+        let out_of_range = Some(NegativeResponseCode::RequestOutOfRange);
+        assert!(expectation.is_response_sent(out_of_range, true, true));
+    }
+
+    #[test]
+    fn unrecognized_service_checks_sid_only() {
+        let (request, _) = Request::decode(&[0x01, 0x0C]).unwrap();
+        let expectation = request.response_expectation();
+        assert_eq!(expectation.response_sid(), 0x41);
+        assert_eq!(expectation.allowed_nack_codes(), None);
+        assert!(expectation.matches(&response(&[0x41, 0x0C, 0x1A, 0xF8])));
+        assert!(!expectation.matches(&response(&[0x42, 0x0C])));
+        assert!(expectation.matches(&response(&[0x7F, 0x01, 0x31])));
+    }
+}
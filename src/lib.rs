@@ -16,38 +16,72 @@ pub use automotive_wire_codec::{Decode, DecodeIter, Encode};
 
 mod dtc;
 pub use dtc::{
-    CLEAR_ALL_DTCS, DtcExtDataRecordNumber, DtcFormatIdentifier, DtcRecord, DtcSeverityMask,
-    DtcSnapshotRecordNumber, DtcStatusMask, DtcStoredDataRecordNumber, FunctionalGroupIdentifier,
+    CLEAR_ALL_DTCS, DtcExtDataRecordNumber, DtcFormatIdentifier, DtcRecord, DtcRecordDisplay,
+    DtcSeverityMask, DtcSnapshotRecordNumber, DtcStatusMask, DtcStoredDataRecordNumber,
+    FailureModeIdentifier, FunctionalGroupIdentifier, J1939Dtc,
 };
 
 mod shared;
-pub use shared::{DataFormatIdentifier, NegativeResponseCode, UdsIdentifier, UdsRoutineIdentifier};
+pub use shared::{
+    ActiveDiagnosticSession, ApplicationDataIdentification, ApplicationSoftwareIdentification,
+    BootSoftwareIdentification, CalibrationDate, DataFormatIdentifier, DateEncoding,
+    EcuInstallationDate, EcuManufacturingDate, IdentificationDate, NegativeResponseCode,
+    ProgrammingDate, SoftwareIdentificationList, UdsIdentifier, UdsRoutineIdentifier, Vin,
+};
 
 mod request;
+#[cfg(feature = "alloc")]
+pub use request::OwnedRequest;
 pub use request::Request;
 
 mod response;
+#[cfg(feature = "alloc")]
+pub use response::OwnedResponse;
 pub use response::Response;
 
 mod service;
 pub use service::UdsServiceType;
 
+mod expectation;
+pub use expectation::{ResponseExpectation, ResponseMismatch};
+
 mod services;
 pub use services::{
-    ClearDiagnosticInfoRequest, ClearDiagnosticInfoResponse, CommunicationControlRequest,
-    CommunicationControlResponse, CommunicationControlType, CommunicationType,
-    ControlDtcSettingRequest, ControlDtcSettingResponse, DiagnosticSessionControlRequest,
-    DiagnosticSessionControlResponse, DiagnosticSessionType, DirSizePayload, DtcAndStatusIter,
+    AccessTimingParametersRequest, AccessTimingParametersResponse, ActivatedEventIter,
+    AdministrativeParameter, AuthenticationRequest, AuthenticationRequestParameters,
+    AuthenticationResponse, AuthenticationResponseParameters, AuthenticationReturnParameter,
+    AuthenticationType, ClearDiagnosticInfoRequest, ClearDiagnosticInfoResponse,
+    CommunicationControlRequest, CommunicationControlResponse, CommunicationControlType,
+    CommunicationType, ComparisonLogic, ComparisonOfValuesRecord, ControlDtcSettingRequest,
+    ControlDtcSettingResponse, DataRecordIter, DiagnosticSessionControlRequest,
+    DiagnosticSessionControlResponse, DiagnosticSessionType, DidCodec, DidRegistry, DirSizePayload,
+    DtcAndExtDataIter, DtcAndStatusIter, DtcExtDataIter, DtcExtDataRegistry,
     DtcFaultDetectionCounterRecord, DtcFaultDetectionIter, DtcSettingType,
-    DtcSeverityAndStatusIter, EcuResetRequest, EcuResetResponse, FileOperationMode,
-    FileSizePayload, NamePayload, NegativeResponse, PositionPayload, ReadDataByIdentifierRequest,
-    ReadDataByIdentifierResponse, ReadDtcInfoRequest, ReadDtcInfoResponse, ReadDtcInfoSubFunction,
+    DtcSeverityAndStatusIter, DtcSeverityFunctionalUnitIter, DtcSnapshotIdentificationIter,
+    DtcSnapshotRecord, DtcSnapshotRecordIter, DtcStoredDataRecord, DtcStoredDataRecordIter,
+    DynamicDefinitionType, DynamicallyDefinedDataIdentifierRequest,
+    DynamicallyDefinedDataIdentifierResponse, EcuResetRequest, EcuResetResponse,
+    EventConfiguration, EventType, EventTypeRecord, EventWindowTime, FileOperationMode,
+    FileSizePayload, FormulaConstant, FormulaIdentifier, InputOutputControlByIdentifierRequest,
+    InputOutputControlByIdentifierResponse, InputOutputControlParameter, LinkControlModeIdentifier,
+    LinkControlRequest, LinkControlResponse, LinkControlType, MemoryAddressDefinition,
+    MemoryAddressDefinitionIter, NamePayload, NegativeResponse, PeriodicDataFrame,
+    PeriodicIdentifier, PositionPayload, ReadDataByIdentifierBatches, ReadDataByIdentifierBuilder,
+    ReadDataByIdentifierRequest, ReadDataByIdentifierResponse, ReadDataByPeriodicIdentifierRequest,
+    ReadDataByPeriodicIdentifierResponse, ReadDtcInfoRequest, ReadDtcInfoResponse,
+    ReadDtcInfoSubFunction, ReadMemoryByAddressRequest, ReadMemoryByAddressResponse,
+    ReadScalingDataByIdentifierRequest, ReadScalingDataByIdentifierResponse,
     RequestDownloadRequest, RequestDownloadResponse, RequestFileTransferRequest,
     RequestFileTransferResponse, RequestTransferExitRequest, RequestTransferExitResponse,
-    ResetType, RoutineControlRequest, RoutineControlResponse, RoutineControlSubFunction,
+    RequestUploadRequest, RequestUploadResponse, ResetType, ResponseOnEventRequest,
+    ResponseOnEventResponse, RoutineControlRequest, RoutineControlResponse,
+    RoutineControlSubFunction, ScalingByteExtension, ScalingDataType, ScalingRecord,
+    ScalingRecordIter, SecuredDataTransmissionRequest, SecuredDataTransmissionResponse,
     SecurityAccessLevel, SecurityAccessRequest, SecurityAccessResponse, SecurityAccessType,
-    SentDataPayload, SizePayload, TesterPresentRequest, TesterPresentResponse, TransferDataRequest,
-    TransferDataResponse, WriteDataByIdentifierRequest, WriteDataByIdentifierResponse,
+    SentDataPayload, SizePayload, SourceDataIdentifierDefinition, SourceDataIdentifierIter,
+    TesterPresentRequest, TesterPresentResponse, TimingParameterAccessType, TransferDataRequest,
+    TransferDataResponse, TransmissionMode, WriteDataByIdentifierRequest,
+    WriteDataByIdentifierResponse, WriteMemoryByAddressRequest, WriteMemoryByAddressResponse,
 };
 
 #[cfg(test)]
@@ -2,13 +2,20 @@
 use crate::{
     Decode, Encode, Error, Incomplete,
     services::{
-        ClearDiagnosticInfoRequest, CommunicationControlRequest, ControlDtcSettingRequest,
-        DiagnosticSessionControlRequest, EcuResetRequest, ReadDataByIdentifierRequest,
-        ReadDtcInfoRequest, RequestDownloadRequest, RequestFileTransferRequest,
-        RequestTransferExitRequest, RoutineControlRequest, SecurityAccessRequest,
+        AccessTimingParametersRequest, AuthenticationRequest, ClearDiagnosticInfoRequest,
+        CommunicationControlRequest, ControlDtcSettingRequest, DiagnosticSessionControlRequest,
+        DynamicallyDefinedDataIdentifierRequest, EcuResetRequest,
+        InputOutputControlByIdentifierRequest, LinkControlRequest, ReadDataByIdentifierRequest,
+        ReadDataByPeriodicIdentifierRequest, ReadDtcInfoRequest, ReadMemoryByAddressRequest,
+        ReadScalingDataByIdentifierRequest, RequestDownloadRequest, RequestFileTransferRequest,
+        RequestTransferExitRequest, RequestUploadRequest, ResponseOnEventRequest,
+        RoutineControlRequest, SecuredDataTransmissionRequest, SecurityAccessRequest,
         TesterPresentRequest, TransferDataRequest, WriteDataByIdentifierRequest,
+        WriteMemoryByAddressRequest,
     },
 };
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use automotive_wire_codec::{write_all, write_u8};
 
 use super::service::UdsServiceType;
@@ -20,6 +27,10 @@ use super::service::UdsServiceType;
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum Request<'a> {
+    /// Access timing parameters request.
+    AccessTimingParameters(AccessTimingParametersRequest<'a>),
+    /// Authentication request.
+    Authentication(AuthenticationRequest<'a>),
     /// Clear diagnostic information request.
     ClearDiagnosticInfo(ClearDiagnosticInfoRequest),
     /// Communication control request.
@@ -28,20 +39,38 @@ pub enum Request<'a> {
     ControlDtcSetting(ControlDtcSettingRequest),
     /// Diagnostic session control request.
     DiagnosticSessionControl(DiagnosticSessionControlRequest),
+    /// Dynamically defined data identifier request.
+    DynamicallyDefinedDataIdentifier(DynamicallyDefinedDataIdentifierRequest<'a>),
     /// ECU reset request.
     EcuReset(EcuResetRequest),
+    /// Input/output control by identifier request.
+    InputOutputControlByIdentifier(InputOutputControlByIdentifierRequest<'a>),
+    /// Link control request.
+    LinkControl(LinkControlRequest<'a>),
     /// Read data by identifier request.
     ReadDataByIdentifier(ReadDataByIdentifierRequest<'a>),
+    /// Read data by periodic identifier request.
+    ReadDataByIdentifierPeriodic(ReadDataByPeriodicIdentifierRequest<'a>),
     /// Read DTC information request.
     ReadDtcInfo(ReadDtcInfoRequest),
+    /// Read memory by address request.
+    ReadMemoryByAddress(ReadMemoryByAddressRequest),
+    /// Request scaling information for a data identifier
+    ReadScalingDataByIdentifier(ReadScalingDataByIdentifierRequest),
     /// Request download.
     RequestDownload(RequestDownloadRequest),
     /// Request file transfer.
     RequestFileTransfer(RequestFileTransferRequest<'a>),
     /// Request transfer exit.
     RequestTransferExit(RequestTransferExitRequest<'a>),
+    /// Request upload.
+    RequestUpload(RequestUploadRequest),
+    /// Response on event request.
+    ResponseOnEvent(ResponseOnEventRequest<'a>),
     /// Routine control request.
     RoutineControl(RoutineControlRequest<'a>),
+    /// Secured data transmission request.
+    SecuredDataTransmission(SecuredDataTransmissionRequest<'a>),
     /// Security access request.
     SecurityAccess(SecurityAccessRequest<'a>),
     /// Tester present request.
@@ -50,7 +79,9 @@ pub enum Request<'a> {
     TransferData(TransferDataRequest<'a>),
     /// Write data by identifier request.
     WriteDataByIdentifier(WriteDataByIdentifierRequest<'a>),
-    /// A known-but-unmodeled (or unrecognized) service. Carries the raw service byte and
+    /// Write memory by address request.
+    WriteMemoryByAddress(WriteMemoryByAddressRequest<'a>),
+    /// An unrecognized service. Carries the raw service byte and
     /// the raw payload bytes following the service identifier, for pass-through.
     ///
     /// Re-encoding is lossless for every service byte: the raw `sid` is echoed verbatim.
@@ -76,6 +107,12 @@ impl<'a> Decode<'a> for Request<'a> {
         let payload = &buf[1..];
 
         let request = match service {
+            UdsServiceType::AccessTimingParameters => Self::AccessTimingParameters(
+                <AccessTimingParametersRequest as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::Authentication => {
+                Self::Authentication(<AuthenticationRequest as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::ClearDiagnosticInfo => Self::ClearDiagnosticInfo(
                 <ClearDiagnosticInfoRequest as Decode>::decode_exact(payload)?,
             ),
@@ -88,15 +125,35 @@ impl<'a> Decode<'a> for Request<'a> {
             UdsServiceType::DiagnosticSessionControl => Self::DiagnosticSessionControl(
                 <DiagnosticSessionControlRequest as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::DynamicallyDefinedDataIdentifier => {
+                Self::DynamicallyDefinedDataIdentifier(
+                    <DynamicallyDefinedDataIdentifierRequest as Decode>::decode_exact(payload)?,
+                )
+            }
             UdsServiceType::EcuReset => {
                 Self::EcuReset(<EcuResetRequest as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::InputOutputControlByIdentifier => Self::InputOutputControlByIdentifier(
+                <InputOutputControlByIdentifierRequest as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::LinkControl => {
+                Self::LinkControl(<LinkControlRequest as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::ReadDataByIdentifier => Self::ReadDataByIdentifier(
                 <ReadDataByIdentifierRequest as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::ReadDataByIdentifierPeriodic => Self::ReadDataByIdentifierPeriodic(
+                <ReadDataByPeriodicIdentifierRequest as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::ReadDtcInfo => {
                 Self::ReadDtcInfo(<ReadDtcInfoRequest as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::ReadMemoryByAddress => Self::ReadMemoryByAddress(
+                <ReadMemoryByAddressRequest as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::ReadScalingDataByIdentifier => Self::ReadScalingDataByIdentifier(
+                <ReadScalingDataByIdentifierRequest as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::RequestDownload => {
                 Self::RequestDownload(<RequestDownloadRequest as Decode>::decode_exact(payload)?)
             }
@@ -106,9 +163,18 @@ impl<'a> Decode<'a> for Request<'a> {
             UdsServiceType::RequestTransferExit => Self::RequestTransferExit(
                 <RequestTransferExitRequest as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::RequestUpload => {
+                Self::RequestUpload(<RequestUploadRequest as Decode>::decode_exact(payload)?)
+            }
+            UdsServiceType::ResponseOnEvent => {
+                Self::ResponseOnEvent(<ResponseOnEventRequest as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::RoutineControl => {
                 Self::RoutineControl(<RoutineControlRequest as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::SecuredDataTransmission => Self::SecuredDataTransmission(
+                <SecuredDataTransmissionRequest as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::SecurityAccess => {
                 Self::SecurityAccess(<SecurityAccessRequest as Decode>::decode_exact(payload)?)
             }
@@ -121,6 +187,9 @@ impl<'a> Decode<'a> for Request<'a> {
             UdsServiceType::WriteDataByIdentifier => Self::WriteDataByIdentifier(
                 <WriteDataByIdentifierRequest as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::WriteMemoryByAddress => Self::WriteMemoryByAddress(
+                <WriteMemoryByAddressRequest as Decode>::decode_exact(payload)?,
+            ),
             _ => Self::Other {
                 sid: buf[0],
                 data: payload,
@@ -147,10 +216,22 @@ impl Encode for Request<'_> {
             Self::EcuReset(req) => req.encode(writer)?,
             Self::ReadDataByIdentifier(req) => req.encode(writer)?,
             Self::ReadDtcInfo(req) => req.encode(writer)?,
+            Self::ReadMemoryByAddress(req) => req.encode(writer)?,
             Self::WriteDataByIdentifier(req) => req.encode(writer)?,
             Self::RequestDownload(req) => req.encode(writer)?,
             Self::RequestFileTransfer(req) => req.encode(writer)?,
             Self::RequestTransferExit(req) => req.encode(writer)?,
+            Self::WriteMemoryByAddress(req) => req.encode(writer)?,
+            Self::RequestUpload(req) => req.encode(writer)?,
+            Self::Authentication(req) => req.encode(writer)?,
+            Self::InputOutputControlByIdentifier(req) => req.encode(writer)?,
+            Self::DynamicallyDefinedDataIdentifier(req) => req.encode(writer)?,
+            Self::ReadDataByIdentifierPeriodic(req) => req.encode(writer)?,
+            Self::ResponseOnEvent(req) => req.encode(writer)?,
+            Self::LinkControl(req) => req.encode(writer)?,
+            Self::AccessTimingParameters(req) => req.encode(writer)?,
+            Self::SecuredDataTransmission(req) => req.encode(writer)?,
+            Self::ReadScalingDataByIdentifier(req) => req.encode(writer)?,
             Self::Other { data, .. } => write_all(writer, data).map_err(Error::io)?,
             Self::RoutineControl(req) => req.encode(writer)?,
             Self::SecurityAccess(req) => req.encode(writer)?,
@@ -163,13 +244,27 @@ impl Encode for Request<'_> {
 
 impl Request<'_> {
     /// Whether the positive response for this request is suppressed (SPRMIB).
+    #[deprecated(note = "renamed to `suppress_positive_response`")]
     #[must_use]
     pub fn is_positive_response_suppressed(&self) -> bool {
+        self.suppress_positive_response()
+    }
+
+    /// Whether the positive response for this request is suppressed (SPRMIB).
+    ///
+    /// Always `false` for services without a sub-function.
+    #[must_use]
+    pub fn suppress_positive_response(&self) -> bool {
         match self {
+            Self::AccessTimingParameters(req) => req.suppress_positive_response,
+            Self::Authentication(req) => req.suppress_positive_response,
             Self::CommunicationControl(req) => req.suppress_positive_response(),
             Self::ControlDtcSetting(req) => req.suppress_positive_response,
             Self::DiagnosticSessionControl(req) => req.suppress_positive_response,
+            Self::DynamicallyDefinedDataIdentifier(req) => req.suppress_positive_response,
             Self::EcuReset(req) => req.suppress_positive_response,
+            Self::LinkControl(req) => req.suppress_positive_response,
+            Self::ResponseOnEvent(req) => req.suppress_positive_response,
             Self::RoutineControl(req) => req.suppress_positive_response,
             Self::SecurityAccess(req) => req.suppress_positive_response,
             Self::TesterPresent(req) => req.suppress_positive_response,
@@ -177,30 +272,143 @@ impl Request<'_> {
         }
     }
 
+    /// Set the suppress-positive-response bit (SPRMIB) of this request's sub-function.
+    ///
+    /// # Errors
+    /// Returns [`Error::SuppressPositiveResponseNotSupported`] if the service has no
+    /// sub-function to carry the bit.
+    pub fn set_suppress_positive_response(&mut self, suppress: bool) -> Result<(), Error> {
+        match self {
+            Self::AccessTimingParameters(req) => req.suppress_positive_response = suppress,
+            Self::Authentication(req) => req.suppress_positive_response = suppress,
+            Self::CommunicationControl(req) => req.set_suppress_positive_response(suppress),
+            Self::ControlDtcSetting(req) => req.suppress_positive_response = suppress,
+            Self::DiagnosticSessionControl(req) => req.suppress_positive_response = suppress,
+            Self::DynamicallyDefinedDataIdentifier(req) => {
+                req.suppress_positive_response = suppress;
+            }
+            Self::EcuReset(req) => req.suppress_positive_response = suppress,
+            Self::LinkControl(req) => req.suppress_positive_response = suppress,
+            Self::ResponseOnEvent(req) => req.suppress_positive_response = suppress,
+            Self::RoutineControl(req) => req.suppress_positive_response = suppress,
+            Self::SecurityAccess(req) => req.suppress_positive_response = suppress,
+            Self::TesterPresent(req) => req.suppress_positive_response = suppress,
+            other => return Err(Error::SuppressPositiveResponseNotSupported(other.service())),
+        }
+        Ok(())
+    }
+
     /// Returns the [`UdsServiceType`] corresponding to this request variant.
     #[must_use]
     pub fn service(&self) -> UdsServiceType {
         match self {
+            Self::AccessTimingParameters(_) => UdsServiceType::AccessTimingParameters,
+            Self::Authentication(_) => UdsServiceType::Authentication,
             Self::ClearDiagnosticInfo(_) => UdsServiceType::ClearDiagnosticInfo,
             Self::CommunicationControl(_) => UdsServiceType::CommunicationControl,
             Self::ControlDtcSetting(_) => UdsServiceType::ControlDtcSetting,
             Self::DiagnosticSessionControl(_) => UdsServiceType::DiagnosticSessionControl,
+            Self::DynamicallyDefinedDataIdentifier(_) => {
+                UdsServiceType::DynamicallyDefinedDataIdentifier
+            }
             Self::EcuReset(_) => UdsServiceType::EcuReset,
+            Self::InputOutputControlByIdentifier(_) => {
+                UdsServiceType::InputOutputControlByIdentifier
+            }
+            Self::LinkControl(_) => UdsServiceType::LinkControl,
             Self::ReadDataByIdentifier(_) => UdsServiceType::ReadDataByIdentifier,
+            Self::ReadDataByIdentifierPeriodic(_) => UdsServiceType::ReadDataByIdentifierPeriodic,
             Self::ReadDtcInfo(_) => UdsServiceType::ReadDtcInfo,
+            Self::ReadMemoryByAddress(_) => UdsServiceType::ReadMemoryByAddress,
+            Self::ReadScalingDataByIdentifier(_) => UdsServiceType::ReadScalingDataByIdentifier,
             Self::RequestDownload(_) => UdsServiceType::RequestDownload,
             Self::RequestFileTransfer(_) => UdsServiceType::RequestFileTransfer,
             Self::RequestTransferExit(_) => UdsServiceType::RequestTransferExit,
+            Self::RequestUpload(_) => UdsServiceType::RequestUpload,
+            Self::ResponseOnEvent(_) => UdsServiceType::ResponseOnEvent,
             Self::RoutineControl(_) => UdsServiceType::RoutineControl,
+            Self::SecuredDataTransmission(_) => UdsServiceType::SecuredDataTransmission,
             Self::SecurityAccess(_) => UdsServiceType::SecurityAccess,
             Self::TesterPresent(_) => UdsServiceType::TesterPresent,
             Self::TransferData(_) => UdsServiceType::TransferData,
             Self::WriteDataByIdentifier(_) => UdsServiceType::WriteDataByIdentifier,
+            Self::WriteMemoryByAddress(_) => UdsServiceType::WriteMemoryByAddress,
             Self::Other { sid, .. } => UdsServiceType::from_request_sid(*sid),
         }
     }
 }
 
+/// An owned request frame that can be stored or sent across tasks.
+///
+/// Holds the encoded frame, service byte included, and re-parses it on demand with
+/// [`as_borrowed`](Self::as_borrowed). The frame is validated on construction. A request
+/// decoded as [`Request::Other`] round-trips, but a hand-built `Other` whose `sid` belongs to a
+/// modeled service does not: its frame decodes as that service, or not at all.
+///
+/// ```
+/// use uds_protocol::{EcuResetRequest, OwnedRequest, Request, ResetType};
+/// let request = Request::EcuReset(EcuResetRequest::new(false, ResetType::HardReset));
+/// let owned = OwnedRequest::try_from(&request).unwrap();
+/// let handle = std::thread::spawn(move || owned.as_borrowed().unwrap().service());
+/// assert_eq!(handle.join().unwrap(), uds_protocol::UdsServiceType::EcuReset);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OwnedRequest {
+    frame: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedRequest {
+    /// Take ownership of an encoded request frame.
+    ///
+    /// # Errors
+    /// Returns the [`Request::decode_exact`](Decode::decode_exact) error if `frame` is not a
+    /// valid request.
+    pub fn from_bytes(frame: Vec<u8>) -> Result<Self, Error> {
+        Request::decode_exact(&frame)?;
+        Ok(Self { frame })
+    }
+
+    /// Borrow the frame as a zero-copy [`Request`], decoding it again on every call.
+    ///
+    /// # Errors
+    /// Returns the [`Request::decode_exact`](Decode::decode_exact) error of the frame. A frame
+    /// accepted by [`from_bytes`](Self::from_bytes) decodes the same way every time, so this
+    /// only fails if construction did.
+    pub fn as_borrowed(&self) -> Result<Request<'_>, Error> {
+        Request::decode_exact(&self.frame)
+    }
+
+    /// The encoded frame, service byte included.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.frame
+    }
+
+    /// Consume this value and return the encoded frame.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.frame
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&Request<'_>> for OwnedRequest {
+    type Error = Error;
+
+    /// Copy `value` into an owned frame that does not borrow the wire buffer.
+    ///
+    /// # Errors
+    /// Returns an error if `value` cannot be encoded, or if its encoding does not decode
+    /// back into a [`Request`].
+    fn try_from(value: &Request<'_>) -> Result<Self, Error> {
+        let mut frame = Vec::new();
+        value.encode(&mut frame)?;
+        Self::from_bytes(frame)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,6 +427,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn suppression_forwards_to_inner_request() {
         let suppressed = Request::EcuReset(EcuResetRequest::new(true, ResetType::HardReset));
         assert!(suppressed.is_positive_response_suppressed());
@@ -227,6 +436,36 @@ mod tests {
         assert!(!not_suppressed.is_positive_response_suppressed());
     }
 
+    #[test]
+    fn set_suppress_positive_response_updates_sub_function() {
+        let mut req = Request::CommunicationControl(
+            CommunicationControlRequest::new(
+                false,
+                crate::CommunicationControlType::EnableRxAndTx,
+                crate::CommunicationType::Normal,
+            )
+            .unwrap(),
+        );
+        req.set_suppress_positive_response(true).unwrap();
+        assert!(req.suppress_positive_response());
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x28, 0x80, 0x01]);
+
+        let mut req = Request::TesterPresent(TesterPresentRequest::new(true));
+        req.set_suppress_positive_response(false).unwrap();
+        assert!(!req.suppress_positive_response());
+
+        let mut req = Request::ReadDataByIdentifier(ReadDataByIdentifierRequest::new(&[0xF190]));
+        assert!(matches!(
+            req.set_suppress_positive_response(true),
+            Err(Error::SuppressPositiveResponseNotSupported(
+                UdsServiceType::ReadDataByIdentifier
+            ))
+        ));
+        assert!(!req.suppress_positive_response());
+    }
+
     #[test]
     fn write_data_by_identifier_request_roundtrips() {
         // SID 0x2E, DID 0xF190, one data byte 0x01
@@ -245,21 +484,230 @@ mod tests {
         let wire = [0x31, 0x81, 0xFF, 0x00, 0xAA];
         let (req, rest) = Request::decode(&wire).unwrap();
         assert!(rest.is_empty());
-        assert!(req.is_positive_response_suppressed());
+        assert!(req.suppress_positive_response());
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn read_memory_by_address_request_roundtrips() {
+        // SID 0x23, ALFID 0x24 (2-byte size, 4-byte address), address 0x20481392, size 0x0103
+        let wire = [0x23, 0x24, 0x20, 0x48, 0x13, 0x92, 0x01, 0x03];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::ReadMemoryByAddress(_)));
+        assert_eq!(req.service(), UdsServiceType::ReadMemoryByAddress);
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn write_memory_by_address_request_roundtrips() {
+        // SID 0x3D, ALFID 0x12 (1-byte size, 2-byte address), address 0x4000, size 2, data
+        let wire = [0x3D, 0x12, 0x40, 0x00, 0x02, 0xAA, 0xBB];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::WriteMemoryByAddress(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+
+        // A data record longer than memorySize is a malformed frame.
+        assert!(matches!(
+            Request::decode(&[0x3D, 0x12, 0x40, 0x00, 0x01, 0xAA, 0xBB]),
+            Err(Error::TrailingBytes(_))
+        ));
+    }
+
+    #[test]
+    fn request_upload_request_roundtrips() {
+        // SID 0x35, DFI 0x00, ALFID 0x14, address 0x08000000, size 0x80
+        let wire = [0x35, 0x00, 0x14, 0x08, 0x00, 0x00, 0x00, 0x80];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::RequestUpload(_)));
         let mut buf = [0u8; 8];
         let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
         assert_eq!(&buf[..written], &wire);
     }
 
+    #[test]
+    fn authentication_request_roundtrips() {
+        // SID 0x29, deAuthenticate with SPRMIB set
+        let wire = [0x29, 0x80];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::Authentication(_)));
+        assert!(req.suppress_positive_response());
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn input_output_control_request_roundtrips() {
+        // SID 0x2F, DID 0x9B00, shortTermAdjustment, controlState 0x3C
+        let wire = [0x2F, 0x9B, 0x00, 0x03, 0x3C];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::InputOutputControlByIdentifier(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn dynamically_defined_data_identifier_request_roundtrips() {
+        // SID 0x2C, defineByIdentifier DDDI 0xF301 from DID 0x010A position 1 size 2
+        let wire = [0x2C, 0x01, 0xF3, 0x01, 0x01, 0x0A, 0x01, 0x02];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::DynamicallyDefinedDataIdentifier(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn read_data_by_periodic_identifier_request_roundtrips() {
+        // SID 0x2A, sendAtFastRate, PDIDs 0xE3 and 0x24
+        let wire = [0x2A, 0x03, 0xE3, 0x24];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::ReadDataByIdentifierPeriodic(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn response_on_event_request_roundtrips() {
+        // SID 0x86, onChangeOfDataIdentifier 0x010D, respond with ReadDataByIdentifier 0x010D
+        let wire = [0x86, 0x03, 0x02, 0x01, 0x0D, 0x22, 0x01, 0x0D];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let Request::ResponseOnEvent(roe) = &req else {
+            panic!("expected ResponseOnEvent");
+        };
+        assert!(matches!(
+            roe.event.service_to_respond_to_request(),
+            Ok(Some(Request::ReadDataByIdentifier(_)))
+        ));
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn link_control_request_roundtrips_with_suppress_bit() {
+        // SID 0x87, SPRMIB + transitionMode
+        let wire = [0x87, 0x83];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::LinkControl(_)));
+        assert!(req.suppress_positive_response());
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn access_timing_parameters_request_roundtrips() {
+        // SID 0x83, setTimingParametersToGivenValues, P2 = 50 ms, P2* = 5000 ms
+        let wire = [0x83, 0x04, 0x00, 0x32, 0x01, 0xF4];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::AccessTimingParameters(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn secured_data_transmission_request_roundtrips() {
+        // SID 0x84 wrapping an unsigned TesterPresent request
+        let wire = [0x84, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x05, 0x3E, 0x00];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let Request::SecuredDataTransmission(secured) = &req else {
+            panic!("expected SecuredDataTransmission");
+        };
+        assert!(matches!(
+            secured.inner_request(),
+            Ok(Request::TesterPresent(_))
+        ));
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn read_scaling_data_by_identifier_request_roundtrips() {
+        // SID 0x24, DID 0xF190
+        let wire = [0x24, 0xF1, 0x90];
+        let (req, rest) = Request::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(req, Request::ReadScalingDataByIdentifier(_)));
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_request_outlives_wire_buffer() {
+        let owned = {
+            let frame = [0x01, 0xAA, 0xBB];
+            let (req, _) = Request::decode(&frame).unwrap();
+            OwnedRequest::try_from(&req).unwrap()
+        };
+        assert_eq!(owned.as_bytes(), &[0x01, 0xAA, 0xBB]);
+        assert!(matches!(
+            owned.as_borrowed().unwrap(),
+            Request::Other {
+                sid: 0x01,
+                data: &[0xAA, 0xBB]
+            }
+        ));
+
+        let owned = OwnedRequest::try_from(&Request::EcuReset(EcuResetRequest::new(
+            true,
+            ResetType::SoftReset,
+        )))
+        .unwrap();
+        assert!(owned.as_borrowed().unwrap().suppress_positive_response());
+        assert_eq!(owned.into_bytes(), [0x11, 0x83]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_request_rejects_invalid_frame() {
+        assert!(OwnedRequest::from_bytes(alloc::vec![]).is_err());
+        // A hand-built `Other` for a modeled service re-decodes as that service.
+        assert!(
+            OwnedRequest::try_from(&Request::Other {
+                sid: 0x10,
+                data: &[]
+            })
+            .is_err()
+        );
+        assert!(matches!(
+            OwnedRequest::from_bytes(alloc::vec![0x11, 0x01, 0xAA]),
+            Err(Error::TrailingBytes(_))
+        ));
+    }
+
     #[test]
     fn unmodeled_service_decodes_to_other() {
-        // 0x23 = ReadMemoryByAddress, enumerated but not modeled.
-        let frame = [0x23, 0xAA, 0xBB];
+        // 0x01 = OBD "show current data", outside the UDS service table.
+        let frame = [0x01, 0xAA, 0xBB];
         let (req, rest) = Request::decode(&frame).unwrap();
         assert!(rest.is_empty());
         match req {
             Request::Other { sid, data } => {
-                assert_eq!(sid, 0x23);
+                assert_eq!(sid, 0x01);
                 assert_eq!(data, &[0xAA, 0xBB]);
             }
             other => panic!("expected Other, got {other:?}"),
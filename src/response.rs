@@ -1,11 +1,18 @@
 use crate::{
-    ClearDiagnosticInfoResponse, CommunicationControlResponse, ControlDtcSettingResponse, Decode,
-    DiagnosticSessionControlResponse, EcuResetResponse, Encode, Error, Incomplete,
-    NegativeResponse, ReadDataByIdentifierResponse, ReadDtcInfoResponse, RequestDownloadResponse,
-    RequestFileTransferResponse, RequestTransferExitResponse, RoutineControlResponse,
-    SecurityAccessResponse, TesterPresentResponse, TransferDataResponse, UdsServiceType,
-    WriteDataByIdentifierResponse,
+    AccessTimingParametersResponse, AuthenticationResponse, ClearDiagnosticInfoResponse,
+    CommunicationControlResponse, ControlDtcSettingResponse, Decode,
+    DiagnosticSessionControlResponse, DynamicallyDefinedDataIdentifierResponse, EcuResetResponse,
+    Encode, Error, Incomplete, InputOutputControlByIdentifierResponse, LinkControlResponse,
+    NegativeResponse, ReadDataByIdentifierResponse, ReadDataByPeriodicIdentifierResponse,
+    ReadDtcInfoResponse, ReadMemoryByAddressResponse, ReadScalingDataByIdentifierResponse,
+    RequestDownloadResponse, RequestFileTransferResponse, RequestTransferExitResponse,
+    RequestUploadResponse, ResponseOnEventResponse, RoutineControlResponse,
+    SecuredDataTransmissionResponse, SecurityAccessResponse, TesterPresentResponse,
+    TransferDataResponse, UdsServiceType, WriteDataByIdentifierResponse,
+    WriteMemoryByAddressResponse,
 };
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use automotive_wire_codec::{write_all, write_u8};
 
 /// Parsed zero-copy UDS response. Borrows from the wire buffer.
@@ -15,6 +22,10 @@ use automotive_wire_codec::{write_all, write_u8};
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum Response<'a> {
+    /// Positive response to `AccessTimingParameters`.
+    AccessTimingParameters(AccessTimingParametersResponse<'a>),
+    /// Positive response to `Authentication`.
+    Authentication(AuthenticationResponse<'a>),
     /// Positive response to `ClearDiagnosticInfo`.
     ClearDiagnosticInfo(ClearDiagnosticInfoResponse),
     /// Positive response to `CommunicationControl`.
@@ -23,30 +34,44 @@ pub enum Response<'a> {
     ControlDtcSetting(ControlDtcSettingResponse),
     /// Positive response to `DiagnosticSessionControl`.
     DiagnosticSessionControl(DiagnosticSessionControlResponse),
+    /// Positive response to `DynamicallyDefinedDataIdentifier`.
+    DynamicallyDefinedDataIdentifier(DynamicallyDefinedDataIdentifierResponse),
     /// Positive response to `EcuReset`.
     EcuReset(EcuResetResponse),
+    /// Positive response to `InputOutputControlByIdentifier`.
+    InputOutputControlByIdentifier(InputOutputControlByIdentifierResponse<'a>),
+    /// Positive response to `LinkControl`.
+    LinkControl(LinkControlResponse),
     /// Negative response to any request.
     NegativeResponse(NegativeResponse),
     /// Positive response to `ReadDataByIdentifier`: raw `[DID][data record]…` bytes.
     ///
-    /// Unlike the request (a self-delimiting list of 2-byte DIDs, parsed by
-    /// [`ReadDataByIdentifierRequest::dids`](crate::ReadDataByIdentifierRequest::dids)),
-    /// this response is left opaque **by design**: each data record's length is defined by
-    /// the ECU's configuration for that DID and is *not* present on the wire, so the library
-    /// cannot split it into `(DID, value)` pairs. Parse it caller-side once you know each
-    /// DID's record length — read the 2-byte big-endian DID, take the application-defined
-    /// number of data bytes, then repeat on the remainder.
+    /// Record lengths are defined by the ECU's configuration for each DID and are *not*
+    /// present on the wire; split the records with a [`DidRegistry`](crate::DidRegistry) via
+    /// [`ReadDataByIdentifierResponse::data_records`](crate::ReadDataByIdentifierResponse::data_records).
     ReadDataByIdentifier(ReadDataByIdentifierResponse<'a>),
+    /// Positive response to `ReadDataByPeriodicIdentifier`, or periodic data sent with its response SID.
+    ReadDataByIdentifierPeriodic(ReadDataByPeriodicIdentifierResponse<'a>),
     /// Positive response to `ReadDTCInformation` with lazy iterators.
     ReadDtcInfo(ReadDtcInfoResponse<'a>),
+    /// Positive response to `ReadMemoryByAddress`.
+    ReadMemoryByAddress(ReadMemoryByAddressResponse<'a>),
+    /// Scaling information for a data identifier
+    ReadScalingDataByIdentifier(ReadScalingDataByIdentifierResponse<'a>),
     /// Positive response to `RequestDownload`.
     RequestDownload(RequestDownloadResponse<'a>),
     /// Positive response to `RequestFileTransfer`.
     RequestFileTransfer(RequestFileTransferResponse<'a>),
     /// Positive response to `RequestTransferExit`.
     RequestTransferExit(RequestTransferExitResponse<'a>),
+    /// Positive response to `RequestUpload`.
+    RequestUpload(RequestUploadResponse<'a>),
+    /// Positive response to `ResponseOnEvent`.
+    ResponseOnEvent(ResponseOnEventResponse<'a>),
     /// Positive response to `RoutineControl`.
     RoutineControl(RoutineControlResponse<'a>),
+    /// Positive response to `SecuredDataTransmission`.
+    SecuredDataTransmission(SecuredDataTransmissionResponse<'a>),
     /// Positive response to `SecurityAccess`.
     SecurityAccess(SecurityAccessResponse<'a>),
     /// Positive response to `TesterPresent`.
@@ -55,7 +80,9 @@ pub enum Response<'a> {
     TransferData(TransferDataResponse<'a>),
     /// Positive response to `WriteDataByIdentifier`. Contains the echoed DID.
     WriteDataByIdentifier(WriteDataByIdentifierResponse),
-    /// A known-but-unmodeled (or unrecognized) service response. Carries the raw service
+    /// Positive response to `WriteMemoryByAddress`. Contains the echoed address and size.
+    WriteMemoryByAddress(WriteMemoryByAddressResponse),
+    /// An unrecognized service response. Carries the raw service
     /// byte and the raw payload bytes following the service identifier.
     ///
     /// Re-encoding is lossless for every service byte: the raw `sid` is echoed verbatim.
@@ -70,6 +97,7 @@ pub enum Response<'a> {
 impl<'a> Decode<'a> for Response<'a> {
     type Error = crate::Error;
 
+    #[allow(clippy::too_many_lines)]
     fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
         if buf.is_empty() {
             return Err(Error::InsufficientData(Incomplete {
@@ -81,6 +109,12 @@ impl<'a> Decode<'a> for Response<'a> {
         let payload = &buf[1..];
 
         let response = match service {
+            UdsServiceType::AccessTimingParameters => Self::AccessTimingParameters(
+                <AccessTimingParametersResponse as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::Authentication => {
+                Self::Authentication(<AuthenticationResponse as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::ClearDiagnosticInfo => Self::ClearDiagnosticInfo(
                 <ClearDiagnosticInfoResponse as Decode>::decode_exact(payload)?,
             ),
@@ -93,18 +127,38 @@ impl<'a> Decode<'a> for Response<'a> {
             UdsServiceType::DiagnosticSessionControl => Self::DiagnosticSessionControl(
                 <DiagnosticSessionControlResponse as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::DynamicallyDefinedDataIdentifier => {
+                Self::DynamicallyDefinedDataIdentifier(
+                    <DynamicallyDefinedDataIdentifierResponse as Decode>::decode_exact(payload)?,
+                )
+            }
             UdsServiceType::EcuReset => {
                 Self::EcuReset(<EcuResetResponse as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::InputOutputControlByIdentifier => Self::InputOutputControlByIdentifier(
+                <InputOutputControlByIdentifierResponse as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::LinkControl => {
+                Self::LinkControl(<LinkControlResponse as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::NegativeResponse => {
                 Self::NegativeResponse(<NegativeResponse as Decode>::decode_exact(payload)?)
             }
             UdsServiceType::ReadDataByIdentifier => {
                 Self::ReadDataByIdentifier(ReadDataByIdentifierResponse::new(payload))
             }
+            UdsServiceType::ReadDataByIdentifierPeriodic => Self::ReadDataByIdentifierPeriodic(
+                <ReadDataByPeriodicIdentifierResponse as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::ReadDtcInfo => {
                 Self::ReadDtcInfo(<ReadDtcInfoResponse as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::ReadMemoryByAddress => Self::ReadMemoryByAddress(
+                <ReadMemoryByAddressResponse as Decode>::decode_exact(payload)?,
+            ),
+            UdsServiceType::ReadScalingDataByIdentifier => Self::ReadScalingDataByIdentifier(
+                <ReadScalingDataByIdentifierResponse as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::RequestDownload => {
                 Self::RequestDownload(<RequestDownloadResponse as Decode>::decode_exact(payload)?)
             }
@@ -114,9 +168,18 @@ impl<'a> Decode<'a> for Response<'a> {
             UdsServiceType::RequestTransferExit => Self::RequestTransferExit(
                 <RequestTransferExitResponse as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::RequestUpload => {
+                Self::RequestUpload(<RequestUploadResponse as Decode>::decode_exact(payload)?)
+            }
+            UdsServiceType::ResponseOnEvent => {
+                Self::ResponseOnEvent(<ResponseOnEventResponse as Decode>::decode_exact(payload)?)
+            }
             UdsServiceType::RoutineControl => {
                 Self::RoutineControl(<RoutineControlResponse as Decode>::decode_exact(payload)?)
             }
+            UdsServiceType::SecuredDataTransmission => Self::SecuredDataTransmission(
+                <SecuredDataTransmissionResponse as Decode>::decode_exact(payload)?,
+            ),
             UdsServiceType::SecurityAccess => {
                 Self::SecurityAccess(<SecurityAccessResponse as Decode>::decode_exact(payload)?)
             }
@@ -129,6 +192,9 @@ impl<'a> Decode<'a> for Response<'a> {
             UdsServiceType::WriteDataByIdentifier => Self::WriteDataByIdentifier(
                 <WriteDataByIdentifierResponse as Decode>::decode_exact(payload)?,
             ),
+            UdsServiceType::WriteMemoryByAddress => Self::WriteMemoryByAddress(
+                <WriteMemoryByAddressResponse as Decode>::decode_exact(payload)?,
+            ),
             _ => Self::Other {
                 sid: buf[0],
                 data: payload,
@@ -154,26 +220,50 @@ impl Response<'_> {
     /// Returns the response service-ID byte that frames this response on the wire.
     fn response_sid(&self) -> u8 {
         match self {
+            Self::AccessTimingParameters(_) => {
+                UdsServiceType::AccessTimingParameters.to_response_sid()
+            }
+            Self::Authentication(_) => UdsServiceType::Authentication.to_response_sid(),
             Self::ClearDiagnosticInfo(_) => UdsServiceType::ClearDiagnosticInfo.to_response_sid(),
             Self::CommunicationControl(_) => UdsServiceType::CommunicationControl.to_response_sid(),
             Self::ControlDtcSetting(_) => UdsServiceType::ControlDtcSetting.to_response_sid(),
             Self::DiagnosticSessionControl(_) => {
                 UdsServiceType::DiagnosticSessionControl.to_response_sid()
             }
+            Self::DynamicallyDefinedDataIdentifier(_) => {
+                UdsServiceType::DynamicallyDefinedDataIdentifier.to_response_sid()
+            }
             Self::EcuReset(_) => UdsServiceType::EcuReset.to_response_sid(),
+            Self::InputOutputControlByIdentifier(_) => {
+                UdsServiceType::InputOutputControlByIdentifier.to_response_sid()
+            }
+            Self::LinkControl(_) => UdsServiceType::LinkControl.to_response_sid(),
             Self::NegativeResponse(_) => UdsServiceType::NegativeResponse.to_response_sid(),
             Self::ReadDataByIdentifier(_) => UdsServiceType::ReadDataByIdentifier.to_response_sid(),
+            Self::ReadDataByIdentifierPeriodic(_) => {
+                UdsServiceType::ReadDataByIdentifierPeriodic.to_response_sid()
+            }
             Self::ReadDtcInfo(_) => UdsServiceType::ReadDtcInfo.to_response_sid(),
+            Self::ReadMemoryByAddress(_) => UdsServiceType::ReadMemoryByAddress.to_response_sid(),
+            Self::ReadScalingDataByIdentifier(_) => {
+                UdsServiceType::ReadScalingDataByIdentifier.to_response_sid()
+            }
             Self::RequestDownload(_) => UdsServiceType::RequestDownload.to_response_sid(),
             Self::RequestFileTransfer(_) => UdsServiceType::RequestFileTransfer.to_response_sid(),
             Self::RequestTransferExit(_) => UdsServiceType::RequestTransferExit.to_response_sid(),
+            Self::RequestUpload(_) => UdsServiceType::RequestUpload.to_response_sid(),
+            Self::ResponseOnEvent(_) => UdsServiceType::ResponseOnEvent.to_response_sid(),
             Self::RoutineControl(_) => UdsServiceType::RoutineControl.to_response_sid(),
+            Self::SecuredDataTransmission(_) => {
+                UdsServiceType::SecuredDataTransmission.to_response_sid()
+            }
             Self::SecurityAccess(_) => UdsServiceType::SecurityAccess.to_response_sid(),
             Self::TesterPresent(_) => UdsServiceType::TesterPresent.to_response_sid(),
             Self::TransferData(_) => UdsServiceType::TransferData.to_response_sid(),
             Self::WriteDataByIdentifier(_) => {
                 UdsServiceType::WriteDataByIdentifier.to_response_sid()
             }
+            Self::WriteMemoryByAddress(_) => UdsServiceType::WriteMemoryByAddress.to_response_sid(),
             Self::Other { sid, .. } => *sid,
         }
     }
@@ -195,18 +285,99 @@ impl Encode for Response<'_> {
             Self::ReadDataByIdentifier(resp) => resp.encode(writer)?,
             Self::WriteDataByIdentifier(resp) => resp.encode(writer)?,
             Self::ReadDtcInfo(resp) => resp.encode(writer)?,
+            Self::ReadMemoryByAddress(resp) => resp.encode(writer)?,
             Self::RequestDownload(resp) => resp.encode(writer)?,
             Self::RequestFileTransfer(resp) => resp.encode(writer)?,
             Self::RoutineControl(resp) => resp.encode(writer)?,
             Self::SecurityAccess(resp) => resp.encode(writer)?,
             Self::TesterPresent(resp) => resp.encode(writer)?,
             Self::TransferData(resp) => resp.encode(writer)?,
+            Self::WriteMemoryByAddress(resp) => resp.encode(writer)?,
+            Self::RequestUpload(resp) => resp.encode(writer)?,
+            Self::Authentication(resp) => resp.encode(writer)?,
+            Self::InputOutputControlByIdentifier(resp) => resp.encode(writer)?,
+            Self::DynamicallyDefinedDataIdentifier(resp) => resp.encode(writer)?,
+            Self::ReadDataByIdentifierPeriodic(resp) => resp.encode(writer)?,
+            Self::ResponseOnEvent(resp) => resp.encode(writer)?,
+            Self::LinkControl(resp) => resp.encode(writer)?,
+            Self::AccessTimingParameters(resp) => resp.encode(writer)?,
+            Self::SecuredDataTransmission(resp) => resp.encode(writer)?,
+            Self::ReadScalingDataByIdentifier(resp) => resp.encode(writer)?,
             Self::Other { data, .. } => write_all(writer, data).map_err(Error::io)?,
         };
         Ok(sid_len + payload)
     }
 }
 
+/// An owned response frame that can be stored or sent across tasks.
+///
+/// Holds the encoded frame, service byte included, and re-parses it on demand with
+/// [`as_borrowed`](Self::as_borrowed). The frame is validated on construction. A response
+/// decoded as [`Response::Other`] round-trips, but a hand-built `Other` whose `sid` belongs to a
+/// modeled service does not: its frame decodes as that service, or not at all.
+///
+/// ```
+/// use uds_protocol::{OwnedResponse, Response};
+/// let owned = OwnedResponse::from_bytes(vec![0x50, 0x01, 0x00, 0x32, 0x01, 0xF4]).unwrap();
+/// assert!(matches!(owned.as_borrowed().unwrap(), Response::DiagnosticSessionControl(_)));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OwnedResponse {
+    frame: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedResponse {
+    /// Take ownership of an encoded response frame.
+    ///
+    /// # Errors
+    /// Returns the [`Response::decode_exact`](Decode::decode_exact) error if `frame` is not a
+    /// valid response.
+    pub fn from_bytes(frame: Vec<u8>) -> Result<Self, Error> {
+        Response::decode_exact(&frame)?;
+        Ok(Self { frame })
+    }
+
+    /// Borrow the frame as a zero-copy [`Response`], decoding it again on every call.
+    ///
+    /// # Errors
+    /// Returns the [`Response::decode_exact`](Decode::decode_exact) error of the frame. A frame
+    /// accepted by [`from_bytes`](Self::from_bytes) decodes the same way every time, so this
+    /// only fails if construction did.
+    pub fn as_borrowed(&self) -> Result<Response<'_>, Error> {
+        Response::decode_exact(&self.frame)
+    }
+
+    /// The encoded frame, service byte included.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.frame
+    }
+
+    /// Consume this value and return the encoded frame.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.frame
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&Response<'_>> for OwnedResponse {
+    type Error = Error;
+
+    /// Copy `value` into an owned frame that does not borrow the wire buffer.
+    ///
+    /// # Errors
+    /// Returns an error if `value` cannot be encoded, or if its encoding does not decode
+    /// back into a [`Response`].
+    fn try_from(value: &Response<'_>) -> Result<Self, Error> {
+        let mut frame = Vec::new();
+        value.encode(&mut frame)?;
+        Self::from_bytes(frame)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,15 +405,172 @@ mod tests {
         assert_eq!(&buf[..written], &wire);
     }
 
+    #[test]
+    fn read_memory_by_address_response_roundtrips() {
+        // SID 0x63, three data bytes
+        let wire = [0x63, 0x00, 0x01, 0x8C];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::ReadMemoryByAddress(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn write_memory_by_address_response_roundtrips() {
+        // SID 0x7D, ALFID 0x12, echoed address 0x4000 and size 2
+        let wire = [0x7D, 0x12, 0x40, 0x00, 0x02];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::WriteMemoryByAddress(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn request_upload_response_roundtrips() {
+        // SID 0x75, LFI 0x20, maxNumberOfBlockLength 0x0FFA
+        let wire = [0x75, 0x20, 0x0F, 0xFA];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::RequestUpload(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn authentication_response_roundtrips() {
+        // SID 0x69, proofOfOwnership, ownershipVerified, 1-byte sessionKeyInfo
+        let wire = [0x69, 0x03, 0x12, 0x00, 0x01, 0x5A];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::Authentication(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn input_output_control_response_roundtrips() {
+        // SID 0x6F, DID 0x9B00, returnControlToECU, controlState 0x0A
+        let wire = [0x6F, 0x9B, 0x00, 0x00, 0x0A];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::InputOutputControlByIdentifier(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn dynamically_defined_data_identifier_response_roundtrips() {
+        // SID 0x6C, defineByIdentifier, DDDI 0xF301
+        let wire = [0x6C, 0x01, 0xF3, 0x01];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(
+            resp,
+            Response::DynamicallyDefinedDataIdentifier(_)
+        ));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn read_data_by_periodic_identifier_response_roundtrips() {
+        // SID 0x6A alone is the positive response; with a PDID it is periodic data.
+        for wire in [&[0x6A][..], &[0x6A, 0xE3, 0x4C, 0x01]] {
+            let (resp, remaining) = Response::decode(wire).unwrap();
+            assert!(remaining.is_empty());
+            assert!(matches!(resp, Response::ReadDataByIdentifierPeriodic(_)));
+            let mut buf = [0u8; 8];
+            let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+            assert_eq!(&buf[..written], wire);
+        }
+    }
+
+    #[test]
+    fn response_on_event_response_roundtrips() {
+        // SID 0xC6, startResponseOnEvent, one identified event, infinite window
+        let wire = [0xC6, 0x05, 0x01, 0x02];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::ResponseOnEvent(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn link_control_response_roundtrips() {
+        // SID 0xC7, verifyModeTransitionWithSpecificParameter
+        let wire = [0xC7, 0x02];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::LinkControl(_)));
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn access_timing_parameters_response_roundtrips() {
+        // SID 0xC3, readCurrentlyActiveTimingParameters, P2 = 50 ms, P2* = 5000 ms
+        let wire = [0xC3, 0x03, 0x00, 0x32, 0x01, 0xF4];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        assert!(matches!(resp, Response::AccessTimingParameters(_)));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn secured_data_transmission_response_roundtrips() {
+        // SID 0xC4 wrapping an unsigned TesterPresent response
+        let wire = [0xC4, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x7E, 0x00];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        let Response::SecuredDataTransmission(secured) = &resp else {
+            panic!("expected SecuredDataTransmission");
+        };
+        assert!(matches!(
+            secured.inner_response(),
+            Ok(Response::TesterPresent(_))
+        ));
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn read_scaling_data_by_identifier_response_roundtrips() {
+        // SID 0x64, DID 0x0105, unsigned numeric 1 byte, unit/format 0x0B
+        let wire = [0x64, 0x01, 0x05, 0x01, 0xA1, 0x0B];
+        let (resp, remaining) = Response::decode(&wire).unwrap();
+        assert!(remaining.is_empty());
+        let Response::ReadScalingDataByIdentifier(scaling) = &resp else {
+            panic!("expected ReadScalingDataByIdentifier");
+        };
+        assert_eq!(scaling.records().count(), 2);
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
     #[test]
     fn unmodeled_response_decodes_to_other() {
-        // 0x63 = ReadMemoryByAddress positive response, not modeled.
-        let frame = [0x63, 0x01, 0x02];
+        // 0x41 = OBD "show current data" positive response, outside the UDS service table.
+        let frame = [0x41, 0x01, 0x02];
         let (resp, remaining) = Response::decode(&frame).unwrap();
         assert!(remaining.is_empty());
         match resp {
             Response::Other { sid, data } => {
-                assert_eq!(sid, 0x63);
+                assert_eq!(sid, 0x41);
                 assert_eq!(data, &[0x01, 0x02]);
             }
             other => panic!("expected Other, got {other:?}"),
@@ -252,6 +580,29 @@ mod tests {
         assert_eq!(&buf[..written], &frame);
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_response_outlives_wire_buffer() {
+        let owned = {
+            let frame = [0x99, 0x01, 0x02];
+            let (resp, _) = Response::decode(&frame).unwrap();
+            OwnedResponse::try_from(&resp).unwrap()
+        };
+        assert!(matches!(
+            owned.as_borrowed().unwrap(),
+            Response::Other {
+                sid: 0x99,
+                data: &[0x01, 0x02]
+            }
+        ));
+        assert_eq!(owned.clone().into_bytes(), [0x99, 0x01, 0x02]);
+        assert_eq!(
+            OwnedResponse::from_bytes(alloc::vec![0x99, 0x01, 0x02]).unwrap(),
+            owned
+        );
+        assert!(OwnedResponse::from_bytes(alloc::vec![0x54, 0x00]).is_err());
+    }
+
     #[test]
     fn unknown_response_byte_round_trips_losslessly() {
         let frame = [0x99, 0x01, 0x02];
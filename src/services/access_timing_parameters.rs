@@ -0,0 +1,392 @@
+//! `AccessTimingParameters` (0x83) service implementation
+use crate::shared::SuppressablePositiveResponse;
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use automotive_wire_codec::{write_all, write_u8};
+
+const ACCESS_TIMING_PARAMETERS_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 4] = [
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+];
+
+/// Size of a P2/P2* timing parameter record.
+const P2_TIMING_RECORD_LEN: usize = 4;
+
+/// `timingParameterAccessType` sub-function of the `AccessTimingParameters` service
+///
+/// See ISO-14229-1:2020, Section 10.8.2.2
+///
+/// *Note*:
+///
+/// Conversions from `u8` to `TimingParameterAccessType` are fallible and will return an [`Error`](crate::Error) if the
+/// Suppress Positive Response bit is set.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TimingParameterAccessType {
+    /// Read the extended timing parameter set supported by the server
+    ReadExtendedTimingParameterSet,
+    /// Reset the timing parameters to the defaults of the active session
+    SetTimingParametersToDefaultValues,
+    /// Read the timing parameters currently in use
+    ReadCurrentlyActiveTimingParameters,
+    /// Set the timing parameters to the values in the request
+    SetTimingParametersToGivenValues,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    ///
+    /// Construct through [`TimingParameterAccessType::try_from`] so the raw byte is
+    /// range-checked and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    IsoSaeReserved(u8),
+}
+
+impl From<TimingParameterAccessType> for u8 {
+    fn from(value: TimingParameterAccessType) -> Self {
+        match value {
+            TimingParameterAccessType::ReadExtendedTimingParameterSet => 0x01,
+            TimingParameterAccessType::SetTimingParametersToDefaultValues => 0x02,
+            TimingParameterAccessType::ReadCurrentlyActiveTimingParameters => 0x03,
+            TimingParameterAccessType::SetTimingParametersToGivenValues => 0x04,
+            TimingParameterAccessType::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for TimingParameterAccessType {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            0x01 => Ok(Self::ReadExtendedTimingParameterSet),
+            0x02 => Ok(Self::SetTimingParametersToDefaultValues),
+            0x03 => Ok(Self::ReadCurrentlyActiveTimingParameters),
+            0x04 => Ok(Self::SetTimingParametersToGivenValues),
+            0x00 | 0x05..=0x7F => Ok(Self::IsoSaeReserved(value)),
+            _ => Err(Error::InvalidTimingParameterAccessType(value)),
+        }
+    }
+}
+
+/// Read the leading P2/P2* pair of a timing parameter record, as laid out by
+/// [`DiagnosticSessionControlResponse`](crate::DiagnosticSessionControlResponse).
+fn p2_timing(record: &[u8]) -> Option<(u16, u16)> {
+    let [p2_high, p2_low, p2_star_high, p2_star_low] =
+        *record.first_chunk::<P2_TIMING_RECORD_LEN>()?;
+    Some((
+        u16::from_be_bytes([p2_high, p2_low]),
+        u16::from_be_bytes([p2_star_high, p2_star_low]),
+    ))
+}
+
+/// Request to read, reset, or set the server's timing parameters.
+///
+/// The layout of the timing parameter record depends on the data link. On CAN it is the
+/// same P2/P2* pair the server reports in
+/// [`DiagnosticSessionControlResponse`](crate::DiagnosticSessionControlResponse); use
+/// [`p2_timing_record`](Self::p2_timing_record) to build one.
+///
+/// See ISO-14229-1:2020, Section 10.8.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AccessTimingParametersRequest<'a> {
+    /// Whether a positive response should be suppressed.
+    pub suppress_positive_response: bool,
+    /// The requested access type.
+    pub access_type: TimingParameterAccessType,
+    /// `TimingParameterRequestRecord`; only present for
+    /// [`SetTimingParametersToGivenValues`](TimingParameterAccessType::SetTimingParametersToGivenValues).
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub timing_parameters: &'a [u8],
+}
+
+impl<'a> AccessTimingParametersRequest<'a> {
+    /// Create a request for an access type that carries no timing parameter record.
+    #[must_use]
+    pub const fn new(
+        suppress_positive_response: bool,
+        access_type: TimingParameterAccessType,
+    ) -> Self {
+        Self {
+            suppress_positive_response,
+            access_type,
+            timing_parameters: &[],
+        }
+    }
+
+    /// Create a `setTimingParametersToGivenValues` request.
+    #[must_use]
+    pub const fn set_timing_parameters_to_given_values(
+        suppress_positive_response: bool,
+        timing_parameters: &'a [u8],
+    ) -> Self {
+        Self {
+            suppress_positive_response,
+            access_type: TimingParameterAccessType::SetTimingParametersToGivenValues,
+            timing_parameters,
+        }
+    }
+
+    /// Build a P2/P2* timing parameter record, with the same units as
+    /// [`DiagnosticSessionControlResponse`](crate::DiagnosticSessionControlResponse):
+    /// `p2_server_max` in milliseconds, `p2_star_server_max` in units of 10 milliseconds.
+    #[must_use]
+    pub const fn p2_timing_record(
+        p2_server_max: u16,
+        p2_star_server_max: u16,
+    ) -> [u8; P2_TIMING_RECORD_LEN] {
+        let [p2_high, p2_low] = p2_server_max.to_be_bytes();
+        let [p2_star_high, p2_star_low] = p2_star_server_max.to_be_bytes();
+        [p2_high, p2_low, p2_star_high, p2_star_low]
+    }
+
+    /// P2 server max (milliseconds) of a P2/P2* timing parameter record.
+    ///
+    /// `None` if the record is shorter than 4 bytes.
+    #[must_use]
+    pub fn p2_server_max(&self) -> Option<u16> {
+        p2_timing(self.timing_parameters).map(|(p2, _)| p2)
+    }
+
+    /// P2* server max (milliseconds × 10) of a P2/P2* timing parameter record.
+    ///
+    /// `None` if the record is shorter than 4 bytes.
+    #[must_use]
+    pub fn p2_star_server_max(&self) -> Option<u16> {
+        p2_timing(self.timing_parameters).map(|(_, p2_star)| p2_star)
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &ACCESS_TIMING_PARAMETERS_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for AccessTimingParametersRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let sub_function =
+            SuppressablePositiveResponse::new(self.suppress_positive_response, self.access_type);
+        let written = write_u8(writer, u8::from(sub_function)).map_err(Error::io)?;
+        Ok(written + write_all(writer, self.timing_parameters).map_err(Error::io)?)
+    }
+}
+
+impl<'a> Decode<'a> for AccessTimingParametersRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let sub_function =
+            SuppressablePositiveResponse::<TimingParameterAccessType>::try_from(buf[0])?;
+        let rest = &buf[1..];
+        let (timing_parameters, rest) = match sub_function.value() {
+            TimingParameterAccessType::SetTimingParametersToGivenValues => {
+                if rest.is_empty() {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                }
+                (rest, &[][..])
+            }
+            TimingParameterAccessType::IsoSaeReserved(_) => (rest, &[][..]),
+            _ => (&[][..], rest),
+        };
+        Ok((
+            Self {
+                suppress_positive_response: sub_function.suppress_positive_response(),
+                access_type: sub_function.value(),
+                timing_parameters,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Positive response to `AccessTimingParameters`
+///
+/// See ISO-14229-1:2020, Section 10.8.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AccessTimingParametersResponse<'a> {
+    /// The access type echoed from the request.
+    pub access_type: TimingParameterAccessType,
+    /// `TimingParameterResponseRecord`; only present for the two read access types.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub timing_parameters: &'a [u8],
+}
+
+impl<'a> AccessTimingParametersResponse<'a> {
+    /// Create a new response.
+    #[must_use]
+    pub const fn new(access_type: TimingParameterAccessType, timing_parameters: &'a [u8]) -> Self {
+        Self {
+            access_type,
+            timing_parameters,
+        }
+    }
+
+    /// P2 server max (milliseconds) of a P2/P2* timing parameter record.
+    ///
+    /// `None` if the record is shorter than 4 bytes.
+    #[must_use]
+    pub fn p2_server_max(&self) -> Option<u16> {
+        p2_timing(self.timing_parameters).map(|(p2, _)| p2)
+    }
+
+    /// P2* server max (milliseconds × 10) of a P2/P2* timing parameter record.
+    ///
+    /// `None` if the record is shorter than 4 bytes.
+    #[must_use]
+    pub fn p2_star_server_max(&self) -> Option<u16> {
+        p2_timing(self.timing_parameters).map(|(_, p2_star)| p2_star)
+    }
+}
+
+impl Encode for AccessTimingParametersResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let written = write_u8(writer, u8::from(self.access_type)).map_err(Error::io)?;
+        Ok(written + write_all(writer, self.timing_parameters).map_err(Error::io)?)
+    }
+}
+
+impl<'a> Decode<'a> for AccessTimingParametersResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let access_type = TimingParameterAccessType::try_from(buf[0])?;
+        let rest = &buf[1..];
+        let (timing_parameters, rest) = match access_type {
+            TimingParameterAccessType::ReadExtendedTimingParameterSet
+            | TimingParameterAccessType::ReadCurrentlyActiveTimingParameters
+            | TimingParameterAccessType::IsoSaeReserved(_) => (rest, &[][..]),
+            _ => (&[][..], rest),
+        };
+        Ok((Self::new(access_type, timing_parameters), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn access_type_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            match TimingParameterAccessType::try_from(i) {
+                Ok(value) => assert_eq!(u8::from(value), i),
+                Err(Error::InvalidTimingParameterAccessType(value)) => {
+                    assert_eq!(value, i);
+                    assert!(i >= 0x80);
+                }
+                _ => panic!("Invalid error type"),
+            }
+        }
+    }
+
+    #[test]
+    fn set_given_values_roundtrip() {
+        let record = AccessTimingParametersRequest::p2_timing_record(0x0032, 0x01F4);
+        let req =
+            AccessTimingParametersRequest::set_timing_parameters_to_given_values(true, &record);
+        let wire = [0x84, 0x00, 0x32, 0x01, 0xF4];
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) = <AccessTimingParametersRequest as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.p2_server_max(), Some(0x0032));
+        assert_eq!(decoded.p2_star_server_max(), Some(0x01F4));
+    }
+
+    #[test]
+    fn read_requests_carry_no_record() {
+        let (decoded, rest) = <AccessTimingParametersRequest as Decode>::decode(&[0x03]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            decoded,
+            AccessTimingParametersRequest::new(
+                false,
+                TimingParameterAccessType::ReadCurrentlyActiveTimingParameters
+            )
+        );
+        assert_eq!(decoded.p2_server_max(), None);
+
+        // Trailing bytes are left for `decode_exact` to reject.
+        let (_, rest) = <AccessTimingParametersRequest as Decode>::decode(&[0x02, 0xAA]).unwrap();
+        assert_eq!(rest, &[0xAA]);
+    }
+
+    #[test]
+    fn request_short_buffers() {
+        assert!(matches!(
+            <AccessTimingParametersRequest as Decode>::decode(&[]),
+            Err(Error::InsufficientData(_))
+        ));
+        assert!(matches!(
+            <AccessTimingParametersRequest as Decode>::decode(&[0x04]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let wire = [0x03, 0x00, 0x32, 0x01, 0xF4];
+        let (response, rest) = <AccessTimingParametersResponse as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            response.access_type,
+            TimingParameterAccessType::ReadCurrentlyActiveTimingParameters
+        );
+        assert_eq!(response.p2_server_max(), Some(0x0032));
+        assert_eq!(response.p2_star_server_max(), Some(0x01F4));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&response);
+
+        let (set, rest) = <AccessTimingParametersResponse as Decode>::decode(&[0x04]).unwrap();
+        assert!(rest.is_empty());
+        assert!(set.timing_parameters.is_empty());
+        assert!(matches!(
+            <AccessTimingParametersResponse as Decode>::decode(&[]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<AccessTimingParametersRequest<'static>>();
+        assert_impl_eq::<AccessTimingParametersResponse<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<AccessTimingParametersRequest<'static>>();
+            assert_impl_serde::<AccessTimingParametersResponse<'static>>();
+        }
+    }
+}
@@ -0,0 +1,1115 @@
+//! `Authentication` (0x29) service implementation
+use crate::shared::SuppressablePositiveResponse;
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use automotive_wire_codec::{
+    read_array, read_u8, read_u16_be, take, write_all, write_u8, write_u16_be,
+};
+
+/// Length in bytes of the `algorithmIndicator` parameter (an ASN.1 OID, zero padded).
+const ALGORITHM_INDICATOR_LEN: usize = 16;
+
+/// `AuthenticationType` selects which step of the authentication procedure is requested
+///
+/// See ISO-14229-1:2020, Table 75
+///
+/// *Note*:
+///
+/// Conversions from `u8` to `AuthenticationType` are fallible and will return an [`Error`](crate::Error) if the
+/// Suppress Positive Response bit is set.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuthenticationType {
+    /// Leave the authenticated state and return to the default (unauthenticated) state
+    DeAuthenticate,
+    /// Start a unidirectional authentication with the Authentication with PKI Certificate
+    /// Exchange (APCE) procedure: the client sends its certificate to the server
+    VerifyCertificateUnidirectional,
+    /// Start a bidirectional APCE authentication: client and server exchange certificates
+    VerifyCertificateBidirectional,
+    /// Send the client's proof of ownership of the certificate previously verified by the server
+    ProofOfOwnership,
+    /// Transmit a certificate to the server for evaluation outside of an authentication procedure
+    TransmitCertificate,
+    /// Request a server challenge for the Authentication with Challenge-Response (ACR) procedure
+    RequestChallengeForAuthentication,
+    /// Send the client's proof of ownership for a unidirectional ACR authentication
+    VerifyProofOfOwnershipUnidirectional,
+    /// Send the client's proof of ownership for a bidirectional ACR authentication
+    VerifyProofOfOwnershipBidirectional,
+    /// Ask the server which authentication procedure it supports
+    AuthenticationConfiguration,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    ///
+    /// Construct through [`AuthenticationType::try_from`] so the raw byte is
+    /// range-checked and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    IsoSaeReserved(u8),
+}
+
+impl From<AuthenticationType> for u8 {
+    fn from(value: AuthenticationType) -> Self {
+        match value {
+            AuthenticationType::DeAuthenticate => 0x00,
+            AuthenticationType::VerifyCertificateUnidirectional => 0x01,
+            AuthenticationType::VerifyCertificateBidirectional => 0x02,
+            AuthenticationType::ProofOfOwnership => 0x03,
+            AuthenticationType::TransmitCertificate => 0x04,
+            AuthenticationType::RequestChallengeForAuthentication => 0x05,
+            AuthenticationType::VerifyProofOfOwnershipUnidirectional => 0x06,
+            AuthenticationType::VerifyProofOfOwnershipBidirectional => 0x07,
+            AuthenticationType::AuthenticationConfiguration => 0x08,
+            AuthenticationType::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for AuthenticationType {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            0x00 => Ok(Self::DeAuthenticate),
+            0x01 => Ok(Self::VerifyCertificateUnidirectional),
+            0x02 => Ok(Self::VerifyCertificateBidirectional),
+            0x03 => Ok(Self::ProofOfOwnership),
+            0x04 => Ok(Self::TransmitCertificate),
+            0x05 => Ok(Self::RequestChallengeForAuthentication),
+            0x06 => Ok(Self::VerifyProofOfOwnershipUnidirectional),
+            0x07 => Ok(Self::VerifyProofOfOwnershipBidirectional),
+            0x08 => Ok(Self::AuthenticationConfiguration),
+            0x09..=0x7F => Ok(Self::IsoSaeReserved(value)),
+            _ => Err(Error::InvalidAuthenticationType(value)),
+        }
+    }
+}
+
+/// `authenticationReturnParameter` carried by every positive [`AuthenticationResponse`]
+///
+/// See ISO-14229-1:2020, Table 84
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuthenticationReturnParameter {
+    /// The request has been accepted
+    RequestAccepted,
+    /// The request has been rejected for an unspecified reason
+    GeneralReject,
+    /// The server uses Authentication with PKI Certificate Exchange (APCE)
+    AuthenticationConfigurationApce,
+    /// The server uses Authentication with Challenge-Response (ACR) with asymmetric cryptography
+    AuthenticationConfigurationAcrWithAsymmetricCryptography,
+    /// The server uses Authentication with Challenge-Response (ACR) with symmetric cryptography
+    AuthenticationConfigurationAcrWithSymmetricCryptography,
+    /// The server returned to the unauthenticated state
+    DeAuthenticationSuccessful,
+    /// The client certificate is valid; the client must now prove ownership of it
+    CertificateVerifiedOwnershipVerificationNecessary,
+    /// Proof of ownership was verified and the authentication is complete
+    OwnershipVerifiedAuthenticationComplete,
+    /// The certificate sent with [`AuthenticationType::TransmitCertificate`] was verified
+    CertificateVerified,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    IsoSaeReserved(u8),
+    /// Values reserved for use by vehicle manufacturers.
+    VehicleManufacturerSpecific(u8),
+    /// Values reserved for use by system suppliers.
+    SystemSupplierSpecific(u8),
+}
+
+impl From<AuthenticationReturnParameter> for u8 {
+    #[allow(clippy::match_same_arms)]
+    fn from(value: AuthenticationReturnParameter) -> Self {
+        match value {
+            AuthenticationReturnParameter::RequestAccepted => 0x00,
+            AuthenticationReturnParameter::GeneralReject => 0x01,
+            AuthenticationReturnParameter::AuthenticationConfigurationApce => 0x02,
+            AuthenticationReturnParameter::AuthenticationConfigurationAcrWithAsymmetricCryptography => 0x03,
+            AuthenticationReturnParameter::AuthenticationConfigurationAcrWithSymmetricCryptography => 0x04,
+            AuthenticationReturnParameter::DeAuthenticationSuccessful => 0x10,
+            AuthenticationReturnParameter::CertificateVerifiedOwnershipVerificationNecessary => 0x11,
+            AuthenticationReturnParameter::OwnershipVerifiedAuthenticationComplete => 0x12,
+            AuthenticationReturnParameter::CertificateVerified => 0x13,
+            AuthenticationReturnParameter::IsoSaeReserved(val) => val,
+            AuthenticationReturnParameter::VehicleManufacturerSpecific(val) => val,
+            AuthenticationReturnParameter::SystemSupplierSpecific(val) => val,
+        }
+    }
+}
+
+impl From<u8> for AuthenticationReturnParameter {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::RequestAccepted,
+            0x01 => Self::GeneralReject,
+            0x02 => Self::AuthenticationConfigurationApce,
+            0x03 => Self::AuthenticationConfigurationAcrWithAsymmetricCryptography,
+            0x04 => Self::AuthenticationConfigurationAcrWithSymmetricCryptography,
+            0x10 => Self::DeAuthenticationSuccessful,
+            0x11 => Self::CertificateVerifiedOwnershipVerificationNecessary,
+            0x12 => Self::OwnershipVerifiedAuthenticationComplete,
+            0x13 => Self::CertificateVerified,
+            0xA0..=0xCF => Self::VehicleManufacturerSpecific(value),
+            0xD0..=0xFE => Self::SystemSupplierSpecific(value),
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// Read a 2-byte big-endian length followed by that many bytes.
+fn read_length_prefixed(buf: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let (len, rest) = read_u16_be(buf)?;
+    Ok(take(rest, usize::from(len))?)
+}
+
+/// Write `data` preceded by its 2-byte big-endian length.
+fn write_length_prefixed(
+    writer: &mut impl embedded_io::Write,
+    data: &[u8],
+) -> Result<usize, Error> {
+    let len =
+        u16::try_from(data.len()).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+    let mut written = write_u16_be(writer, len).map_err(Error::io)?;
+    written += write_all(writer, data).map_err(Error::io)?;
+    Ok(written)
+}
+
+/// List of allowed [`NegativeResponseCode`] variants for the `Authentication` service
+const AUTHENTICATION_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 19] = [
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestSequenceError,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::CertificateVerificationFailedInvalidTimePeriod,
+    NegativeResponseCode::CertificateVerificationFailedInvalidSignature,
+    NegativeResponseCode::CertificateVerificationFailedInvalidChainOfTrust,
+    NegativeResponseCode::CertificateVerificationFailedInvalidType,
+    NegativeResponseCode::CertificateVerificationFailedInvalidFormat,
+    NegativeResponseCode::CertificateVerificationFailedInvalidContent,
+    NegativeResponseCode::CertificateVerificationFailedInvalidScope,
+    NegativeResponseCode::CertificateVerificationFailedInvalidCertificate,
+    NegativeResponseCode::OwnershipVerificationFailed,
+    NegativeResponseCode::ChallengeCalculationFailed,
+    NegativeResponseCode::SettingAccessRightsFailed,
+    NegativeResponseCode::SessionKeyCreationDerivationFailed,
+    NegativeResponseCode::ConfigurationDataUsageFailed,
+    NegativeResponseCode::DeAuthenticationFailed,
+];
+
+/// Sub-function specific parameters of an [`AuthenticationRequest`]
+///
+/// Each variant corresponds to one [`AuthenticationType`]. Variable-length parameters are
+/// length-prefixed on the wire with a 2-byte big-endian length, which is derived from the
+/// slice length at encode time. See ISO-14229-1:2020, Section 10.6.2
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuthenticationRequestParameters<'d> {
+    /// [`AuthenticationType::DeAuthenticate`]
+    DeAuthenticate,
+    /// [`AuthenticationType::VerifyCertificateUnidirectional`]
+    VerifyCertificateUnidirectional {
+        /// Manufacturer-defined configuration for the secure communication after authentication
+        communication_configuration: u8,
+        /// The client's certificate
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        certificate_client: &'d [u8],
+        /// Optional client challenge (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_client: &'d [u8],
+    },
+    /// [`AuthenticationType::VerifyCertificateBidirectional`]
+    VerifyCertificateBidirectional {
+        /// Manufacturer-defined configuration for the secure communication after authentication
+        communication_configuration: u8,
+        /// The client's certificate
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        certificate_client: &'d [u8],
+        /// The client challenge the server has to sign with its proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_client: &'d [u8],
+    },
+    /// [`AuthenticationType::ProofOfOwnership`]
+    ProofOfOwnership {
+        /// The client's proof of ownership (signature over the server challenge)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        proof_of_ownership_client: &'d [u8],
+        /// Optional ephemeral public key for session key agreement (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ephemeral_public_key_client: &'d [u8],
+    },
+    /// [`AuthenticationType::TransmitCertificate`]
+    TransmitCertificate {
+        /// Identifies how the server shall evaluate the certificate
+        certificate_evaluation_id: u16,
+        /// The certificate to evaluate
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        certificate_data: &'d [u8],
+    },
+    /// [`AuthenticationType::RequestChallengeForAuthentication`]
+    RequestChallengeForAuthentication {
+        /// Manufacturer-defined configuration for the secure communication after authentication
+        communication_configuration: u8,
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+    },
+    /// [`AuthenticationType::VerifyProofOfOwnershipUnidirectional`]
+    VerifyProofOfOwnershipUnidirectional {
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+        /// The client's proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        proof_of_ownership_client: &'d [u8],
+        /// Optional client challenge (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_client: &'d [u8],
+        /// Additional parameters requested by the server (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        additional_parameter: &'d [u8],
+    },
+    /// [`AuthenticationType::VerifyProofOfOwnershipBidirectional`]
+    VerifyProofOfOwnershipBidirectional {
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+        /// The client's proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        proof_of_ownership_client: &'d [u8],
+        /// The client challenge the server has to answer with its proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_client: &'d [u8],
+        /// Additional parameters requested by the server (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        additional_parameter: &'d [u8],
+    },
+    /// [`AuthenticationType::AuthenticationConfiguration`]
+    AuthenticationConfiguration,
+    /// A reserved sub-function. The raw parameter bytes are kept for pass-through.
+    IsoSaeReserved {
+        /// The reserved [`AuthenticationType::IsoSaeReserved`] sub-function
+        authentication_type: AuthenticationType,
+        /// Raw parameter bytes following the sub-function
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        data: &'d [u8],
+    },
+}
+
+impl AuthenticationRequestParameters<'_> {
+    /// The [`AuthenticationType`] (sub-function) these parameters belong to.
+    #[must_use]
+    pub const fn authentication_type(&self) -> AuthenticationType {
+        match self {
+            Self::DeAuthenticate => AuthenticationType::DeAuthenticate,
+            Self::VerifyCertificateUnidirectional { .. } => {
+                AuthenticationType::VerifyCertificateUnidirectional
+            }
+            Self::VerifyCertificateBidirectional { .. } => {
+                AuthenticationType::VerifyCertificateBidirectional
+            }
+            Self::ProofOfOwnership { .. } => AuthenticationType::ProofOfOwnership,
+            Self::TransmitCertificate { .. } => AuthenticationType::TransmitCertificate,
+            Self::RequestChallengeForAuthentication { .. } => {
+                AuthenticationType::RequestChallengeForAuthentication
+            }
+            Self::VerifyProofOfOwnershipUnidirectional { .. } => {
+                AuthenticationType::VerifyProofOfOwnershipUnidirectional
+            }
+            Self::VerifyProofOfOwnershipBidirectional { .. } => {
+                AuthenticationType::VerifyProofOfOwnershipBidirectional
+            }
+            Self::AuthenticationConfiguration => AuthenticationType::AuthenticationConfiguration,
+            Self::IsoSaeReserved {
+                authentication_type,
+                ..
+            } => *authentication_type,
+        }
+    }
+}
+
+/// Client request to authenticate (or de-authenticate) with the server
+///
+/// Zero-alloc request for authentication. Borrows from the caller.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthenticationRequest<'d> {
+    /// Whether a positive response should be suppressed.
+    pub suppress_positive_response: bool,
+    /// The sub-function and its parameters.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub parameters: AuthenticationRequestParameters<'d>,
+}
+
+impl<'d> AuthenticationRequest<'d> {
+    /// Create a new authentication request.
+    #[must_use]
+    pub const fn new(
+        suppress_positive_response: bool,
+        parameters: AuthenticationRequestParameters<'d>,
+    ) -> Self {
+        Self {
+            suppress_positive_response,
+            parameters,
+        }
+    }
+
+    /// The requested [`AuthenticationType`].
+    #[must_use]
+    pub const fn authentication_type(&self) -> AuthenticationType {
+        self.parameters.authentication_type()
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &AUTHENTICATION_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for AuthenticationRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let sub_function = SuppressablePositiveResponse::new(
+            self.suppress_positive_response,
+            self.authentication_type(),
+        );
+        let mut written = write_u8(writer, u8::from(sub_function)).map_err(Error::io)?;
+        written += match self.parameters {
+            AuthenticationRequestParameters::DeAuthenticate
+            | AuthenticationRequestParameters::AuthenticationConfiguration => 0,
+            AuthenticationRequestParameters::VerifyCertificateUnidirectional {
+                communication_configuration,
+                certificate_client,
+                challenge_client,
+            }
+            | AuthenticationRequestParameters::VerifyCertificateBidirectional {
+                communication_configuration,
+                certificate_client,
+                challenge_client,
+            } => {
+                write_u8(writer, communication_configuration).map_err(Error::io)?
+                    + write_length_prefixed(writer, certificate_client)?
+                    + write_length_prefixed(writer, challenge_client)?
+            }
+            AuthenticationRequestParameters::ProofOfOwnership {
+                proof_of_ownership_client,
+                ephemeral_public_key_client,
+            } => {
+                write_length_prefixed(writer, proof_of_ownership_client)?
+                    + write_length_prefixed(writer, ephemeral_public_key_client)?
+            }
+            AuthenticationRequestParameters::TransmitCertificate {
+                certificate_evaluation_id,
+                certificate_data,
+            } => {
+                write_u16_be(writer, certificate_evaluation_id).map_err(Error::io)?
+                    + write_length_prefixed(writer, certificate_data)?
+            }
+            AuthenticationRequestParameters::RequestChallengeForAuthentication {
+                communication_configuration,
+                algorithm_indicator,
+            } => {
+                write_u8(writer, communication_configuration).map_err(Error::io)?
+                    + write_all(writer, &algorithm_indicator).map_err(Error::io)?
+            }
+            AuthenticationRequestParameters::VerifyProofOfOwnershipUnidirectional {
+                algorithm_indicator,
+                proof_of_ownership_client,
+                challenge_client,
+                additional_parameter,
+            }
+            | AuthenticationRequestParameters::VerifyProofOfOwnershipBidirectional {
+                algorithm_indicator,
+                proof_of_ownership_client,
+                challenge_client,
+                additional_parameter,
+            } => {
+                write_all(writer, &algorithm_indicator).map_err(Error::io)?
+                    + write_length_prefixed(writer, proof_of_ownership_client)?
+                    + write_length_prefixed(writer, challenge_client)?
+                    + write_length_prefixed(writer, additional_parameter)?
+            }
+            AuthenticationRequestParameters::IsoSaeReserved { data, .. } => {
+                write_all(writer, data).map_err(Error::io)?
+            }
+        };
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for AuthenticationRequest<'a> {
+    type Error = crate::Error;
+
+    #[allow(clippy::too_many_lines)]
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let sub_function = SuppressablePositiveResponse::<AuthenticationType>::try_from(buf[0])?;
+        let rest = &buf[1..];
+        let (parameters, rest) = match sub_function.value() {
+            AuthenticationType::DeAuthenticate => {
+                (AuthenticationRequestParameters::DeAuthenticate, rest)
+            }
+            AuthenticationType::AuthenticationConfiguration => (
+                AuthenticationRequestParameters::AuthenticationConfiguration,
+                rest,
+            ),
+            authentication_type @ (AuthenticationType::VerifyCertificateUnidirectional
+            | AuthenticationType::VerifyCertificateBidirectional) => {
+                let (communication_configuration, rest) = read_u8(rest)?;
+                let (certificate_client, rest) = read_length_prefixed(rest)?;
+                let (challenge_client, rest) = read_length_prefixed(rest)?;
+                let parameters =
+                    if authentication_type == AuthenticationType::VerifyCertificateUnidirectional {
+                        AuthenticationRequestParameters::VerifyCertificateUnidirectional {
+                            communication_configuration,
+                            certificate_client,
+                            challenge_client,
+                        }
+                    } else {
+                        AuthenticationRequestParameters::VerifyCertificateBidirectional {
+                            communication_configuration,
+                            certificate_client,
+                            challenge_client,
+                        }
+                    };
+                (parameters, rest)
+            }
+            AuthenticationType::ProofOfOwnership => {
+                let (proof_of_ownership_client, rest) = read_length_prefixed(rest)?;
+                let (ephemeral_public_key_client, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationRequestParameters::ProofOfOwnership {
+                        proof_of_ownership_client,
+                        ephemeral_public_key_client,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::TransmitCertificate => {
+                let (certificate_evaluation_id, rest) = read_u16_be(rest)?;
+                let (certificate_data, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationRequestParameters::TransmitCertificate {
+                        certificate_evaluation_id,
+                        certificate_data,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::RequestChallengeForAuthentication => {
+                let (communication_configuration, rest) = read_u8(rest)?;
+                let (algorithm_indicator, rest) = read_array(rest)?;
+                (
+                    AuthenticationRequestParameters::RequestChallengeForAuthentication {
+                        communication_configuration,
+                        algorithm_indicator,
+                    },
+                    rest,
+                )
+            }
+            authentication_type @ (AuthenticationType::VerifyProofOfOwnershipUnidirectional
+            | AuthenticationType::VerifyProofOfOwnershipBidirectional) => {
+                let (algorithm_indicator, rest) = read_array(rest)?;
+                let (proof_of_ownership_client, rest) = read_length_prefixed(rest)?;
+                let (challenge_client, rest) = read_length_prefixed(rest)?;
+                let (additional_parameter, rest) = read_length_prefixed(rest)?;
+                let parameters = if authentication_type
+                    == AuthenticationType::VerifyProofOfOwnershipUnidirectional
+                {
+                    AuthenticationRequestParameters::VerifyProofOfOwnershipUnidirectional {
+                        algorithm_indicator,
+                        proof_of_ownership_client,
+                        challenge_client,
+                        additional_parameter,
+                    }
+                } else {
+                    AuthenticationRequestParameters::VerifyProofOfOwnershipBidirectional {
+                        algorithm_indicator,
+                        proof_of_ownership_client,
+                        challenge_client,
+                        additional_parameter,
+                    }
+                };
+                (parameters, rest)
+            }
+            authentication_type @ AuthenticationType::IsoSaeReserved(_) => (
+                AuthenticationRequestParameters::IsoSaeReserved {
+                    authentication_type,
+                    data: rest,
+                },
+                &[][..],
+            ),
+        };
+        Ok((
+            Self {
+                suppress_positive_response: sub_function.suppress_positive_response(),
+                parameters,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Sub-function specific parameters of an [`AuthenticationResponse`]
+///
+/// Each variant corresponds to one [`AuthenticationType`]. Variable-length parameters are
+/// length-prefixed on the wire with a 2-byte big-endian length, which is derived from the
+/// slice length at encode time. See ISO-14229-1:2020, Section 10.6.3
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuthenticationResponseParameters<'d> {
+    /// [`AuthenticationType::DeAuthenticate`]
+    DeAuthenticate,
+    /// [`AuthenticationType::VerifyCertificateUnidirectional`]
+    VerifyCertificateUnidirectional {
+        /// The server challenge the client has to sign with its proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_server: &'d [u8],
+        /// Optional ephemeral public key for session key agreement (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ephemeral_public_key_server: &'d [u8],
+    },
+    /// [`AuthenticationType::VerifyCertificateBidirectional`]
+    VerifyCertificateBidirectional {
+        /// The server challenge the client has to sign with its proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_server: &'d [u8],
+        /// The server's certificate
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        certificate_server: &'d [u8],
+        /// The server's proof of ownership (signature over the client challenge)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        proof_of_ownership_server: &'d [u8],
+        /// Optional ephemeral public key for session key agreement (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        ephemeral_public_key_server: &'d [u8],
+    },
+    /// [`AuthenticationType::ProofOfOwnership`]
+    ProofOfOwnership {
+        /// Optional session key information (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        session_key_info: &'d [u8],
+    },
+    /// [`AuthenticationType::TransmitCertificate`]
+    TransmitCertificate,
+    /// [`AuthenticationType::RequestChallengeForAuthentication`]
+    RequestChallengeForAuthentication {
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+        /// The server challenge
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        challenge_server: &'d [u8],
+        /// Additional parameters the client has to supply (empty if none are needed)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        needed_additional_parameter: &'d [u8],
+    },
+    /// [`AuthenticationType::VerifyProofOfOwnershipUnidirectional`]
+    VerifyProofOfOwnershipUnidirectional {
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+        /// Optional session key information (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        session_key_info: &'d [u8],
+    },
+    /// [`AuthenticationType::VerifyProofOfOwnershipBidirectional`]
+    VerifyProofOfOwnershipBidirectional {
+        /// OID of the algorithm used for the challenge-response procedure
+        algorithm_indicator: [u8; ALGORITHM_INDICATOR_LEN],
+        /// The server's proof of ownership
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        proof_of_ownership_server: &'d [u8],
+        /// Optional session key information (empty if not used)
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        session_key_info: &'d [u8],
+    },
+    /// [`AuthenticationType::AuthenticationConfiguration`]
+    AuthenticationConfiguration,
+    /// A reserved sub-function. The raw parameter bytes are kept for pass-through.
+    IsoSaeReserved {
+        /// The reserved [`AuthenticationType::IsoSaeReserved`] sub-function
+        authentication_type: AuthenticationType,
+        /// Raw parameter bytes following the `authenticationReturnParameter`
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        data: &'d [u8],
+    },
+}
+
+impl AuthenticationResponseParameters<'_> {
+    /// The [`AuthenticationType`] (sub-function) these parameters belong to.
+    #[must_use]
+    pub const fn authentication_type(&self) -> AuthenticationType {
+        match self {
+            Self::DeAuthenticate => AuthenticationType::DeAuthenticate,
+            Self::VerifyCertificateUnidirectional { .. } => {
+                AuthenticationType::VerifyCertificateUnidirectional
+            }
+            Self::VerifyCertificateBidirectional { .. } => {
+                AuthenticationType::VerifyCertificateBidirectional
+            }
+            Self::ProofOfOwnership { .. } => AuthenticationType::ProofOfOwnership,
+            Self::TransmitCertificate => AuthenticationType::TransmitCertificate,
+            Self::RequestChallengeForAuthentication { .. } => {
+                AuthenticationType::RequestChallengeForAuthentication
+            }
+            Self::VerifyProofOfOwnershipUnidirectional { .. } => {
+                AuthenticationType::VerifyProofOfOwnershipUnidirectional
+            }
+            Self::VerifyProofOfOwnershipBidirectional { .. } => {
+                AuthenticationType::VerifyProofOfOwnershipBidirectional
+            }
+            Self::AuthenticationConfiguration => AuthenticationType::AuthenticationConfiguration,
+            Self::IsoSaeReserved {
+                authentication_type,
+                ..
+            } => *authentication_type,
+        }
+    }
+}
+
+/// Zero-alloc response for authentication. Borrows from the caller.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthenticationResponse<'d> {
+    /// Outcome of the requested authentication step.
+    pub authentication_return_parameter: AuthenticationReturnParameter,
+    /// The sub-function echoed from the request and its parameters.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub parameters: AuthenticationResponseParameters<'d>,
+}
+
+impl<'d> AuthenticationResponse<'d> {
+    /// Create a new authentication response.
+    #[must_use]
+    pub const fn new(
+        authentication_return_parameter: AuthenticationReturnParameter,
+        parameters: AuthenticationResponseParameters<'d>,
+    ) -> Self {
+        Self {
+            authentication_return_parameter,
+            parameters,
+        }
+    }
+
+    /// The [`AuthenticationType`] echoed from the request.
+    #[must_use]
+    pub const fn authentication_type(&self) -> AuthenticationType {
+        self.parameters.authentication_type()
+    }
+}
+
+impl Encode for AuthenticationResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_all(
+            writer,
+            &[
+                u8::from(self.authentication_type()),
+                u8::from(self.authentication_return_parameter),
+            ],
+        )
+        .map_err(Error::io)?;
+        written += match self.parameters {
+            AuthenticationResponseParameters::DeAuthenticate
+            | AuthenticationResponseParameters::TransmitCertificate
+            | AuthenticationResponseParameters::AuthenticationConfiguration => 0,
+            AuthenticationResponseParameters::VerifyCertificateUnidirectional {
+                challenge_server,
+                ephemeral_public_key_server,
+            } => {
+                write_length_prefixed(writer, challenge_server)?
+                    + write_length_prefixed(writer, ephemeral_public_key_server)?
+            }
+            AuthenticationResponseParameters::VerifyCertificateBidirectional {
+                challenge_server,
+                certificate_server,
+                proof_of_ownership_server,
+                ephemeral_public_key_server,
+            } => {
+                write_length_prefixed(writer, challenge_server)?
+                    + write_length_prefixed(writer, certificate_server)?
+                    + write_length_prefixed(writer, proof_of_ownership_server)?
+                    + write_length_prefixed(writer, ephemeral_public_key_server)?
+            }
+            AuthenticationResponseParameters::ProofOfOwnership { session_key_info } => {
+                write_length_prefixed(writer, session_key_info)?
+            }
+            AuthenticationResponseParameters::RequestChallengeForAuthentication {
+                algorithm_indicator,
+                challenge_server,
+                needed_additional_parameter,
+            } => {
+                write_all(writer, &algorithm_indicator).map_err(Error::io)?
+                    + write_length_prefixed(writer, challenge_server)?
+                    + write_length_prefixed(writer, needed_additional_parameter)?
+            }
+            AuthenticationResponseParameters::VerifyProofOfOwnershipUnidirectional {
+                algorithm_indicator,
+                session_key_info,
+            } => {
+                write_all(writer, &algorithm_indicator).map_err(Error::io)?
+                    + write_length_prefixed(writer, session_key_info)?
+            }
+            AuthenticationResponseParameters::VerifyProofOfOwnershipBidirectional {
+                algorithm_indicator,
+                proof_of_ownership_server,
+                session_key_info,
+            } => {
+                write_all(writer, &algorithm_indicator).map_err(Error::io)?
+                    + write_length_prefixed(writer, proof_of_ownership_server)?
+                    + write_length_prefixed(writer, session_key_info)?
+            }
+            AuthenticationResponseParameters::IsoSaeReserved { data, .. } => {
+                write_all(writer, data).map_err(Error::io)?
+            }
+        };
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for AuthenticationResponse<'a> {
+    type Error = crate::Error;
+
+    #[allow(clippy::too_many_lines)]
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.len() < 2 {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 2,
+                available: buf.len(),
+            }));
+        }
+        let authentication_type = AuthenticationType::try_from(buf[0])?;
+        let authentication_return_parameter = AuthenticationReturnParameter::from(buf[1]);
+        let rest = &buf[2..];
+        let (parameters, rest) = match authentication_type {
+            AuthenticationType::DeAuthenticate => {
+                (AuthenticationResponseParameters::DeAuthenticate, rest)
+            }
+            AuthenticationType::TransmitCertificate => {
+                (AuthenticationResponseParameters::TransmitCertificate, rest)
+            }
+            AuthenticationType::AuthenticationConfiguration => (
+                AuthenticationResponseParameters::AuthenticationConfiguration,
+                rest,
+            ),
+            AuthenticationType::VerifyCertificateUnidirectional => {
+                let (challenge_server, rest) = read_length_prefixed(rest)?;
+                let (ephemeral_public_key_server, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::VerifyCertificateUnidirectional {
+                        challenge_server,
+                        ephemeral_public_key_server,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::VerifyCertificateBidirectional => {
+                let (challenge_server, rest) = read_length_prefixed(rest)?;
+                let (certificate_server, rest) = read_length_prefixed(rest)?;
+                let (proof_of_ownership_server, rest) = read_length_prefixed(rest)?;
+                let (ephemeral_public_key_server, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::VerifyCertificateBidirectional {
+                        challenge_server,
+                        certificate_server,
+                        proof_of_ownership_server,
+                        ephemeral_public_key_server,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::ProofOfOwnership => {
+                let (session_key_info, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::ProofOfOwnership { session_key_info },
+                    rest,
+                )
+            }
+            AuthenticationType::RequestChallengeForAuthentication => {
+                let (algorithm_indicator, rest) = read_array(rest)?;
+                let (challenge_server, rest) = read_length_prefixed(rest)?;
+                let (needed_additional_parameter, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::RequestChallengeForAuthentication {
+                        algorithm_indicator,
+                        challenge_server,
+                        needed_additional_parameter,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::VerifyProofOfOwnershipUnidirectional => {
+                let (algorithm_indicator, rest) = read_array(rest)?;
+                let (session_key_info, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::VerifyProofOfOwnershipUnidirectional {
+                        algorithm_indicator,
+                        session_key_info,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::VerifyProofOfOwnershipBidirectional => {
+                let (algorithm_indicator, rest) = read_array(rest)?;
+                let (proof_of_ownership_server, rest) = read_length_prefixed(rest)?;
+                let (session_key_info, rest) = read_length_prefixed(rest)?;
+                (
+                    AuthenticationResponseParameters::VerifyProofOfOwnershipBidirectional {
+                        algorithm_indicator,
+                        proof_of_ownership_server,
+                        session_key_info,
+                    },
+                    rest,
+                )
+            }
+            AuthenticationType::IsoSaeReserved(_) => (
+                AuthenticationResponseParameters::IsoSaeReserved {
+                    authentication_type,
+                    data: rest,
+                },
+                &[][..],
+            ),
+        };
+        Ok((
+            Self {
+                authentication_return_parameter,
+                parameters,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    const OID: [u8; ALGORITHM_INDICATOR_LEN] = [
+        0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+
+    #[test]
+    fn authentication_type_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            match AuthenticationType::try_from(i) {
+                Ok(value) => assert_eq!(u8::from(value), i),
+                Err(Error::InvalidAuthenticationType(value)) => {
+                    assert_eq!(value, i);
+                    assert!(i >= 0x80);
+                }
+                _ => panic!("Invalid error type"),
+            }
+        }
+    }
+
+    #[test]
+    fn return_parameter_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(AuthenticationReturnParameter::from(i)), i);
+        }
+        assert_eq!(
+            AuthenticationReturnParameter::from(0x12),
+            AuthenticationReturnParameter::OwnershipVerifiedAuthenticationComplete
+        );
+        assert_eq!(
+            AuthenticationReturnParameter::from(0xA0),
+            AuthenticationReturnParameter::VehicleManufacturerSpecific(0xA0)
+        );
+        assert_eq!(
+            AuthenticationReturnParameter::from(0xFF),
+            AuthenticationReturnParameter::IsoSaeReserved(0xFF)
+        );
+    }
+
+    #[test]
+    fn verify_certificate_unidirectional_request() {
+        let bytes = [
+            0x01, // verifyCertificateUnidirectional
+            0x00, // communicationConfiguration
+            0x00, 0x03, 0xC0, 0xC1, 0xC2, // certificateClient
+            0x00, 0x00, // no challengeClient
+        ];
+        let (req, rest) = <AuthenticationRequest as Decode>::decode(&bytes).unwrap();
+        assert!(rest.is_empty());
+        assert!(!req.suppress_positive_response);
+        assert_eq!(
+            req.parameters,
+            AuthenticationRequestParameters::VerifyCertificateUnidirectional {
+                communication_configuration: 0x00,
+                certificate_client: &[0xC0, 0xC1, 0xC2],
+                challenge_client: &[],
+            }
+        );
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &bytes);
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn verify_proof_of_ownership_request_roundtrip() {
+        let req = AuthenticationRequest::new(
+            true,
+            AuthenticationRequestParameters::VerifyProofOfOwnershipBidirectional {
+                algorithm_indicator: OID,
+                proof_of_ownership_client: &[0x01, 0x02],
+                challenge_client: &[0x03],
+                additional_parameter: &[],
+            },
+        );
+        let mut buf = [0u8; 32];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(buf[0], 0x87);
+        assert_eq!(written, 1 + ALGORITHM_INDICATOR_LEN + 4 + 3 + 2);
+        let (decoded, rest) = <AuthenticationRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(
+            decoded.authentication_type(),
+            AuthenticationType::VerifyProofOfOwnershipBidirectional
+        );
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn transmit_certificate_request_roundtrip() {
+        let req = AuthenticationRequest::new(
+            false,
+            AuthenticationRequestParameters::TransmitCertificate {
+                certificate_evaluation_id: 0x0102,
+                certificate_data: &[0xAA, 0xBB],
+            },
+        );
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x04, 0x01, 0x02, 0x00, 0x02, 0xAA, 0xBB]);
+        let (decoded, _) = <AuthenticationRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert_eq!(decoded, req);
+    }
+
+    #[test]
+    fn request_rejects_truncated_length_prefixed_field() {
+        // certificateClient announces 4 bytes, only 2 follow
+        let err = <AuthenticationRequest as Decode>::decode(&[0x02, 0x00, 0x00, 0x04, 0xC0, 0xC1]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 4 && i.available == 2)
+        );
+    }
+
+    #[test]
+    fn reserved_request_keeps_raw_bytes() {
+        let (req, rest) = <AuthenticationRequest as Decode>::decode(&[0x09, 0xDE, 0xAD]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            req.authentication_type(),
+            AuthenticationType::IsoSaeReserved(0x09)
+        );
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x09, 0xDE, 0xAD]);
+    }
+
+    #[test]
+    fn authentication_configuration_response() {
+        let bytes = [0x08, 0x02];
+        let (response, rest) = <AuthenticationResponse as Decode>::decode(&bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            response.authentication_return_parameter,
+            AuthenticationReturnParameter::AuthenticationConfigurationApce
+        );
+        assert_eq!(
+            response.parameters,
+            AuthenticationResponseParameters::AuthenticationConfiguration
+        );
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn verify_certificate_bidirectional_response_roundtrip() {
+        let response = AuthenticationResponse::new(
+            AuthenticationReturnParameter::CertificateVerifiedOwnershipVerificationNecessary,
+            AuthenticationResponseParameters::VerifyCertificateBidirectional {
+                challenge_server: &[0x11, 0x22],
+                certificate_server: &[0x33],
+                proof_of_ownership_server: &[0x44, 0x55, 0x66],
+                ephemeral_public_key_server: &[],
+            },
+        );
+        let mut buf = [0u8; 32];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(
+            &buf[..written],
+            &[
+                0x02, 0x11, 0x00, 0x02, 0x11, 0x22, 0x00, 0x01, 0x33, 0x00, 0x03, 0x44, 0x55, 0x66,
+                0x00, 0x00,
+            ]
+        );
+        let (decoded, rest) = <AuthenticationResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn request_challenge_response_roundtrip() {
+        let response = AuthenticationResponse::new(
+            AuthenticationReturnParameter::RequestAccepted,
+            AuthenticationResponseParameters::RequestChallengeForAuthentication {
+                algorithm_indicator: OID,
+                challenge_server: &[0x01, 0x02, 0x03, 0x04],
+                needed_additional_parameter: &[],
+            },
+        );
+        let mut buf = [0u8; 32];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        let (decoded, _) = <AuthenticationResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert_eq!(decoded, response);
+        assert_eq!(
+            decoded.authentication_type(),
+            AuthenticationType::RequestChallengeForAuthentication
+        );
+    }
+
+    #[test]
+    fn response_rejects_short_buffer() {
+        let err = <AuthenticationResponse as Decode>::decode(&[0x00]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 2 && i.available == 1)
+        );
+        // algorithmIndicator is truncated
+        let err = <AuthenticationResponse as Decode>::decode(&[0x06, 0x00, 0x06, 0x09]);
+        assert!(matches!(err, Err(Error::InsufficientData(i)) if i.needed == 16));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<AuthenticationRequest<'static>>();
+        assert_impl_eq::<AuthenticationResponse<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<AuthenticationRequest<'static>>();
+            assert_impl_serde::<AuthenticationResponse<'static>>();
+        }
+    }
+}
@@ -342,6 +342,14 @@ impl CommunicationControlRequest {
         self.control_type.suppress_positive_response()
     }
 
+    /// Setter for whether a positive response should be suppressed
+    pub fn set_suppress_positive_response(&mut self, suppress_positive_response: bool) {
+        self.control_type = SuppressablePositiveResponse::new(
+            suppress_positive_response,
+            self.control_type.value(),
+        );
+    }
+
     /// Getter for the requested [`CommunicationControlType`]
     #[must_use]
     pub fn control_type(&self) -> CommunicationControlType {
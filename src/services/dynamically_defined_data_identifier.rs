@@ -0,0 +1,800 @@
+//! `DynamicallyDefinedDataIdentifier` (0x2C) service implementation
+use crate::shared::{MemoryFormatIdentifier, SuppressablePositiveResponse};
+use crate::{Decode, Encode, Error, NegativeResponseCode, UdsIdentifier};
+use automotive_wire_codec::{read_u8, read_u16_be, write_all, write_u8, write_u16_be};
+
+const DYNAMICALLY_DEFINE_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 5] = [
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+];
+
+/// Size of one `defineByIdentifier` source definition on the wire.
+const SOURCE_DATA_IDENTIFIER_DEFINITION_LEN: usize = 4;
+
+/// `definitionType` sub-function of the `DynamicallyDefineDataIdentifier` service
+///
+/// See ISO-14229-1:2020, Section 11.6.2.2
+///
+/// *Note*:
+///
+/// Conversions from `u8` to `DynamicDefinitionType` are fallible and will return an [`Error`](crate::Error) if the
+/// Suppress Positive Response bit is set.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DynamicDefinitionType {
+    /// Define a data identifier from parts of other data identifiers
+    DefineByIdentifier,
+    /// Define a data identifier from areas of server memory
+    DefineByMemoryAddress,
+    /// Clear one dynamically defined data identifier, or all of them
+    ClearDynamicallyDefinedDataIdentifier,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    ///
+    /// Construct through [`DynamicDefinitionType::try_from`] so the raw byte is
+    /// range-checked and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    IsoSaeReserved(u8),
+}
+
+impl From<DynamicDefinitionType> for u8 {
+    fn from(value: DynamicDefinitionType) -> Self {
+        match value {
+            DynamicDefinitionType::DefineByIdentifier => 0x01,
+            DynamicDefinitionType::DefineByMemoryAddress => 0x02,
+            DynamicDefinitionType::ClearDynamicallyDefinedDataIdentifier => 0x03,
+            DynamicDefinitionType::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for DynamicDefinitionType {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            0x01 => Ok(Self::DefineByIdentifier),
+            0x02 => Ok(Self::DefineByMemoryAddress),
+            0x03 => Ok(Self::ClearDynamicallyDefinedDataIdentifier),
+            0x00 | 0x04..=0x7F => Ok(Self::IsoSaeReserved(value)),
+            _ => Err(Error::InvalidDynamicDefinitionType(value)),
+        }
+    }
+}
+
+/// One `defineByIdentifier` source: `memory_size` bytes of the record of `source_identifier`,
+/// starting at the 1-based `position_in_source_data_record`.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SourceDataIdentifierDefinition {
+    /// The data identifier to copy bytes from.
+    pub source_identifier: UdsIdentifier,
+    /// 1-based position of the first byte to copy within the source data record.
+    pub position_in_source_data_record: u8,
+    /// Number of bytes to copy.
+    pub memory_size: u8,
+}
+
+impl SourceDataIdentifierDefinition {
+    /// Create a new source definition.
+    #[must_use]
+    pub const fn new(
+        source_identifier: UdsIdentifier,
+        position_in_source_data_record: u8,
+        memory_size: u8,
+    ) -> Self {
+        Self {
+            source_identifier,
+            position_in_source_data_record,
+            memory_size,
+        }
+    }
+}
+
+/// One `defineByMemoryAddress` source: `memory_size` bytes of server memory at `memory_address`.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MemoryAddressDefinition {
+    /// Starting address of the server memory (max 5 bytes on the wire).
+    pub memory_address: u64,
+    /// Number of bytes to copy (max 4 bytes on the wire).
+    pub memory_size: u32,
+}
+
+impl MemoryAddressDefinition {
+    /// Create a new memory source definition.
+    #[must_use]
+    pub const fn new(memory_address: u64, memory_size: u32) -> Self {
+        Self {
+            memory_address,
+            memory_size,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum IdentifierSources<'d> {
+    Native(&'d [SourceDataIdentifierDefinition]),
+    Wire(&'d [u8]),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MemorySources<'d> {
+    Native(&'d [MemoryAddressDefinition]),
+    Wire(&'d [u8]),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Definition<'d> {
+    ByIdentifier {
+        identifier: UdsIdentifier,
+        sources: IdentifierSources<'d>,
+    },
+    ByMemoryAddress {
+        identifier: UdsIdentifier,
+        address_and_length_format_identifier: MemoryFormatIdentifier,
+        sources: MemorySources<'d>,
+    },
+    Clear(Option<UdsIdentifier>),
+    IsoSaeReserved {
+        definition_type: DynamicDefinitionType,
+        data: &'d [u8],
+    },
+}
+
+/// Lazy iterator over the [`SourceDataIdentifierDefinition`]s of a `defineByIdentifier` request.
+#[derive(Clone, Debug)]
+pub struct SourceDataIdentifierIter<'d> {
+    sources: IdentifierSources<'d>,
+}
+
+impl Iterator for SourceDataIdentifierIter<'_> {
+    type Item = SourceDataIdentifierDefinition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.sources {
+            IdentifierSources::Native(s) => {
+                let (first, rest) = s.split_first()?;
+                *s = rest;
+                Some(*first)
+            }
+            IdentifierSources::Wire(b) => {
+                let (record, rest) =
+                    b.split_first_chunk::<SOURCE_DATA_IDENTIFIER_DEFINITION_LEN>()?;
+                *b = rest;
+                Some(SourceDataIdentifierDefinition {
+                    source_identifier: UdsIdentifier::from(u16::from_be_bytes([
+                        record[0], record[1],
+                    ])),
+                    position_in_source_data_record: record[2],
+                    memory_size: record[3],
+                })
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.sources {
+            IdentifierSources::Native(s) => s.len(),
+            IdentifierSources::Wire(b) => b.len() / SOURCE_DATA_IDENTIFIER_DEFINITION_LEN,
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SourceDataIdentifierIter<'_> {}
+
+/// Lazy iterator over the [`MemoryAddressDefinition`]s of a `defineByMemoryAddress` request.
+#[derive(Clone, Debug)]
+pub struct MemoryAddressDefinitionIter<'d> {
+    address_and_length_format_identifier: MemoryFormatIdentifier,
+    sources: MemorySources<'d>,
+}
+
+impl Iterator for MemoryAddressDefinitionIter<'_> {
+    type Item = MemoryAddressDefinition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.sources {
+            MemorySources::Native(s) => {
+                let (first, rest) = s.split_first()?;
+                *s = rest;
+                Some(*first)
+            }
+            MemorySources::Wire(b) => {
+                // Decoding checked the length is a whole number of definitions.
+                let (memory_address, memory_size, rest) = self
+                    .address_and_length_format_identifier
+                    .decode_parameters(b)
+                    .ok()?;
+                *b = rest;
+                Some(MemoryAddressDefinition {
+                    memory_address,
+                    memory_size,
+                })
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.sources {
+            MemorySources::Native(s) => s.len(),
+            MemorySources::Wire(b) => {
+                b.len() / self.address_and_length_format_identifier.parameter_len()
+            }
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for MemoryAddressDefinitionIter<'_> {}
+
+/// Request to define or clear a dynamically defined data identifier (usually in the
+/// `0xF300..=0xF3FF` range, see [`UdsIdentifier::DynamicallyDefinedDataIdentifier`]).
+///
+/// Built from native source definition slices or borrowed from the wire;
+/// [`source_identifiers`](Self::source_identifiers) and
+/// [`memory_addresses`](Self::memory_addresses) iterate the definitions either way.
+///
+/// # serde / utoipa carve-out
+///
+/// serde and utoipa derives are omitted for the same reason as on
+/// [`ReadDataByIdentifierRequest`](crate::ReadDataByIdentifierRequest): the native
+/// `&[SourceDataIdentifierDefinition]` / `&[MemoryAddressDefinition]` backings cannot be
+/// deserialized without an owned allocation.
+///
+/// See ISO-14229-1:2020, Section 11.6.2.1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DynamicallyDefinedDataIdentifierRequest<'d> {
+    /// Whether a positive response should be suppressed.
+    pub suppress_positive_response: bool,
+    definition: Definition<'d>,
+}
+
+impl<'d> DynamicallyDefinedDataIdentifierRequest<'d> {
+    /// Define `identifier` as the concatenation of the given parts of other data identifiers.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoDefinitionSources`] if `sources` is empty.
+    pub const fn define_by_identifier(
+        suppress_positive_response: bool,
+        identifier: UdsIdentifier,
+        sources: &'d [SourceDataIdentifierDefinition],
+    ) -> Result<Self, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoDefinitionSources);
+        }
+        Ok(Self {
+            suppress_positive_response,
+            definition: Definition::ByIdentifier {
+                identifier,
+                sources: IdentifierSources::Native(sources),
+            },
+        })
+    }
+
+    /// Define `identifier` as the concatenation of the given areas of server memory.
+    ///
+    /// A single `addressAndLengthFormatIdentifier` is shared by all definitions, so its
+    /// widths are derived from the largest address and size.
+    ///
+    /// # Errors
+    /// Returns [`Error::NoDefinitionSources`] if `sources` is empty, and
+    /// [`Error::InvalidMemoryAddress`] if an address exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    pub fn define_by_memory_address(
+        suppress_positive_response: bool,
+        identifier: UdsIdentifier,
+        sources: &'d [MemoryAddressDefinition],
+    ) -> Result<Self, Error> {
+        if sources.is_empty() {
+            return Err(Error::NoDefinitionSources);
+        }
+        let max_address = sources.iter().map(|s| s.memory_address).max().unwrap_or(0);
+        let max_size = sources.iter().map(|s| s.memory_size).max().unwrap_or(0);
+        Ok(Self {
+            suppress_positive_response,
+            definition: Definition::ByMemoryAddress {
+                identifier,
+                address_and_length_format_identifier: MemoryFormatIdentifier::for_address_and_size(
+                    max_address,
+                    max_size,
+                )?,
+                sources: MemorySources::Native(sources),
+            },
+        })
+    }
+
+    /// Clear the definition of `identifier`, or of every dynamically defined data identifier
+    /// if `identifier` is `None`.
+    #[must_use]
+    pub const fn clear(
+        suppress_positive_response: bool,
+        identifier: Option<UdsIdentifier>,
+    ) -> Self {
+        Self {
+            suppress_positive_response,
+            definition: Definition::Clear(identifier),
+        }
+    }
+
+    /// The requested [`DynamicDefinitionType`].
+    #[must_use]
+    pub const fn definition_type(&self) -> DynamicDefinitionType {
+        match self.definition {
+            Definition::ByIdentifier { .. } => DynamicDefinitionType::DefineByIdentifier,
+            Definition::ByMemoryAddress { .. } => DynamicDefinitionType::DefineByMemoryAddress,
+            Definition::Clear(_) => DynamicDefinitionType::ClearDynamicallyDefinedDataIdentifier,
+            Definition::IsoSaeReserved {
+                definition_type, ..
+            } => definition_type,
+        }
+    }
+
+    /// The dynamically defined data identifier being defined or cleared.
+    ///
+    /// `None` for a request clearing all identifiers, or for a reserved sub-function.
+    #[must_use]
+    pub const fn identifier(&self) -> Option<UdsIdentifier> {
+        match self.definition {
+            Definition::ByIdentifier { identifier, .. }
+            | Definition::ByMemoryAddress { identifier, .. } => Some(identifier),
+            Definition::Clear(identifier) => identifier,
+            Definition::IsoSaeReserved { .. } => None,
+        }
+    }
+
+    /// Iterate the source definitions of a `defineByIdentifier` request.
+    ///
+    /// `None` for any other [`DynamicDefinitionType`].
+    #[must_use]
+    pub const fn source_identifiers(&self) -> Option<SourceDataIdentifierIter<'d>> {
+        match self.definition {
+            Definition::ByIdentifier { sources, .. } => Some(SourceDataIdentifierIter { sources }),
+            _ => None,
+        }
+    }
+
+    /// Iterate the source definitions of a `defineByMemoryAddress` request.
+    ///
+    /// `None` for any other [`DynamicDefinitionType`].
+    #[must_use]
+    pub const fn memory_addresses(&self) -> Option<MemoryAddressDefinitionIter<'d>> {
+        match self.definition {
+            Definition::ByMemoryAddress {
+                address_and_length_format_identifier,
+                sources,
+                ..
+            } => Some(MemoryAddressDefinitionIter {
+                address_and_length_format_identifier,
+                sources,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Raw parameter bytes of a reserved sub-function, kept for pass-through.
+    ///
+    /// `None` for any defined [`DynamicDefinitionType`].
+    #[must_use]
+    pub const fn reserved_data(&self) -> Option<&'d [u8]> {
+        match self.definition {
+            Definition::IsoSaeReserved { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &DYNAMICALLY_DEFINE_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for DynamicallyDefinedDataIdentifierRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let sub_function = SuppressablePositiveResponse::new(
+            self.suppress_positive_response,
+            self.definition_type(),
+        );
+        let mut written = write_u8(writer, u8::from(sub_function)).map_err(Error::io)?;
+        if let Some(identifier) = self.identifier() {
+            written += write_u16_be(writer, u16::from(identifier)).map_err(Error::io)?;
+        }
+        match self.definition {
+            Definition::ByIdentifier {
+                sources: IdentifierSources::Native(sources),
+                ..
+            } => {
+                for source in sources {
+                    written += write_u16_be(writer, u16::from(source.source_identifier))
+                        .map_err(Error::io)?;
+                    written += write_all(
+                        writer,
+                        &[source.position_in_source_data_record, source.memory_size],
+                    )
+                    .map_err(Error::io)?;
+                }
+            }
+            Definition::ByMemoryAddress {
+                address_and_length_format_identifier,
+                sources: MemorySources::Native(sources),
+                ..
+            } => {
+                written += write_u8(writer, address_and_length_format_identifier.into())
+                    .map_err(Error::io)?;
+                for source in sources {
+                    written += address_and_length_format_identifier.encode_parameters(
+                        writer,
+                        source.memory_address,
+                        source.memory_size,
+                    )?;
+                }
+            }
+            Definition::ByMemoryAddress {
+                address_and_length_format_identifier,
+                sources: MemorySources::Wire(data),
+                ..
+            } => {
+                written += write_u8(writer, address_and_length_format_identifier.into())
+                    .map_err(Error::io)?;
+                written += write_all(writer, data).map_err(Error::io)?;
+            }
+            Definition::ByIdentifier {
+                sources: IdentifierSources::Wire(data),
+                ..
+            }
+            | Definition::IsoSaeReserved { data, .. } => {
+                written += write_all(writer, data).map_err(Error::io)?;
+            }
+            Definition::Clear(_) => {}
+        }
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for DynamicallyDefinedDataIdentifierRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (sub_function, rest) = read_u8(buf)?;
+        let sub_function =
+            SuppressablePositiveResponse::<DynamicDefinitionType>::try_from(sub_function)?;
+        let (definition, rest) = match sub_function.value() {
+            DynamicDefinitionType::DefineByIdentifier => {
+                let (identifier, sources) = read_u16_be(rest)?;
+                if sources.is_empty() || sources.len() % SOURCE_DATA_IDENTIFIER_DEFINITION_LEN != 0
+                {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                }
+                (
+                    Definition::ByIdentifier {
+                        identifier: UdsIdentifier::from(identifier),
+                        sources: IdentifierSources::Wire(sources),
+                    },
+                    &[][..],
+                )
+            }
+            DynamicDefinitionType::DefineByMemoryAddress => {
+                let (identifier, rest) = read_u16_be(rest)?;
+                let (format, sources) = read_u8(rest)?;
+                let address_and_length_format_identifier =
+                    MemoryFormatIdentifier::try_from(format)?;
+                if sources.is_empty()
+                    || sources.len() % address_and_length_format_identifier.parameter_len() != 0
+                {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                }
+                (
+                    Definition::ByMemoryAddress {
+                        identifier: UdsIdentifier::from(identifier),
+                        address_and_length_format_identifier,
+                        sources: MemorySources::Wire(sources),
+                    },
+                    &[][..],
+                )
+            }
+            DynamicDefinitionType::ClearDynamicallyDefinedDataIdentifier => {
+                if rest.is_empty() {
+                    (Definition::Clear(None), rest)
+                } else {
+                    let (identifier, rest) = read_u16_be(rest)?;
+                    (
+                        Definition::Clear(Some(UdsIdentifier::from(identifier))),
+                        rest,
+                    )
+                }
+            }
+            definition_type @ DynamicDefinitionType::IsoSaeReserved(_) => (
+                Definition::IsoSaeReserved {
+                    definition_type,
+                    data: rest,
+                },
+                &[][..],
+            ),
+        };
+        Ok((
+            Self {
+                suppress_positive_response: sub_function.suppress_positive_response(),
+                definition,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Positive response to `DynamicallyDefineDataIdentifier`: echoes the definition type and,
+/// unless all identifiers were cleared, the dynamically defined data identifier.
+///
+/// See ISO-14229-1:2020, Section 11.6.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DynamicallyDefinedDataIdentifierResponse {
+    /// The definition type echoed from the request.
+    pub definition_type: DynamicDefinitionType,
+    /// The dynamically defined data identifier echoed from the request (`None` when the
+    /// request cleared all identifiers).
+    pub identifier: Option<UdsIdentifier>,
+}
+
+impl DynamicallyDefinedDataIdentifierResponse {
+    /// Create a new response.
+    #[must_use]
+    pub const fn new(
+        definition_type: DynamicDefinitionType,
+        identifier: Option<UdsIdentifier>,
+    ) -> Self {
+        Self {
+            definition_type,
+            identifier,
+        }
+    }
+}
+
+impl Encode for DynamicallyDefinedDataIdentifierResponse {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u8(writer, u8::from(self.definition_type)).map_err(Error::io)?;
+        if let Some(identifier) = self.identifier {
+            written += write_u16_be(writer, u16::from(identifier)).map_err(Error::io)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for DynamicallyDefinedDataIdentifierResponse {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (definition_type, rest) = read_u8(buf)?;
+        let definition_type = DynamicDefinitionType::try_from(definition_type)?;
+        // Only clearing every definition leaves out the DDDI.
+        let clear_all = definition_type
+            == DynamicDefinitionType::ClearDynamicallyDefinedDataIdentifier
+            && rest.is_empty();
+        let (identifier, rest) = if clear_all {
+            (None, rest)
+        } else {
+            let (identifier, rest) = read_u16_be(rest)?;
+            (Some(UdsIdentifier::from(identifier)), rest)
+        };
+        Ok((
+            Self {
+                definition_type,
+                identifier,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn definition_type_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            match DynamicDefinitionType::try_from(i) {
+                Ok(value) => assert_eq!(u8::from(value), i),
+                Err(Error::InvalidDynamicDefinitionType(value)) => {
+                    assert_eq!(value, i);
+                    assert!(i >= 0x80);
+                }
+                _ => panic!("Invalid error type"),
+            }
+        }
+    }
+
+    #[test]
+    fn define_by_identifier_roundtrip() {
+        let sources = [
+            SourceDataIdentifierDefinition::new(UdsIdentifier::from(0x010A), 1, 2),
+            SourceDataIdentifierDefinition::new(UdsIdentifier::from(0x050B), 3, 1),
+        ];
+        let req = DynamicallyDefinedDataIdentifierRequest::define_by_identifier(
+            false,
+            UdsIdentifier::from(0xF301),
+            &sources,
+        )
+        .unwrap();
+        let wire = [
+            0x01, 0xF3, 0x01, 0x01, 0x0A, 0x01, 0x02, 0x05, 0x0B, 0x03, 0x01,
+        ];
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) =
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded.identifier(), Some(UdsIdentifier::from(0xF301)));
+        let iter = decoded.source_identifiers().unwrap();
+        assert_eq!(iter.len(), 2);
+        assert!(iter.eq(sources));
+        assert!(decoded.memory_addresses().is_none());
+        assert_encode_size_agrees(&decoded);
+    }
+
+    #[test]
+    fn define_by_memory_address_roundtrip() {
+        let sources = [
+            MemoryAddressDefinition::new(0x2000_0000, 4),
+            MemoryAddressDefinition::new(0x2000_0100, 0x0102),
+        ];
+        let req = DynamicallyDefinedDataIdentifierRequest::define_by_memory_address(
+            true,
+            UdsIdentifier::from(0xF302),
+            &sources,
+        )
+        .unwrap();
+        let wire = [
+            0x82, 0xF3, 0x02, 0x24, // SPRMIB + defineByMemoryAddress, DDDI, ALFID
+            0x20, 0x00, 0x00, 0x00, 0x00, 0x04, // first source
+            0x20, 0x00, 0x01, 0x00, 0x01, 0x02, // second source
+        ];
+        let mut buf = [0u8; 24];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, _) =
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&wire).unwrap();
+        assert!(decoded.suppress_positive_response);
+        assert_eq!(
+            decoded.definition_type(),
+            DynamicDefinitionType::DefineByMemoryAddress
+        );
+        assert!(decoded.memory_addresses().unwrap().eq(sources));
+        let mut reencoded = [0u8; 24];
+        let n = Encode::encode(&decoded, &mut reencoded.as_mut_slice()).unwrap();
+        assert_eq!(&reencoded[..n], &wire);
+    }
+
+    #[test]
+    fn define_rejects_partial_source_definitions() {
+        assert!(matches!(
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&[
+                0x01, 0xF3, 0x01, 0x01, 0x0A, 0x01
+            ]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&[
+                0x02, 0xF3, 0x01, 0x12, 0x20, 0x00
+            ]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&[0x01, 0xF3, 0x01]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn define_rejects_empty_sources() {
+        assert!(matches!(
+            DynamicallyDefinedDataIdentifierRequest::define_by_identifier(
+                false,
+                UdsIdentifier::from(0xF301),
+                &[]
+            ),
+            Err(Error::NoDefinitionSources)
+        ));
+        assert!(matches!(
+            DynamicallyDefinedDataIdentifierRequest::define_by_memory_address(
+                false,
+                UdsIdentifier::from(0xF301),
+                &[]
+            ),
+            Err(Error::NoDefinitionSources)
+        ));
+    }
+
+    #[test]
+    fn clear_with_and_without_identifier() {
+        let (all, _) =
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&[0x03]).unwrap();
+        assert_eq!(
+            all,
+            DynamicallyDefinedDataIdentifierRequest::clear(false, None)
+        );
+        assert_eq!(all.identifier(), None);
+
+        let one = DynamicallyDefinedDataIdentifierRequest::clear(
+            false,
+            Some(UdsIdentifier::from(0xF303)),
+        );
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&one, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x03, 0xF3, 0x03]);
+        let (decoded, _) =
+            <DynamicallyDefinedDataIdentifierRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert_eq!(decoded, one);
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let response = DynamicallyDefinedDataIdentifierResponse::new(
+            DynamicDefinitionType::DefineByIdentifier,
+            Some(UdsIdentifier::from(0xF301)),
+        );
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x01, 0xF3, 0x01]);
+        let (decoded, rest) =
+            <DynamicallyDefinedDataIdentifierResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+
+        let (clear_all, _) =
+            <DynamicallyDefinedDataIdentifierResponse as Decode>::decode(&[0x03]).unwrap();
+        assert_eq!(clear_all.identifier, None);
+    }
+
+    #[test]
+    fn response_rejects_truncated_identifier() {
+        let err = <DynamicallyDefinedDataIdentifierResponse as Decode>::decode(&[0x01, 0xF3]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 2 && i.available == 1)
+        );
+        for definition_type in [0x01, 0x02] {
+            assert!(matches!(
+                <DynamicallyDefinedDataIdentifierResponse as Decode>::decode(&[definition_type]),
+                Err(Error::InsufficientData(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<DynamicallyDefinedDataIdentifierRequest<'static>>();
+        assert_impl_eq::<DynamicallyDefinedDataIdentifierResponse>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            // Request: omitted — see struct-level doc comment for rationale
+            assert_impl_serde::<DynamicallyDefinedDataIdentifierResponse>();
+            assert_impl_serde::<SourceDataIdentifierDefinition>();
+            assert_impl_serde::<MemoryAddressDefinition>();
+        }
+    }
+}
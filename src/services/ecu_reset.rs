@@ -1,7 +1,8 @@
 //! `ECUReset` (0x11) service implementation
 use crate::shared::SuppressablePositiveResponse;
 use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
-use automotive_wire_codec::{write_all, write_u8};
+use automotive_wire_codec::{read_u8, write_u8};
+use core::time::Duration;
 
 /// UDS defines a number of different types of resets that can be requested
 /// The reset type is used to specify the type of reset that the ECU should perform
@@ -243,26 +244,74 @@ impl<'a> Decode<'a> for EcuResetRequest {
     }
 }
 
+/// `powerDownTime` value reporting that the time is not available or the request failed.
+const POWER_DOWN_TIME_NOT_AVAILABLE: u8 = 0xFF;
+
 /// Positive response to an `EcuResetRequest`
+///
+/// `powerDownTime` is present if and only if the reset type is
+/// [`ResetType::EnableRapidPowerShutDown`]; the constructor and decoder enforce this, and the
+/// encoder checks it again for deserialized values.
+///
+/// See ISO-14229-1:2020, Section 10.3.3.1
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct EcuResetResponse {
-    /// The reset type echoed from the request.
-    pub reset_type: ResetType,
-    /// Time in seconds before the server powers down (`0x00` = not available).
-    pub power_down_time: u8,
+    reset_type: ResetType,
+    power_down_time: Option<u8>,
 }
 
 impl EcuResetResponse {
     /// Create a new '`EcuResetResponse`'
-    #[must_use]
-    pub const fn new(reset_type: ResetType, power_down_time: u8) -> Self {
-        Self {
+    ///
+    /// # Errors
+    /// Returns [`Error::IncorrectMessageLengthOrInvalidFormat`] if `power_down_time` is given
+    /// for any reset type other than [`ResetType::EnableRapidPowerShutDown`], or missing for
+    /// that one.
+    pub fn new(reset_type: ResetType, power_down_time: Option<u8>) -> Result<Self, Error> {
+        let response = Self {
             reset_type,
             power_down_time,
+        };
+        response.validate()?;
+        Ok(response)
+    }
+
+    fn validate(self) -> Result<(), Error> {
+        if self.power_down_time.is_some()
+            != (self.reset_type == ResetType::EnableRapidPowerShutDown)
+        {
+            return Err(Error::IncorrectMessageLengthOrInvalidFormat);
         }
+        Ok(())
+    }
+
+    /// The reset type echoed from the request.
+    #[must_use]
+    pub const fn reset_type(&self) -> ResetType {
+        self.reset_type
+    }
+
+    /// The raw `powerDownTime` byte: seconds until the server powers down, or `0xFF` if the
+    /// time is not available.
+    ///
+    /// `None` unless the reset type is [`ResetType::EnableRapidPowerShutDown`].
+    #[must_use]
+    pub const fn power_down_time(&self) -> Option<u8> {
+        self.power_down_time
+    }
+
+    /// The `powerDownTime` as a [`Duration`].
+    ///
+    /// `None` if the response carries no `powerDownTime` or the server reported it as not
+    /// available (`0xFF`).
+    #[must_use]
+    pub fn power_down_duration(&self) -> Option<Duration> {
+        self.power_down_time
+            .filter(|&seconds| seconds != POWER_DOWN_TIME_NOT_AVAILABLE)
+            .map(|seconds| Duration::from_secs(u64::from(seconds)))
     }
 }
 
@@ -270,7 +319,13 @@ impl Encode for EcuResetResponse {
     type Error = crate::Error;
 
     fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
-        write_all(writer, &[u8::from(self.reset_type), self.power_down_time]).map_err(Error::io)
+        // A deserialized response bypasses `new`, so the invariant is checked again here.
+        self.validate()?;
+        let mut written = write_u8(writer, u8::from(self.reset_type)).map_err(Error::io)?;
+        if let Some(power_down_time) = self.power_down_time {
+            written += write_u8(writer, power_down_time).map_err(Error::io)?;
+        }
+        Ok(written)
     }
 }
 
@@ -278,22 +333,24 @@ impl<'a> Decode<'a> for EcuResetResponse {
     type Error = crate::Error;
 
     fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
-        if buf.is_empty() {
-            return Err(Error::InsufficientData(Incomplete {
-                needed: 1,
-                available: buf.len(),
-            }));
+        let (reset_type, rest) = read_u8(buf)?;
+        let reset_type = ResetType::try_from(reset_type)?;
+        if reset_type != ResetType::EnableRapidPowerShutDown {
+            return Ok((
+                Self {
+                    reset_type,
+                    power_down_time: None,
+                },
+                rest,
+            ));
         }
-        let reset_type = ResetType::try_from(buf[0])?;
-        // powerDownTime is conditional per ISO 14229-1
-        let power_down_time = buf.get(1).copied().unwrap_or(0);
-        let consumed = core::cmp::min(buf.len(), 2);
+        let (power_down_time, rest) = read_u8(rest)?;
         Ok((
             Self {
                 reset_type,
-                power_down_time,
+                power_down_time: Some(power_down_time),
             },
-            &buf[consumed..],
+            rest,
         ))
     }
 }
@@ -331,15 +388,68 @@ mod response {
     #[cfg(feature = "alloc")]
     #[test]
     fn ecu_reset_response() {
-        let bytes: [u8; 2] = [0x01, 0x20];
-        let resp = EcuResetResponse::new(ResetType::HardReset, 0x20);
+        let bytes: [u8; 1] = [0x01];
+        let resp = EcuResetResponse::new(ResetType::HardReset, None).unwrap();
         let mut buffer = Vec::new();
         let written = Encode::encode(&resp, &mut buffer).unwrap();
         let (result, _) = <EcuResetResponse as Decode>::decode(&bytes).unwrap();
         assert_eq!(result, resp);
+        assert_eq!(result.power_down_time(), None);
+        assert_eq!(result.power_down_duration(), None);
 
-        assert_eq!(written, 2);
+        assert_eq!(written, 1);
         assert_eq!(written, resp.encoded_size().unwrap());
         assert_encode_size_agrees(&resp);
     }
+
+    #[test]
+    fn rapid_power_shut_down_carries_power_down_time() {
+        let bytes: [u8; 2] = [0x04, 0x20];
+        let (result, rest) = <EcuResetResponse as Decode>::decode(&bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(result.reset_type(), ResetType::EnableRapidPowerShutDown);
+        assert_eq!(result.power_down_time(), Some(0x20));
+        assert_eq!(result.power_down_duration(), Some(Duration::from_secs(32)));
+
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&result, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &bytes);
+        assert_encode_size_agrees(&result);
+
+        // 0xFF: the server cannot tell how long it will stay awake.
+        let not_available =
+            EcuResetResponse::new(ResetType::EnableRapidPowerShutDown, Some(0xFF)).unwrap();
+        assert_eq!(not_available.power_down_time(), Some(0xFF));
+        assert_eq!(not_available.power_down_duration(), None);
+    }
+
+    #[test]
+    fn power_down_time_is_tied_to_reset_type() {
+        assert!(matches!(
+            EcuResetResponse::new(ResetType::HardReset, Some(0x20)),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            EcuResetResponse::new(ResetType::EnableRapidPowerShutDown, None),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            <EcuResetResponse as Decode>::decode(&[0x04]),
+            Err(Error::InsufficientData(_))
+        ));
+        let mut buf = [0u8; 4];
+        let unchecked = EcuResetResponse {
+            reset_type: ResetType::HardReset,
+            power_down_time: Some(0x20),
+        };
+        assert!(matches!(
+            Encode::encode(&unchecked, &mut buf.as_mut_slice()),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        // A powerDownTime after any other reset type is a trailing byte.
+        assert!(matches!(
+            <EcuResetResponse as Decode>::decode_exact(&[0x01, 0x20]),
+            Err(Error::TrailingBytes(_))
+        ));
+    }
 }
@@ -0,0 +1,329 @@
+//! `InputOutputControlByIdentifier` (0x2F) service implementation
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode, UdsIdentifier};
+use automotive_wire_codec::{write_all, write_u8, write_u16_be};
+
+const INPUT_OUTPUT_CONTROL_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 5] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+    NegativeResponseCode::AuthenticationRequired,
+];
+
+/// `inputOutputControlParameter` describes how the server shall control its input or output
+///
+/// See ISO-14229-1:2020, Table E.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InputOutputControlParameter {
+    /// Give control of the input/output signal back to the ECU
+    ReturnControlToEcu,
+    /// Set the input/output signal to its default value
+    ResetToDefault,
+    /// Freeze the input/output signal at its current value
+    FreezeCurrentState,
+    /// Set the input/output signal to the value given in the `controlState` record
+    ShortTermAdjustment,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl From<InputOutputControlParameter> for u8 {
+    fn from(value: InputOutputControlParameter) -> Self {
+        match value {
+            InputOutputControlParameter::ReturnControlToEcu => 0x00,
+            InputOutputControlParameter::ResetToDefault => 0x01,
+            InputOutputControlParameter::FreezeCurrentState => 0x02,
+            InputOutputControlParameter::ShortTermAdjustment => 0x03,
+            InputOutputControlParameter::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for InputOutputControlParameter {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::ReturnControlToEcu,
+            0x01 => Self::ResetToDefault,
+            0x02 => Self::FreezeCurrentState,
+            0x03 => Self::ShortTermAdjustment,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// Zero-alloc request to control an input or output of the server. Borrows the
+/// `controlState` and `controlEnableMask` records from the caller.
+///
+/// The lengths of `controlState` and `controlEnableMask` are defined by the server's
+/// configuration for `identifier` and are not present on the wire, so a decoded request
+/// carries every byte after the `inputOutputControlParameter` in `control_state`. Use
+/// [`split_control_enable_mask`](Self::split_control_enable_mask) to move the trailing mask
+/// bytes out once their length is known.
+///
+/// See ISO-14229-1:2020, Section 13.2.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct InputOutputControlByIdentifierRequest<'d> {
+    /// The Data Identifier of the input/output to control.
+    pub identifier: UdsIdentifier,
+    /// How the server shall control the input/output.
+    pub control_parameter: InputOutputControlParameter,
+    /// The `controlState` record (empty unless `control_parameter` needs one, e.g.
+    /// [`InputOutputControlParameter::ShortTermAdjustment`]).
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub control_state: &'d [u8],
+    /// The optional `controlEnableMask` record selecting which signals of a packeted
+    /// identifier are affected. Encoded after `control_state`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub control_enable_mask: Option<&'d [u8]>,
+}
+
+impl<'d> InputOutputControlByIdentifierRequest<'d> {
+    /// Create a new input/output control request.
+    #[must_use]
+    pub const fn new(
+        identifier: UdsIdentifier,
+        control_parameter: InputOutputControlParameter,
+        control_state: &'d [u8],
+        control_enable_mask: Option<&'d [u8]>,
+    ) -> Self {
+        Self {
+            identifier,
+            control_parameter,
+            control_state,
+            control_enable_mask,
+        }
+    }
+
+    /// Reinterpret the last `mask_len` bytes of `control_state` as the `controlEnableMask`
+    /// record.
+    ///
+    /// # Errors
+    /// - [`Error::IncorrectMessageLengthOrInvalidFormat`] if a mask is already present.
+    /// - [`Error::InsufficientData`] if `control_state` is shorter than `mask_len`.
+    pub fn split_control_enable_mask(&self, mask_len: usize) -> Result<Self, Error> {
+        if self.control_enable_mask.is_some() {
+            return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+        }
+        let Some(split) = self.control_state.len().checked_sub(mask_len) else {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: mask_len,
+                available: self.control_state.len(),
+            }));
+        };
+        let (control_state, control_enable_mask) = self.control_state.split_at(split);
+        Ok(Self {
+            control_state,
+            control_enable_mask: Some(control_enable_mask),
+            ..*self
+        })
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &INPUT_OUTPUT_CONTROL_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for InputOutputControlByIdentifierRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u16_be(writer, u16::from(self.identifier)).map_err(Error::io)?;
+        written += write_u8(writer, u8::from(self.control_parameter)).map_err(Error::io)?;
+        written += write_all(writer, self.control_state).map_err(Error::io)?;
+        if let Some(mask) = self.control_enable_mask {
+            written += write_all(writer, mask).map_err(Error::io)?;
+        }
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for InputOutputControlByIdentifierRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.len() < 3 {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 3,
+                available: buf.len(),
+            }));
+        }
+        Ok((
+            Self {
+                identifier: UdsIdentifier::from(u16::from_be_bytes([buf[0], buf[1]])),
+                control_parameter: InputOutputControlParameter::from(buf[2]),
+                control_state: &buf[3..],
+                control_enable_mask: None,
+            },
+            &[],
+        ))
+    }
+}
+
+/// Positive response to `InputOutputControlByIdentifier`: echoes the identifier and
+/// control parameter, followed by the current `controlState` of the input/output.
+///
+/// See ISO-14229-1:2020, Section 13.2.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct InputOutputControlByIdentifierResponse<'d> {
+    /// The Data Identifier echoed from the request.
+    pub identifier: UdsIdentifier,
+    /// The control parameter echoed from the request.
+    pub control_parameter: InputOutputControlParameter,
+    /// The `controlState` record reported by the server (may be empty).
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub control_state: &'d [u8],
+}
+
+impl<'d> InputOutputControlByIdentifierResponse<'d> {
+    /// Create a new input/output control response.
+    #[must_use]
+    pub const fn new(
+        identifier: UdsIdentifier,
+        control_parameter: InputOutputControlParameter,
+        control_state: &'d [u8],
+    ) -> Self {
+        Self {
+            identifier,
+            control_parameter,
+            control_state,
+        }
+    }
+}
+
+impl Encode for InputOutputControlByIdentifierResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u16_be(writer, u16::from(self.identifier)).map_err(Error::io)?;
+        written += write_u8(writer, u8::from(self.control_parameter)).map_err(Error::io)?;
+        written += write_all(writer, self.control_state).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for InputOutputControlByIdentifierResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.len() < 3 {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 3,
+                available: buf.len(),
+            }));
+        }
+        Ok((
+            Self {
+                identifier: UdsIdentifier::from(u16::from_be_bytes([buf[0], buf[1]])),
+                control_parameter: InputOutputControlParameter::from(buf[2]),
+                control_state: &buf[3..],
+            },
+            &[],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn control_parameter_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(InputOutputControlParameter::from(i)), i);
+        }
+        assert_eq!(
+            InputOutputControlParameter::from(0x03),
+            InputOutputControlParameter::ShortTermAdjustment
+        );
+    }
+
+    #[test]
+    fn short_term_adjustment_request_roundtrip() {
+        let req = InputOutputControlByIdentifierRequest::new(
+            UdsIdentifier::from(0x9B00),
+            InputOutputControlParameter::ShortTermAdjustment,
+            &[0x3C],
+            Some(&[0x80]),
+        );
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x9B, 0x00, 0x03, 0x3C, 0x80]);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) =
+            <InputOutputControlByIdentifierRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded.control_state, &[0x3C, 0x80]);
+        assert_eq!(decoded.control_enable_mask, None);
+        assert_eq!(decoded.split_control_enable_mask(1).unwrap(), req);
+    }
+
+    #[test]
+    fn split_control_enable_mask_rejects_too_long_mask() {
+        let (decoded, _) =
+            <InputOutputControlByIdentifierRequest as Decode>::decode(&[0x01, 0x32, 0x00, 0xFF])
+                .unwrap();
+        assert!(matches!(
+            decoded.split_control_enable_mask(2),
+            Err(Error::InsufficientData(i)) if i.needed == 2 && i.available == 1
+        ));
+        let split = decoded.split_control_enable_mask(1).unwrap();
+        assert!(split.control_state.is_empty());
+        assert!(matches!(
+            split.split_control_enable_mask(0),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn request_rejects_short_buffer() {
+        let err = <InputOutputControlByIdentifierRequest as Decode>::decode(&[0x9B, 0x00]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 3 && i.available == 2)
+        );
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let response = InputOutputControlByIdentifierResponse::new(
+            UdsIdentifier::from(0x9B00),
+            InputOutputControlParameter::ReturnControlToEcu,
+            &[0x0A],
+        );
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x9B, 0x00, 0x00, 0x0A]);
+        let (decoded, rest) =
+            <InputOutputControlByIdentifierResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<InputOutputControlByIdentifierRequest<'static>>();
+        assert_impl_eq::<InputOutputControlByIdentifierResponse<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<InputOutputControlByIdentifierRequest<'static>>();
+            assert_impl_serde::<InputOutputControlByIdentifierResponse<'static>>();
+        }
+    }
+}
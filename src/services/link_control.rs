@@ -0,0 +1,578 @@
+//! `LinkControl` (0x87) service implementation
+use crate::shared::SuppressablePositiveResponse;
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use automotive_wire_codec::{read_array, read_u8, write_all, write_u8};
+
+const LINK_CONTROL_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 5] = [
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::RequestSequenceError,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+];
+
+/// Largest baudrate that fits the 3-byte `linkRecord`.
+const MAX_SPECIFIC_BAUDRATE: u32 = 0x00FF_FFFF;
+
+/// `linkControlType` sub-function of the `LinkControl` service
+///
+/// See ISO-14229-1:2020, Section 10.12.2.2
+///
+/// *Note*:
+///
+/// Conversions from `u8` to `LinkControlType` are fallible and will return an [`Error`](crate::Error) if the
+/// Suppress Positive Response bit is set.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LinkControlType {
+    /// Check that the server can switch to a predefined [`LinkControlModeIdentifier`]
+    VerifyModeTransitionWithFixedParameter,
+    /// Check that the server can switch to a specific baudrate
+    VerifyModeTransitionWithSpecificParameter,
+    /// Switch to the previously verified mode
+    TransitionMode,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    ///
+    /// Construct through [`LinkControlType::try_from`] so the raw byte is
+    /// range-checked and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    IsoSaeReserved(u8),
+    /// Reserved for use by vehicle manufacturers.
+    ///
+    /// Construct through [`LinkControlType::try_from`] so the raw byte is range-checked
+    /// (`0x40..=0x5F`) and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    VehicleManufacturerSpecific(u8),
+    /// Reserved for use by system suppliers.
+    ///
+    /// Construct through [`LinkControlType::try_from`] so the raw byte is range-checked
+    /// (`0x60..=0x7E`) and can never collide with the SPRMIB bit.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    SystemSupplierSpecific(u8),
+}
+
+impl From<LinkControlType> for u8 {
+    #[allow(clippy::match_same_arms)]
+    fn from(value: LinkControlType) -> Self {
+        match value {
+            LinkControlType::VerifyModeTransitionWithFixedParameter => 0x01,
+            LinkControlType::VerifyModeTransitionWithSpecificParameter => 0x02,
+            LinkControlType::TransitionMode => 0x03,
+            LinkControlType::IsoSaeReserved(val) => val,
+            LinkControlType::VehicleManufacturerSpecific(val) => val,
+            LinkControlType::SystemSupplierSpecific(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for LinkControlType {
+    type Error = Error;
+    #[allow(clippy::match_same_arms)]
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            0x01 => Ok(Self::VerifyModeTransitionWithFixedParameter),
+            0x02 => Ok(Self::VerifyModeTransitionWithSpecificParameter),
+            0x03 => Ok(Self::TransitionMode),
+            0x00 | 0x04..=0x3F => Ok(Self::IsoSaeReserved(value)),
+            0x40..=0x5F => Ok(Self::VehicleManufacturerSpecific(value)),
+            0x60..=0x7E => Ok(Self::SystemSupplierSpecific(value)),
+            0x7F => Ok(Self::IsoSaeReserved(value)),
+            _ => Err(Error::InvalidLinkControlType(value)),
+        }
+    }
+}
+
+/// `linkControlModeIdentifier`: the predefined baudrates of
+/// `verifyModeTransitionWithFixedParameter`
+///
+/// See ISO-14229-1:2020, Annex B.3
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LinkControlModeIdentifier {
+    /// Standard PC baudrate of 9.6 kBaud
+    Pc9600Baud,
+    /// Standard PC baudrate of 19.2 kBaud
+    Pc19200Baud,
+    /// Standard PC baudrate of 38.4 kBaud
+    Pc38400Baud,
+    /// Standard PC baudrate of 57.6 kBaud
+    Pc57600Baud,
+    /// Standard PC baudrate of 115.2 kBaud
+    Pc115200Baud,
+    /// CAN baudrate of 125 kBaud
+    Can125000Baud,
+    /// CAN baudrate of 250 kBaud
+    Can250000Baud,
+    /// CAN baudrate of 500 kBaud
+    Can500000Baud,
+    /// CAN baudrate of 1000 kBaud
+    Can1000000Baud,
+    /// Switch to the manufacturer-defined programming setup
+    ProgrammingSetup,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl LinkControlModeIdentifier {
+    /// The baudrate in bit/s, or `None` for [`ProgrammingSetup`](Self::ProgrammingSetup)
+    /// and reserved values.
+    #[must_use]
+    pub const fn baudrate(&self) -> Option<u32> {
+        match self {
+            Self::Pc9600Baud => Some(9_600),
+            Self::Pc19200Baud => Some(19_200),
+            Self::Pc38400Baud => Some(38_400),
+            Self::Pc57600Baud => Some(57_600),
+            Self::Pc115200Baud => Some(115_200),
+            Self::Can125000Baud => Some(125_000),
+            Self::Can250000Baud => Some(250_000),
+            Self::Can500000Baud => Some(500_000),
+            Self::Can1000000Baud => Some(1_000_000),
+            Self::ProgrammingSetup | Self::IsoSaeReserved(_) => None,
+        }
+    }
+}
+
+impl From<LinkControlModeIdentifier> for u8 {
+    fn from(value: LinkControlModeIdentifier) -> Self {
+        match value {
+            LinkControlModeIdentifier::Pc9600Baud => 0x01,
+            LinkControlModeIdentifier::Pc19200Baud => 0x02,
+            LinkControlModeIdentifier::Pc38400Baud => 0x03,
+            LinkControlModeIdentifier::Pc57600Baud => 0x04,
+            LinkControlModeIdentifier::Pc115200Baud => 0x05,
+            LinkControlModeIdentifier::Can125000Baud => 0x10,
+            LinkControlModeIdentifier::Can250000Baud => 0x11,
+            LinkControlModeIdentifier::Can500000Baud => 0x12,
+            LinkControlModeIdentifier::Can1000000Baud => 0x13,
+            LinkControlModeIdentifier::ProgrammingSetup => 0x20,
+            LinkControlModeIdentifier::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for LinkControlModeIdentifier {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => Self::Pc9600Baud,
+            0x02 => Self::Pc19200Baud,
+            0x03 => Self::Pc38400Baud,
+            0x04 => Self::Pc57600Baud,
+            0x05 => Self::Pc115200Baud,
+            0x10 => Self::Can125000Baud,
+            0x11 => Self::Can250000Baud,
+            0x12 => Self::Can500000Baud,
+            0x13 => Self::Can1000000Baud,
+            0x20 => Self::ProgrammingSetup,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LinkControl<'d> {
+    FixedParameter(LinkControlModeIdentifier),
+    SpecificParameter(u32),
+    TransitionMode,
+    Other {
+        link_control_type: LinkControlType,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        data: &'d [u8],
+    },
+}
+
+/// Request for the server to verify, then switch to, a new communication link mode
+/// (usually a faster baudrate for flashing).
+///
+/// See ISO-14229-1:2020, Section 10.12.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct LinkControlRequest<'d> {
+    /// Whether a positive response should be suppressed.
+    pub suppress_positive_response: bool,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    link_control: LinkControl<'d>,
+}
+
+impl<'d> LinkControlRequest<'d> {
+    /// Verify that the server can switch to the predefined `mode`.
+    #[must_use]
+    pub const fn verify_fixed_parameter(
+        suppress_positive_response: bool,
+        mode: LinkControlModeIdentifier,
+    ) -> Self {
+        Self {
+            suppress_positive_response,
+            link_control: LinkControl::FixedParameter(mode),
+        }
+    }
+
+    /// Verify that the server can switch to `baudrate` (in bit/s).
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidBaudrate`] if `baudrate` does not fit in 3 bytes (> `0xFF_FFFF`).
+    pub const fn verify_specific_parameter(
+        suppress_positive_response: bool,
+        baudrate: u32,
+    ) -> Result<Self, Error> {
+        if baudrate > MAX_SPECIFIC_BAUDRATE {
+            return Err(Error::InvalidBaudrate(baudrate));
+        }
+        Ok(Self {
+            suppress_positive_response,
+            link_control: LinkControl::SpecificParameter(baudrate),
+        })
+    }
+
+    /// Switch to the previously verified mode.
+    ///
+    /// The positive response is usually suppressed here, as the server switches
+    /// modes right after sending it.
+    #[must_use]
+    pub const fn transition_mode(suppress_positive_response: bool) -> Self {
+        Self {
+            suppress_positive_response,
+            link_control: LinkControl::TransitionMode,
+        }
+    }
+
+    /// The requested [`LinkControlType`].
+    #[must_use]
+    pub const fn link_control_type(&self) -> LinkControlType {
+        match self.link_control {
+            LinkControl::FixedParameter(_) => {
+                LinkControlType::VerifyModeTransitionWithFixedParameter
+            }
+            LinkControl::SpecificParameter(_) => {
+                LinkControlType::VerifyModeTransitionWithSpecificParameter
+            }
+            LinkControl::TransitionMode => LinkControlType::TransitionMode,
+            LinkControl::Other {
+                link_control_type, ..
+            } => link_control_type,
+        }
+    }
+
+    /// The predefined mode of a `verifyModeTransitionWithFixedParameter` request.
+    ///
+    /// `None` for any other [`LinkControlType`].
+    #[must_use]
+    pub const fn mode_identifier(&self) -> Option<LinkControlModeIdentifier> {
+        match self.link_control {
+            LinkControl::FixedParameter(mode) => Some(mode),
+            _ => None,
+        }
+    }
+
+    /// The baudrate in bit/s being verified: the `linkRecord` of a
+    /// `verifyModeTransitionWithSpecificParameter` request, or the baudrate of a predefined
+    /// [`LinkControlModeIdentifier`].
+    ///
+    /// `None` for any other request.
+    #[must_use]
+    pub const fn baudrate(&self) -> Option<u32> {
+        match self.link_control {
+            LinkControl::FixedParameter(mode) => mode.baudrate(),
+            LinkControl::SpecificParameter(baudrate) => Some(baudrate),
+            _ => None,
+        }
+    }
+
+    /// Raw parameter bytes of a reserved or manufacturer-specific sub-function, kept for
+    /// pass-through.
+    ///
+    /// `None` for any defined [`LinkControlType`].
+    #[must_use]
+    pub const fn other_data(&self) -> Option<&'d [u8]> {
+        match self.link_control {
+            LinkControl::Other { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &LINK_CONTROL_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for LinkControlRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        // A deserialized request bypasses `verify_specific_parameter`, so the 3-byte range
+        // is checked again before the top byte is dropped.
+        if let LinkControl::SpecificParameter(baudrate) = self.link_control {
+            if baudrate > MAX_SPECIFIC_BAUDRATE {
+                return Err(Error::InvalidBaudrate(baudrate));
+            }
+        }
+        let sub_function = SuppressablePositiveResponse::new(
+            self.suppress_positive_response,
+            self.link_control_type(),
+        );
+        let written = write_u8(writer, u8::from(sub_function)).map_err(Error::io)?;
+        let parameters = match self.link_control {
+            LinkControl::FixedParameter(mode) => {
+                write_u8(writer, u8::from(mode)).map_err(Error::io)?
+            }
+            LinkControl::SpecificParameter(baudrate) => {
+                write_all(writer, &baudrate.to_be_bytes()[1..]).map_err(Error::io)?
+            }
+            LinkControl::TransitionMode => 0,
+            LinkControl::Other { data, .. } => write_all(writer, data).map_err(Error::io)?,
+        };
+        Ok(written + parameters)
+    }
+}
+
+impl<'a> Decode<'a> for LinkControlRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let sub_function = SuppressablePositiveResponse::<LinkControlType>::try_from(buf[0])?;
+        let rest = &buf[1..];
+        let (link_control, rest) = match sub_function.value() {
+            LinkControlType::VerifyModeTransitionWithFixedParameter => {
+                let (mode, rest) = read_u8(rest)?;
+                (
+                    LinkControl::FixedParameter(LinkControlModeIdentifier::from(mode)),
+                    rest,
+                )
+            }
+            LinkControlType::VerifyModeTransitionWithSpecificParameter => {
+                let ([high, mid, low], rest) = read_array(rest)?;
+                (
+                    LinkControl::SpecificParameter(u32::from_be_bytes([0, high, mid, low])),
+                    rest,
+                )
+            }
+            LinkControlType::TransitionMode => (LinkControl::TransitionMode, rest),
+            link_control_type => (
+                LinkControl::Other {
+                    link_control_type,
+                    data: rest,
+                },
+                &[][..],
+            ),
+        };
+        Ok((
+            Self {
+                suppress_positive_response: sub_function.suppress_positive_response(),
+                link_control,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Positive response to `LinkControl`: echoes the link control type.
+///
+/// See ISO-14229-1:2020, Section 10.12.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct LinkControlResponse {
+    /// The link control type echoed from the request.
+    pub link_control_type: LinkControlType,
+}
+
+impl LinkControlResponse {
+    /// Create a new response.
+    #[must_use]
+    pub const fn new(link_control_type: LinkControlType) -> Self {
+        Self { link_control_type }
+    }
+}
+
+impl Encode for LinkControlResponse {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        write_u8(writer, u8::from(self.link_control_type)).map_err(Error::io)
+    }
+}
+
+impl<'a> Decode<'a> for LinkControlResponse {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (link_control_type, rest) = read_u8(buf)?;
+        Ok((
+            Self::new(LinkControlType::try_from(link_control_type)?),
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn link_control_type_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            match LinkControlType::try_from(i) {
+                Ok(value) => assert_eq!(u8::from(value), i),
+                Err(Error::InvalidLinkControlType(value)) => {
+                    assert_eq!(value, i);
+                    assert!(i >= 0x80);
+                }
+                _ => panic!("Invalid error type"),
+            }
+        }
+    }
+
+    #[test]
+    fn mode_identifier_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(LinkControlModeIdentifier::from(i)), i);
+        }
+        assert_eq!(
+            LinkControlModeIdentifier::Can500000Baud.baudrate(),
+            Some(500_000)
+        );
+        assert_eq!(LinkControlModeIdentifier::ProgrammingSetup.baudrate(), None);
+    }
+
+    #[test]
+    fn fixed_parameter_roundtrip() {
+        let req = LinkControlRequest::verify_fixed_parameter(
+            false,
+            LinkControlModeIdentifier::Can500000Baud,
+        );
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x01, 0x12]);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) = <LinkControlRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.baudrate(), Some(500_000));
+    }
+
+    #[test]
+    fn specific_parameter_roundtrip() {
+        let req = LinkControlRequest::verify_specific_parameter(false, 2_000_000).unwrap();
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x02, 0x1E, 0x84, 0x80]);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, _) = <LinkControlRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.baudrate(), Some(2_000_000));
+        assert_eq!(decoded.mode_identifier(), None);
+
+        assert!(matches!(
+            LinkControlRequest::verify_specific_parameter(false, 0x0100_0000),
+            Err(Error::InvalidBaudrate(0x0100_0000))
+        ));
+
+        let unchecked = LinkControlRequest {
+            suppress_positive_response: false,
+            link_control: LinkControl::SpecificParameter(0x0100_0000),
+        };
+        assert!(matches!(
+            Encode::encode(&unchecked, &mut buf.as_mut_slice()),
+            Err(Error::InvalidBaudrate(0x0100_0000))
+        ));
+    }
+
+    #[test]
+    fn transition_mode_honors_suppress_bit() {
+        let (decoded, rest) = <LinkControlRequest as Decode>::decode(&[0x83]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, LinkControlRequest::transition_mode(true));
+        assert_eq!(decoded.link_control_type(), LinkControlType::TransitionMode);
+
+        let mut buf = [0u8; 2];
+        let written = Encode::encode(&decoded, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x83]);
+    }
+
+    #[test]
+    fn manufacturer_specific_keeps_raw_data() {
+        let wire = [0x41, 0xAA, 0xBB];
+        let (decoded, _) = <LinkControlRequest as Decode>::decode(&wire).unwrap();
+        assert_eq!(
+            decoded.link_control_type(),
+            LinkControlType::VehicleManufacturerSpecific(0x41)
+        );
+        assert_eq!(decoded.other_data(), Some(&[0xAA, 0xBB][..]));
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&decoded, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn request_short_buffers() {
+        assert!(matches!(
+            <LinkControlRequest as Decode>::decode(&[]),
+            Err(Error::InsufficientData(_))
+        ));
+        assert!(matches!(
+            <LinkControlRequest as Decode>::decode(&[0x01]),
+            Err(Error::InsufficientData(_))
+        ));
+        assert!(matches!(
+            <LinkControlRequest as Decode>::decode(&[0x02, 0x07, 0xA1]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let response =
+            LinkControlResponse::new(LinkControlType::VerifyModeTransitionWithFixedParameter);
+        let mut buf = [0u8; 2];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x01]);
+        let (decoded, rest) = <LinkControlResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+
+        assert!(matches!(
+            <LinkControlResponse as Decode>::decode(&[]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<LinkControlRequest<'static>>();
+        assert_impl_eq::<LinkControlResponse>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<LinkControlRequest<'static>>();
+            assert_impl_serde::<LinkControlResponse>();
+        }
+    }
+}
@@ -1,3 +1,14 @@
+mod access_timing_parameters;
+pub use access_timing_parameters::{
+    AccessTimingParametersRequest, AccessTimingParametersResponse, TimingParameterAccessType,
+};
+
+mod authentication;
+pub use authentication::{
+    AuthenticationRequest, AuthenticationRequestParameters, AuthenticationResponse,
+    AuthenticationResponseParameters, AuthenticationReturnParameter, AuthenticationType,
+};
+
 mod clear_dtc_information;
 pub use clear_dtc_information::{ClearDiagnosticInfoRequest, ClearDiagnosticInfoResponse};
 
@@ -17,35 +28,89 @@ pub use diagnostic_session_control::{
     DiagnosticSessionControlRequest, DiagnosticSessionControlResponse, DiagnosticSessionType,
 };
 
+mod dynamically_defined_data_identifier;
+pub use dynamically_defined_data_identifier::{
+    DynamicDefinitionType, DynamicallyDefinedDataIdentifierRequest,
+    DynamicallyDefinedDataIdentifierResponse, MemoryAddressDefinition, MemoryAddressDefinitionIter,
+    SourceDataIdentifierDefinition, SourceDataIdentifierIter,
+};
+
 mod ecu_reset;
 pub use ecu_reset::{EcuResetRequest, EcuResetResponse, ResetType};
 
+mod input_output_control_by_identifier;
+pub use input_output_control_by_identifier::{
+    InputOutputControlByIdentifierRequest, InputOutputControlByIdentifierResponse,
+    InputOutputControlParameter,
+};
+
+mod link_control;
+pub use link_control::{
+    LinkControlModeIdentifier, LinkControlRequest, LinkControlResponse, LinkControlType,
+};
+
 mod negative_response;
 pub use negative_response::NegativeResponse;
 
 mod read_data_by_identifier;
-pub use read_data_by_identifier::{ReadDataByIdentifierRequest, ReadDataByIdentifierResponse};
+pub use read_data_by_identifier::{
+    DataRecordIter, DidCodec, DidRegistry, ReadDataByIdentifierBatches,
+    ReadDataByIdentifierBuilder, ReadDataByIdentifierRequest, ReadDataByIdentifierResponse,
+};
+
+mod read_data_by_periodic_identifier;
+pub use read_data_by_periodic_identifier::{
+    PeriodicDataFrame, PeriodicIdentifier, ReadDataByPeriodicIdentifierRequest,
+    ReadDataByPeriodicIdentifierResponse, TransmissionMode,
+};
 
 mod read_dtc_information;
 pub use read_dtc_information::{
-    DtcAndStatusIter, DtcFaultDetectionCounterRecord, DtcFaultDetectionIter,
-    DtcSeverityAndStatusIter, ReadDtcInfoRequest, ReadDtcInfoResponse, ReadDtcInfoSubFunction,
+    DtcAndExtDataIter, DtcAndStatusIter, DtcExtDataIter, DtcExtDataRegistry,
+    DtcFaultDetectionCounterRecord, DtcFaultDetectionIter, DtcSeverityAndStatusIter,
+    DtcSeverityFunctionalUnitIter, DtcSnapshotIdentificationIter, DtcSnapshotRecord,
+    DtcSnapshotRecordIter, DtcStoredDataRecord, DtcStoredDataRecordIter, ReadDtcInfoRequest,
+    ReadDtcInfoResponse, ReadDtcInfoSubFunction,
+};
+
+mod read_scaling_data_by_identifier;
+pub use read_scaling_data_by_identifier::{
+    FormulaConstant, FormulaIdentifier, ReadScalingDataByIdentifierRequest,
+    ReadScalingDataByIdentifierResponse, ScalingByteExtension, ScalingDataType, ScalingRecord,
+    ScalingRecordIter,
 };
 
+mod read_memory_by_address;
+pub use read_memory_by_address::{ReadMemoryByAddressRequest, ReadMemoryByAddressResponse};
+
 mod request_download;
 pub use request_download::{RequestDownloadRequest, RequestDownloadResponse};
 
+mod request_upload;
+pub use request_upload::{RequestUploadRequest, RequestUploadResponse};
+
 mod request_file_transfer;
 pub use request_file_transfer::{
     DirSizePayload, FileOperationMode, FileSizePayload, NamePayload, PositionPayload,
     RequestFileTransferRequest, RequestFileTransferResponse, SentDataPayload, SizePayload,
 };
 
+mod response_on_event;
+pub use response_on_event::{
+    ActivatedEventIter, ComparisonLogic, ComparisonOfValuesRecord, EventConfiguration, EventType,
+    EventTypeRecord, EventWindowTime, ResponseOnEventRequest, ResponseOnEventResponse,
+};
+
 mod routine_control;
 pub use routine_control::{
     RoutineControlRequest, RoutineControlResponse, RoutineControlSubFunction,
 };
 
+mod secured_data_transmission;
+pub use secured_data_transmission::{
+    AdministrativeParameter, SecuredDataTransmissionRequest, SecuredDataTransmissionResponse,
+};
+
 mod security_access;
 pub use security_access::{
     SecurityAccessLevel, SecurityAccessRequest, SecurityAccessResponse, SecurityAccessType,
@@ -62,3 +127,47 @@ pub use request_transfer_exit::{RequestTransferExitRequest, RequestTransferExitR
 
 mod write_data_by_identifier;
 pub use write_data_by_identifier::{WriteDataByIdentifierRequest, WriteDataByIdentifierResponse};
+
+mod write_memory_by_address;
+pub use write_memory_by_address::{WriteMemoryByAddressRequest, WriteMemoryByAddressResponse};
+
+/// Decode the next item off the front of `remaining`, advancing past it.
+///
+/// Returns `None` once `remaining` is empty. On error `remaining` is cleared, so an iterator
+/// built on this stops after its first error.
+fn step<'a, T>(
+    remaining: &mut &'a [u8],
+    decode: impl FnOnce(&'a [u8]) -> Result<(T, &'a [u8]), crate::Error>,
+) -> Option<Result<T, crate::Error>> {
+    if remaining.is_empty() {
+        return None;
+    }
+    match decode(remaining) {
+        Ok((item, rest)) => {
+            *remaining = rest;
+            Some(Ok(item))
+        }
+        Err(e) => {
+            *remaining = &[];
+            Some(Err(e))
+        }
+    }
+}
+
+/// `Clone` for iterators over `remaining` bytes that borrow a length `registry`; deriving it
+/// would needlessly require the registry type itself to be `Clone`.
+macro_rules! impl_registry_iter_clone {
+    ($($iter:ident),+ $(,)?) => {
+        $(
+            impl<R: ?Sized> Clone for $iter<'_, '_, R> {
+                fn clone(&self) -> Self {
+                    Self {
+                        remaining: self.remaining,
+                        registry: self.registry,
+                    }
+                }
+            }
+        )+
+    };
+}
+use impl_registry_iter_clone;
@@ -1,13 +1,73 @@
 //! `ReadDataByIdentifier` (0x22) service implementation
-use crate::{Decode, Encode, Error, NegativeResponseCode};
-use automotive_wire_codec::{write_all, write_u16_be};
+use super::{impl_registry_iter_clone, step};
+use crate::{Decode, Encode, Error, NegativeResponseCode, UdsIdentifier};
+use automotive_wire_codec::{read_u16_be, take, write_all, write_u16_be};
+
+/// Maps a data identifier to the length of its data record.
+///
+/// `ReadDataByIdentifier` responses do not carry record lengths; a registry supplies them so
+/// the response can be split into `(DID, data)` pairs. Implemented for static
+/// `(DID, length)` tables, which work in `no_std`, and for closures:
+///
+/// ```
+/// use uds_protocol::{DidRegistry, UdsIdentifier};
+/// static DIDS: [(u16, usize); 2] = [(0xF190, 17), (0x0101, 2)];
+/// assert_eq!(DIDS.record_len(UdsIdentifier::from(0xF190)), Some(17));
+/// assert_eq!(DIDS.record_len(UdsIdentifier::from(0xF186)), None);
+///
+/// let by_fn = |did: UdsIdentifier| (u16::from(did) == 0xF186).then_some(1);
+/// assert_eq!(by_fn.record_len(UdsIdentifier::from(0xF186)), Some(1));
+/// ```
+pub trait DidRegistry {
+    /// The data record length of `did`, or `None` if the DID is unknown.
+    fn record_len(&self, did: UdsIdentifier) -> Option<usize>;
+}
+
+impl DidRegistry for [(u16, usize)] {
+    fn record_len(&self, did: UdsIdentifier) -> Option<usize> {
+        let did = u16::from(did);
+        self.iter()
+            .find_map(|&(entry, len)| (entry == did).then_some(len))
+    }
+}
+
+impl<const N: usize> DidRegistry for [(u16, usize); N] {
+    fn record_len(&self, did: UdsIdentifier) -> Option<usize> {
+        self.as_slice().record_len(did)
+    }
+}
+
+impl<F: Fn(UdsIdentifier) -> Option<usize>> DidRegistry for F {
+    fn record_len(&self, did: UdsIdentifier) -> Option<usize> {
+        self(did)
+    }
+}
+
+/// A typed data record for a single data identifier.
+///
+/// [`ReadDataByIdentifierResponse::decode_record`] finds the record for
+/// [`IDENTIFIER`](Self::IDENTIFIER) and decodes it with [`from_record`](Self::from_record).
+/// The record length comes from the [`DidRegistry`], so unlike [`Decode`] the whole record is
+/// given; types that also implement [`Decode`] forward to
+/// [`decode_exact`](Decode::decode_exact).
+pub trait DidCodec<'a>: Sized {
+    /// The data identifier this type decodes.
+    const IDENTIFIER: u16;
+
+    /// Decode a whole data record.
+    ///
+    /// # Errors
+    /// Returns an error if `record` is malformed or has the wrong length.
+    fn from_record(record: &'a [u8]) -> Result<Self, Error>;
+}
 
 /// Positive response to `ReadDataByIdentifier`: raw `[DID][data record]…` bytes.
 ///
-/// Left opaque **by design**: each data record's length is defined by the ECU's
-/// configuration for that DID and is not present on the wire, so the library cannot
-/// split it into `(DID, value)` pairs. Read the 2-byte big-endian DID, take the
-/// application-defined number of data bytes via [`records`](Self::records), then repeat.
+/// Each data record's length is defined by the ECU's configuration for that DID and is not
+/// present on the wire, so splitting the bytes into `(DID, data)` pairs needs a
+/// [`DidRegistry`]; see [`data_records`](Self::data_records) and
+/// [`decode_record`](Self::decode_record). The raw bytes remain available via
+/// [`records`](Self::records).
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -24,11 +84,112 @@ impl<'a> ReadDataByIdentifierResponse<'a> {
         Self { records }
     }
 
-    /// The raw `[DID][data record]…` bytes, to be parsed caller-side.
+    /// The raw `[DID][data record]…` bytes.
     #[must_use]
     pub const fn records(&self) -> &'a [u8] {
         self.records
     }
+
+    /// Iterate the `(DID, data)` pairs, taking each record length from `registry`.
+    pub fn data_records<'r, R: DidRegistry + ?Sized>(
+        &self,
+        registry: &'r R,
+    ) -> DataRecordIter<'a, 'r, R> {
+        DataRecordIter::new(self.records, registry)
+    }
+
+    /// The data record of `did`, or `None` if the response does not contain it.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownDataIdentifier`] if a DID before `did` is unknown to `registry`,
+    /// and [`Error::IncorrectMessageLengthOrInvalidFormat`] if a record is truncated.
+    pub fn find_record<R: DidRegistry + ?Sized>(
+        &self,
+        registry: &R,
+        did: UdsIdentifier,
+    ) -> Result<Option<&'a [u8]>, Error> {
+        for record in self.data_records(registry) {
+            let (identifier, data) = record?;
+            if identifier == did {
+                return Ok(Some(data));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Decode the data record of `T::IDENTIFIER` as `T`, or `None` if the response does not
+    /// contain it.
+    ///
+    /// # Errors
+    /// Returns the [`find_record`](Self::find_record) error, or `T`'s decode error if the
+    /// record is malformed.
+    pub fn decode_record<T: DidCodec<'a>, R: DidRegistry + ?Sized>(
+        &self,
+        registry: &R,
+    ) -> Result<Option<T>, Error> {
+        self.find_record(registry, UdsIdentifier::from(T::IDENTIFIER))?
+            .map(T::from_record)
+            .transpose()
+    }
+}
+
+/// Lazy iterator over `(DID, data)` pairs, as found in a [`ReadDataByIdentifierResponse`] and
+/// in the snapshot and stored data records of a
+/// [`ReadDtcInfoResponse`](crate::ReadDtcInfoResponse)
+///
+/// A DID unknown to the [`DidRegistry`] yields [`Error::UnknownDataIdentifier`], and a truncated
+/// DID or record yields [`Error::IncorrectMessageLengthOrInvalidFormat`]; the iterator stops
+/// after the first error.
+#[derive(Debug)]
+pub struct DataRecordIter<'a, 'r, R: ?Sized> {
+    remaining: &'a [u8],
+    registry: &'r R,
+}
+
+impl_registry_iter_clone!(DataRecordIter);
+
+impl<'a, 'r, R: DidRegistry + ?Sized> DataRecordIter<'a, 'r, R> {
+    /// Create an iterator over the raw `[DID][data record]…` bytes.
+    #[must_use]
+    pub fn new(data: &'a [u8], registry: &'r R) -> Self {
+        Self {
+            remaining: data,
+            registry,
+        }
+    }
+
+    /// The bytes not yet iterated.
+    pub(crate) const fn remaining(&self) -> &'a [u8] {
+        self.remaining
+    }
+
+    /// Collect all pairs into a `Vec`.
+    ///
+    /// # Errors
+    /// Returns the first error encountered.
+    #[cfg(feature = "alloc")]
+    pub fn collect_all(self) -> Result<alloc::vec::Vec<(UdsIdentifier, &'a [u8])>, Error> {
+        self.collect()
+    }
+}
+
+impl<'a, R: DidRegistry + ?Sized> Iterator for DataRecordIter<'a, '_, R> {
+    type Item = Result<(UdsIdentifier, &'a [u8]), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let registry = self.registry;
+        step(&mut self.remaining, |buf| {
+            let (identifier, rest) =
+                read_u16_be(buf).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+            let identifier = UdsIdentifier::from(identifier);
+            let len = registry
+                .record_len(identifier)
+                .ok_or(Error::UnknownDataIdentifier(u16::from(identifier)))?;
+            let (data, rest) =
+                take(rest, len).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+            Ok(((identifier, data), rest))
+        })
+    }
 }
 
 impl Encode for ReadDataByIdentifierResponse<'_> {
@@ -127,6 +288,196 @@ impl Iterator for DidIter<'_> {
     }
 }
 
+/// Builds `ReadDataByIdentifier` requests from up to `N` distinct DIDs
+///
+/// The ECU limits how many DIDs one request may carry, and the response must fit the
+/// transport's maximum PDU size or the ECU answers with NRC `responseTooLong`.
+/// [`build`](Self::build) produces a single request within the DID limit;
+/// [`split`](Self::split) spreads the DIDs over as many requests as needed to respect both
+/// limits.
+///
+/// ```
+/// use uds_protocol::{ReadDataByIdentifierBuilder, UdsIdentifier};
+/// static DIDS: [(u16, usize); 3] = [(0xF190, 17), (0xF18C, 16), (0xF186, 1)];
+/// let mut builder = ReadDataByIdentifierBuilder::<8>::new(8)?;
+/// builder
+///     .push(UdsIdentifier::Vin)?
+///     .push(UdsIdentifier::EcuSerialNumber)?
+///     .push(UdsIdentifier::ActiveDiagnosticSession)?;
+/// assert_eq!(builder.response_len(&DIDS)?, 1 + 19 + 18 + 3);
+/// // A classic CAN single frame carries 7 bytes, ISO-TP up to 4095; use 32 here.
+/// let requests: Vec<_> = builder.split(&DIDS, 32).collect::<Result<_, _>>()?;
+/// assert_eq!(requests.len(), 2);
+/// # Ok::<(), uds_protocol::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadDataByIdentifierBuilder<const N: usize> {
+    dids: [u16; N],
+    len: usize,
+    max_dids_per_request: usize,
+}
+
+impl<const N: usize> ReadDataByIdentifierBuilder<N> {
+    /// Create an empty builder allowing `max_dids_per_request` DIDs per request.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidDataIdentifierLimit`] if `max_dids_per_request` is 0.
+    pub const fn new(max_dids_per_request: usize) -> Result<Self, Error> {
+        if max_dids_per_request == 0 {
+            return Err(Error::InvalidDataIdentifierLimit(max_dids_per_request));
+        }
+        Ok(Self {
+            dids: [0; N],
+            len: 0,
+            max_dids_per_request,
+        })
+    }
+
+    /// Add a DID.
+    ///
+    /// # Errors
+    /// - [`Error::DuplicateDataIdentifier`] if `did` was already added.
+    /// - [`Error::DataIdentifierCapacityExceeded`] if the builder already holds `N` DIDs.
+    pub fn push(&mut self, did: UdsIdentifier) -> Result<&mut Self, Error> {
+        let did = u16::from(did);
+        if self.dids().contains(&did) {
+            return Err(Error::DuplicateDataIdentifier(did));
+        }
+        let slot = self
+            .dids
+            .get_mut(self.len)
+            .ok_or(Error::DataIdentifierCapacityExceeded(N))?;
+        *slot = did;
+        self.len += 1;
+        Ok(self)
+    }
+
+    /// The DIDs added so far, in order.
+    #[must_use]
+    pub fn dids(&self) -> &[u16] {
+        &self.dids[..self.len]
+    }
+
+    /// Build a single request carrying every DID.
+    ///
+    /// # Errors
+    /// - [`Error::NoDataIdentifiers`] if no DID was added.
+    /// - [`Error::TooManyDataIdentifiers`] if there are more DIDs than allowed per request.
+    pub fn build(&self) -> Result<ReadDataByIdentifierRequest<'_>, Error> {
+        if self.len == 0 {
+            return Err(Error::NoDataIdentifiers);
+        }
+        if self.len > self.max_dids_per_request {
+            return Err(Error::TooManyDataIdentifiers(self.max_dids_per_request));
+        }
+        Ok(ReadDataByIdentifierRequest::new(self.dids()))
+    }
+
+    /// Predicted length of the positive response to all DIDs, response SID included.
+    ///
+    /// # Errors
+    /// - [`Error::UnknownDataIdentifier`] if a DID is unknown to `registry`.
+    /// - [`Error::ResponseTooLong`] if the length does not fit `usize`, naming the DID that
+    ///   overflowed it.
+    pub fn response_len<R: DidRegistry + ?Sized>(&self, registry: &R) -> Result<usize, Error> {
+        self.dids().iter().try_fold(1, |total, &did| {
+            add_record(total, record_len(registry, did)?).ok_or(Error::ResponseTooLong(did))
+        })
+    }
+
+    /// Whether the response to all DIDs would exceed `max_pdu_len` bytes.
+    ///
+    /// # Errors
+    /// See [`response_len`](Self::response_len).
+    pub fn response_overflows<R: DidRegistry + ?Sized>(
+        &self,
+        registry: &R,
+        max_pdu_len: usize,
+    ) -> Result<bool, Error> {
+        Ok(self.response_len(registry)? > max_pdu_len)
+    }
+
+    /// Split the DIDs, in order, into requests whose request and predicted response each fit
+    /// `max_pdu_len` bytes, service byte included, and that carry at most the allowed number of
+    /// DIDs.
+    pub fn split<'r, R: DidRegistry + ?Sized>(
+        &self,
+        registry: &'r R,
+        max_pdu_len: usize,
+    ) -> ReadDataByIdentifierBatches<'_, 'r, R> {
+        ReadDataByIdentifierBatches {
+            remaining: self.dids(),
+            registry,
+            max_dids_per_request: self.max_dids_per_request,
+            max_pdu_len,
+        }
+    }
+}
+
+fn record_len<R: DidRegistry + ?Sized>(registry: &R, did: u16) -> Result<usize, Error> {
+    registry
+        .record_len(UdsIdentifier::from(did))
+        .ok_or(Error::UnknownDataIdentifier(did))
+}
+
+/// `total` plus one record of `len` bytes and its 2-byte DID, or `None` on overflow.
+fn add_record(total: usize, len: usize) -> Option<usize> {
+    total.checked_add(2)?.checked_add(len)
+}
+
+/// Iterator over the requests of [`ReadDataByIdentifierBuilder::split`]
+///
+/// Yields [`Error::ResponseTooLong`] for a DID whose response alone exceeds the PDU size, or
+/// [`Error::UnknownDataIdentifier`] for a DID unknown to the registry; the iterator stops after
+/// the first error.
+#[derive(Debug)]
+pub struct ReadDataByIdentifierBatches<'a, 'r, R: ?Sized> {
+    remaining: &'a [u16],
+    registry: &'r R,
+    max_dids_per_request: usize,
+    max_pdu_len: usize,
+}
+
+impl<'a, R: DidRegistry + ?Sized> Iterator for ReadDataByIdentifierBatches<'a, '_, R> {
+    type Item = Result<ReadDataByIdentifierRequest<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = *self.remaining.first()?;
+        let mut request_len = 1;
+        let mut response_len = 1;
+        let mut count = 0;
+        for &did in self.remaining.iter().take(self.max_dids_per_request) {
+            let len = match record_len(self.registry, did) {
+                Ok(len) => len,
+                Err(e) if count == 0 => {
+                    self.remaining = &[];
+                    return Some(Err(e));
+                }
+                // Report the unknown DID once the DIDs before it are sent.
+                Err(_) => break,
+            };
+            // An overflowing length cannot fit the PDU either.
+            let Some(next_len) = add_record(response_len, len).filter(|&n| n <= self.max_pdu_len)
+            else {
+                break;
+            };
+            if request_len + 2 > self.max_pdu_len {
+                break;
+            }
+            request_len += 2;
+            response_len = next_len;
+            count += 1;
+        }
+        if count == 0 {
+            self.remaining = &[];
+            return Some(Err(Error::ResponseTooLong(first)));
+        }
+        let (batch, rest) = self.remaining.split_at(count);
+        self.remaining = rest;
+        Some(Ok(ReadDataByIdentifierRequest::new(batch)))
+    }
+}
+
 impl Encode for ReadDataByIdentifierRequest<'_> {
     type Error = crate::Error;
 
@@ -164,6 +515,7 @@ impl<'a> Decode<'a> for ReadDataByIdentifierRequest<'a> {
 mod test {
     use super::*;
     use crate::test_util::{assert_encode_size_agrees, assert_impl_eq};
+    use alloc::{vec, vec::Vec};
 
     #[test]
     fn derive_contract() {
@@ -223,6 +575,220 @@ mod test {
         assert_eq!(&buf[..n], &raw);
     }
 
+    #[test]
+    fn rdbi_response_splits_with_static_table() {
+        static DIDS: [(u16, usize); 2] = [(0xF190, 3), (0x0101, 1)];
+        let resp =
+            ReadDataByIdentifierResponse::new(&[0xF1, 0x90, b'A', b'B', b'C', 0x01, 0x01, 0x7F]);
+        let mut it = resp.data_records(&DIDS);
+        assert_eq!(
+            it.next().unwrap().unwrap(),
+            (UdsIdentifier::from(0xF190), b"ABC".as_slice())
+        );
+        assert_eq!(
+            it.next().unwrap().unwrap(),
+            (UdsIdentifier::from(0x0101), [0x7F].as_slice())
+        );
+        assert!(it.next().is_none());
+        assert_eq!(
+            resp.find_record(DIDS.as_slice(), UdsIdentifier::from(0x0101))
+                .unwrap(),
+            Some([0x7F].as_slice())
+        );
+        assert_eq!(
+            resp.find_record(&DIDS, UdsIdentifier::from(0xF186))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn rdbi_response_stops_at_unknown_or_truncated_record() {
+        let resp = ReadDataByIdentifierResponse::new(&[0x01, 0x01, 0x7F, 0xF1, 0x90, 0x00]);
+        let registry = |did: UdsIdentifier| (u16::from(did) == 0x0101).then_some(1);
+        let mut it = resp.data_records(&registry);
+        assert!(it.next().unwrap().is_ok());
+        assert!(matches!(
+            it.next(),
+            Some(Err(Error::UnknownDataIdentifier(0xF190)))
+        ));
+        assert!(it.next().is_none());
+
+        for truncated in [&[0x01, 0x01][..], &[0x01]] {
+            assert!(matches!(
+                ReadDataByIdentifierResponse::new(truncated)
+                    .data_records(&registry)
+                    .next(),
+                Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+            ));
+        }
+    }
+
+    #[test]
+    fn rdbi_response_decodes_typed_record() {
+        #[derive(Debug, PartialEq)]
+        struct Voltage(u16);
+        impl Decode<'_> for Voltage {
+            type Error = Error;
+            fn decode(buf: &[u8]) -> Result<(Self, &[u8]), Error> {
+                let (value, rest) = read_u16_be(buf)?;
+                Ok((Self(value), rest))
+            }
+        }
+        impl DidCodec<'_> for Voltage {
+            const IDENTIFIER: u16 = 0x0102;
+
+            fn from_record(record: &[u8]) -> Result<Self, Error> {
+                Self::decode_exact(record)
+            }
+        }
+
+        static DIDS: [(u16, usize); 2] = [(0x0101, 1), (0x0102, 2)];
+        let resp = ReadDataByIdentifierResponse::new(&[0x01, 0x01, 0x00, 0x01, 0x02, 0x30, 0x39]);
+        assert_eq!(
+            resp.decode_record::<Voltage, _>(&DIDS).unwrap(),
+            Some(Voltage(12345))
+        );
+        assert_eq!(
+            ReadDataByIdentifierResponse::new(&[0x01, 0x01, 0x00])
+                .decode_record::<Voltage, _>(&DIDS)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn builder_rejects_duplicates_and_overflow() {
+        let mut builder = ReadDataByIdentifierBuilder::<2>::new(1).unwrap();
+        assert!(matches!(builder.build(), Err(Error::NoDataIdentifiers)));
+        builder.push(UdsIdentifier::Vin).unwrap();
+        assert!(matches!(
+            builder.push(UdsIdentifier::from(0xF190)),
+            Err(Error::DuplicateDataIdentifier(0xF190))
+        ));
+        assert_eq!(
+            builder.build().unwrap().dids().collect::<Vec<_>>(),
+            [0xF190]
+        );
+        builder.push(UdsIdentifier::EcuSerialNumber).unwrap();
+        assert!(matches!(
+            builder.push(UdsIdentifier::ActiveDiagnosticSession),
+            Err(Error::DataIdentifierCapacityExceeded(2))
+        ));
+        assert!(matches!(
+            builder.build(),
+            Err(Error::TooManyDataIdentifiers(1))
+        ));
+        assert_eq!(builder.dids(), &[0xF190, 0xF18C]);
+    }
+
+    #[test]
+    fn builder_rejects_zero_did_limit() {
+        assert!(matches!(
+            ReadDataByIdentifierBuilder::<2>::new(0),
+            Err(Error::InvalidDataIdentifierLimit(0))
+        ));
+    }
+
+    #[test]
+    fn builder_predicts_response_overflow() {
+        static DIDS: [(u16, usize); 2] = [(0xF190, 17), (0xF186, 1)];
+        let mut builder = ReadDataByIdentifierBuilder::<4>::new(4).unwrap();
+        builder
+            .push(UdsIdentifier::Vin)
+            .unwrap()
+            .push(UdsIdentifier::ActiveDiagnosticSession)
+            .unwrap();
+        assert_eq!(builder.response_len(&DIDS).unwrap(), 23);
+        assert!(!builder.response_overflows(&DIDS, 23).unwrap());
+        assert!(builder.response_overflows(&DIDS, 22).unwrap());
+        builder.push(UdsIdentifier::EcuSerialNumber).unwrap();
+        assert!(matches!(
+            builder.response_len(&DIDS),
+            Err(Error::UnknownDataIdentifier(0xF18C))
+        ));
+    }
+
+    #[test]
+    fn builder_splits_by_pdu_size_and_did_limit() {
+        static DIDS: [(u16, usize); 4] = [(0x0101, 4), (0x0102, 4), (0x0103, 4), (0x0104, 20)];
+        let mut builder = ReadDataByIdentifierBuilder::<4>::new(2).unwrap();
+        for did in [0x0101, 0x0102, 0x0103] {
+            builder.push(UdsIdentifier::from(did)).unwrap();
+        }
+        // DID limit: 2 + 1
+        let batches: Vec<Vec<u16>> = builder
+            .split(&DIDS, 4095)
+            .map(|req| req.unwrap().dids().collect())
+            .collect();
+        assert_eq!(batches, [vec![0x0101, 0x0102], vec![0x0103]]);
+
+        // PDU limit: 1 + 6 + 6 = 13 fits, a third record does not.
+        let mut builder = ReadDataByIdentifierBuilder::<4>::new(4).unwrap();
+        for did in [0x0101, 0x0102, 0x0103] {
+            builder.push(UdsIdentifier::from(did)).unwrap();
+        }
+        let batches: Vec<Vec<u16>> = builder
+            .split(&DIDS, 13)
+            .map(|req| req.unwrap().dids().collect())
+            .collect();
+        assert_eq!(batches, [vec![0x0101, 0x0102], vec![0x0103]]);
+
+        builder.push(UdsIdentifier::from(0x0104)).unwrap();
+        let mut split = builder.split(&DIDS, 13);
+        assert!(split.next().unwrap().is_ok());
+        assert!(split.next().unwrap().is_ok());
+        assert!(matches!(
+            split.next(),
+            Some(Err(Error::ResponseTooLong(0x0104)))
+        ));
+        assert!(split.next().is_none());
+    }
+
+    #[test]
+    fn builder_reports_overflowing_response_len() {
+        static DIDS: [(u16, usize); 2] = [(0x0101, 4), (0x0102, usize::MAX - 1)];
+        let mut builder = ReadDataByIdentifierBuilder::<2>::new(2).unwrap();
+        builder.push(UdsIdentifier::from(0x0101)).unwrap();
+        builder.push(UdsIdentifier::from(0x0102)).unwrap();
+        assert!(matches!(
+            builder.response_len(&DIDS),
+            Err(Error::ResponseTooLong(0x0102))
+        ));
+        assert!(matches!(
+            builder.response_overflows(&DIDS, usize::MAX),
+            Err(Error::ResponseTooLong(0x0102))
+        ));
+        let mut split = builder.split(&DIDS, usize::MAX);
+        assert_eq!(
+            split.next().unwrap().unwrap().dids().collect::<Vec<_>>(),
+            [0x0101]
+        );
+        assert!(matches!(
+            split.next(),
+            Some(Err(Error::ResponseTooLong(0x0102)))
+        ));
+        assert!(split.next().is_none());
+    }
+
+    #[test]
+    fn builder_split_reports_unknown_did_after_known_ones() {
+        static DIDS: [(u16, usize); 1] = [(0x0101, 1)];
+        let mut builder = ReadDataByIdentifierBuilder::<2>::new(2).unwrap();
+        builder.push(UdsIdentifier::from(0x0101)).unwrap();
+        builder.push(UdsIdentifier::from(0x0999)).unwrap();
+        let mut split = builder.split(&DIDS, 64);
+        assert_eq!(
+            split.next().unwrap().unwrap().dids().collect::<Vec<_>>(),
+            [0x0101]
+        );
+        assert!(matches!(
+            split.next(),
+            Some(Err(Error::UnknownDataIdentifier(0x0999)))
+        ));
+        assert!(split.next().is_none());
+    }
+
     #[test]
     fn encode_read_did_request_tx() {
         let ids = [0xF180u16, 0xF186u16];
@@ -0,0 +1,423 @@
+//! `ReadDataByPeriodicIdentifier` (0x2A) service implementation
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode, UdsIdentifier};
+use automotive_wire_codec::{write_all, write_u8};
+
+const READ_PERIODIC_DID_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 4] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+];
+
+/// High byte shared by every periodic data identifier (`0xF200–0xF2FF`).
+const PERIODIC_IDENTIFIER_HIGH_BYTE: u16 = 0xF200;
+
+/// `transmissionMode` of a `ReadDataByPeriodicIdentifier` request
+///
+/// The actual slow/medium/fast periods are defined by the vehicle manufacturer.
+///
+/// See ISO-14229-1:2020, Section 11.5.2.2
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TransmissionMode {
+    /// Send the periodic data identifiers at the slow rate
+    SendAtSlowRate,
+    /// Send the periodic data identifiers at the medium rate
+    SendAtMediumRate,
+    /// Send the periodic data identifiers at the fast rate
+    SendAtFastRate,
+    /// Stop sending the listed periodic data identifiers (all of them if none are listed)
+    StopSending,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl TransmissionMode {
+    /// Returns `true` if this mode starts (or re-rates) periodic transmission.
+    #[must_use]
+    pub const fn is_sending(&self) -> bool {
+        matches!(
+            self,
+            Self::SendAtSlowRate | Self::SendAtMediumRate | Self::SendAtFastRate
+        )
+    }
+}
+
+impl From<TransmissionMode> for u8 {
+    fn from(value: TransmissionMode) -> Self {
+        match value {
+            TransmissionMode::SendAtSlowRate => 0x01,
+            TransmissionMode::SendAtMediumRate => 0x02,
+            TransmissionMode::SendAtFastRate => 0x03,
+            TransmissionMode::StopSending => 0x04,
+            TransmissionMode::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for TransmissionMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => Self::SendAtSlowRate,
+            0x02 => Self::SendAtMediumRate,
+            0x03 => Self::SendAtFastRate,
+            0x04 => Self::StopSending,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// One-byte periodic data identifier (PDID)
+///
+/// On the wire a periodic data identifier is only the low byte of a data identifier in the
+/// `0xF200–0xF2FF` range ([`UdsIdentifier::PeriodicDataIdentifier`]).
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PeriodicIdentifier(pub u8);
+
+impl PeriodicIdentifier {
+    /// The full 2-byte data identifier (`0xF2xx`) this periodic identifier stands for.
+    #[must_use]
+    pub fn data_identifier(self) -> UdsIdentifier {
+        UdsIdentifier::from(PERIODIC_IDENTIFIER_HIGH_BYTE | u16::from(self.0))
+    }
+}
+
+impl From<PeriodicIdentifier> for UdsIdentifier {
+    fn from(value: PeriodicIdentifier) -> Self {
+        value.data_identifier()
+    }
+}
+
+impl TryFrom<UdsIdentifier> for PeriodicIdentifier {
+    type Error = Error;
+
+    /// # Errors
+    /// Returns [`Error::InvalidPeriodicDataIdentifier`] if `value` is outside `0xF200–0xF2FF`.
+    fn try_from(value: UdsIdentifier) -> Result<Self, Error> {
+        match value {
+            UdsIdentifier::PeriodicDataIdentifier(did) => Ok(Self(did.to_be_bytes()[1])),
+            other => Err(Error::InvalidPeriodicDataIdentifier(u16::from(other))),
+        }
+    }
+}
+
+/// Request for the server to start or stop periodically transmitting data identifiers.
+///
+/// Zero-alloc request. The periodic identifiers are borrowed from the caller as raw
+/// one-byte PDIDs.
+///
+/// See ISO-14229-1:2020, Section 11.5.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadDataByPeriodicIdentifierRequest<'d> {
+    /// The requested transmission rate, or [`TransmissionMode::StopSending`].
+    pub transmission_mode: TransmissionMode,
+    /// Raw one-byte periodic identifiers (the low byte of `0xF2xx`).
+    ///
+    /// May only be empty for [`TransmissionMode::StopSending`], which then stops all
+    /// periodic transmissions.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub periodic_identifiers: &'d [u8],
+}
+
+impl<'d> ReadDataByPeriodicIdentifierRequest<'d> {
+    /// Create a new periodic read request.
+    #[must_use]
+    pub const fn new(transmission_mode: TransmissionMode, periodic_identifiers: &'d [u8]) -> Self {
+        Self {
+            transmission_mode,
+            periodic_identifiers,
+        }
+    }
+
+    /// Iterate the requested periodic identifiers.
+    #[must_use]
+    pub fn identifiers(&self) -> impl ExactSizeIterator<Item = PeriodicIdentifier> + 'd {
+        self.periodic_identifiers
+            .iter()
+            .map(|&pdid| PeriodicIdentifier(pdid))
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &READ_PERIODIC_DID_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for ReadDataByPeriodicIdentifierRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u8(writer, u8::from(self.transmission_mode)).map_err(Error::io)?;
+        written += write_all(writer, self.periodic_identifiers).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for ReadDataByPeriodicIdentifierRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let transmission_mode = TransmissionMode::from(buf[0]);
+        let periodic_identifiers = &buf[1..];
+        if transmission_mode.is_sending() && periodic_identifiers.is_empty() {
+            return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+        }
+        Ok((
+            Self {
+                transmission_mode,
+                periodic_identifiers,
+            },
+            &[],
+        ))
+    }
+}
+
+/// One periodic data message: a one-byte periodic identifier followed by its data record.
+///
+/// Periodic data is sent unsolicited after a [`ReadDataByPeriodicIdentifierRequest`]. When
+/// the transport uses a dedicated periodic channel the frame starts directly with the PDID
+/// and has no service identifier, so it must be decoded with this type rather than
+/// [`Response`](crate::Response). When it is sent on the normal response channel it is
+/// prefixed with the `0x6A` response SID and decodes as a
+/// [`ReadDataByPeriodicIdentifierResponse`] carrying this frame.
+///
+/// The data record length is defined by the server's configuration for the identifier, so
+/// every byte after the PDID belongs to `data`.
+///
+/// See ISO-14229-1:2020, Section 11.5.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PeriodicDataFrame<'d> {
+    /// The periodic identifier the data belongs to.
+    pub periodic_identifier: PeriodicIdentifier,
+    /// The data record of the identifier.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub data: &'d [u8],
+}
+
+impl<'d> PeriodicDataFrame<'d> {
+    /// Create a new periodic data frame.
+    #[must_use]
+    pub const fn new(periodic_identifier: PeriodicIdentifier, data: &'d [u8]) -> Self {
+        Self {
+            periodic_identifier,
+            data,
+        }
+    }
+}
+
+impl Encode for PeriodicDataFrame<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u8(writer, self.periodic_identifier.0).map_err(Error::io)?;
+        written += write_all(writer, self.data).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for PeriodicDataFrame<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        Ok((
+            Self {
+                periodic_identifier: PeriodicIdentifier(buf[0]),
+                data: &buf[1..],
+            },
+            &[],
+        ))
+    }
+}
+
+/// Message with the `ReadDataByPeriodicIdentifier` response SID (`0x6A`).
+///
+/// The positive response to the request itself has no parameters (`periodic_data` is
+/// `None`). Periodic data sent on the response channel reuses the same SID and carries a
+/// [`PeriodicDataFrame`].
+///
+/// See ISO-14229-1:2020, Section 11.5.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadDataByPeriodicIdentifierResponse<'d> {
+    /// The periodic data carried by this message, or `None` for the positive response to
+    /// the request.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub periodic_data: Option<PeriodicDataFrame<'d>>,
+}
+
+impl<'d> ReadDataByPeriodicIdentifierResponse<'d> {
+    /// Create a new response. Pass `None` for the positive response to the request.
+    #[must_use]
+    pub const fn new(periodic_data: Option<PeriodicDataFrame<'d>>) -> Self {
+        Self { periodic_data }
+    }
+}
+
+impl Encode for ReadDataByPeriodicIdentifierResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        match &self.periodic_data {
+            Some(frame) => frame.encode(writer),
+            None => Ok(0),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for ReadDataByPeriodicIdentifierResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        if buf.is_empty() {
+            return Ok((Self::default(), buf));
+        }
+        let (frame, rest) = PeriodicDataFrame::decode(buf)?;
+        Ok((
+            Self {
+                periodic_data: Some(frame),
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn transmission_mode_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(TransmissionMode::from(i)), i);
+        }
+        assert_eq!(TransmissionMode::from(0x04), TransmissionMode::StopSending);
+        assert_eq!(
+            TransmissionMode::from(0x00),
+            TransmissionMode::IsoSaeReserved(0)
+        );
+    }
+
+    #[test]
+    fn periodic_identifier_maps_into_f2xx() {
+        let pdid = PeriodicIdentifier(0x0A);
+        assert_eq!(u16::from(pdid.data_identifier()), 0xF20A);
+        assert_eq!(
+            PeriodicIdentifier::try_from(UdsIdentifier::from(0xF2FF)).unwrap(),
+            PeriodicIdentifier(0xFF)
+        );
+        assert!(matches!(
+            PeriodicIdentifier::try_from(UdsIdentifier::from(0xF190)),
+            Err(Error::InvalidPeriodicDataIdentifier(0xF190))
+        ));
+    }
+
+    #[test]
+    fn request_roundtrip() {
+        let req = ReadDataByPeriodicIdentifierRequest::new(
+            TransmissionMode::SendAtMediumRate,
+            &[0xE3, 0x24],
+        );
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x02, 0xE3, 0x24]);
+        let (decoded, rest) =
+            <ReadDataByPeriodicIdentifierRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        let dids: [u16; 2] = [0xF2E3, 0xF224];
+        assert!(
+            decoded
+                .identifiers()
+                .map(|pdid| u16::from(pdid.data_identifier()))
+                .eq(dids)
+        );
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn stop_sending_may_omit_identifiers() {
+        let (decoded, _) =
+            <ReadDataByPeriodicIdentifierRequest as Decode>::decode(&[0x04]).unwrap();
+        assert_eq!(decoded.transmission_mode, TransmissionMode::StopSending);
+        assert!(decoded.periodic_identifiers.is_empty());
+
+        assert!(matches!(
+            <ReadDataByPeriodicIdentifierRequest as Decode>::decode(&[0x01]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            <ReadDataByPeriodicIdentifierRequest as Decode>::decode(&[]),
+            Err(Error::InsufficientData(i)) if i.needed == 1 && i.available == 0
+        ));
+    }
+
+    #[test]
+    fn periodic_frame_roundtrip() {
+        let frame = PeriodicDataFrame::new(PeriodicIdentifier(0xE3), &[0x4C, 0x01]);
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&frame, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0xE3, 0x4C, 0x01]);
+        let (decoded, rest) = <PeriodicDataFrame as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, frame);
+        assert_encode_size_agrees(&frame);
+    }
+
+    #[test]
+    fn response_distinguishes_ack_from_periodic_data() {
+        let (ack, _) = <ReadDataByPeriodicIdentifierResponse as Decode>::decode(&[]).unwrap();
+        assert_eq!(ack.periodic_data, None);
+        assert_encode_size_agrees(&ack);
+
+        let (data, _) =
+            <ReadDataByPeriodicIdentifierResponse as Decode>::decode(&[0xE3, 0x4C]).unwrap();
+        assert_eq!(
+            data.periodic_data,
+            Some(PeriodicDataFrame::new(PeriodicIdentifier(0xE3), &[0x4C]))
+        );
+        assert_encode_size_agrees(&data);
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<ReadDataByPeriodicIdentifierRequest<'static>>();
+        assert_impl_eq::<ReadDataByPeriodicIdentifierResponse<'static>>();
+        assert_impl_eq::<PeriodicDataFrame<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<ReadDataByPeriodicIdentifierRequest<'static>>();
+            assert_impl_serde::<ReadDataByPeriodicIdentifierResponse<'static>>();
+            assert_impl_serde::<PeriodicDataFrame<'static>>();
+        }
+    }
+}
@@ -1,11 +1,12 @@
 //! `ReadDTCInformation` (0x19) request and response service implementation
 
-use automotive_wire_codec::{read_u8, write_all, write_u8, write_u16_be};
+use automotive_wire_codec::{read_u8, take, write_all, write_u8, write_u16_be};
 
+use super::{impl_registry_iter_clone, step};
 use crate::{
-    Decode, DtcExtDataRecordNumber, DtcFormatIdentifier, DtcRecord, DtcSeverityMask,
-    DtcSnapshotRecordNumber, DtcStatusMask, DtcStoredDataRecordNumber, Encode, Error,
-    FunctionalGroupIdentifier, Incomplete, NegativeResponseCode,
+    DataRecordIter, Decode, DidRegistry, DtcExtDataRecordNumber, DtcFormatIdentifier, DtcRecord,
+    DtcSeverityMask, DtcSnapshotRecordNumber, DtcStatusMask, DtcStoredDataRecordNumber, Encode,
+    Error, FunctionalGroupIdentifier, Incomplete, NegativeResponseCode,
 };
 
 const READ_DTC_INFO_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 3] = [
@@ -650,6 +651,395 @@ impl Iterator for DtcSeverityAndStatusIter<'_> {
     }
 }
 
+/// Lazy iterator over `(DtcSeverityMask, functional unit, DtcRecord, DtcStatusMask)` tuples from
+/// raw bytes.
+///
+/// Each `DTCAndSeverityRecord` is 6 bytes: 1 severity + 1 `DTCFunctionalUnit` + 3 DTC record +
+/// 1 status mask.
+#[derive(Clone, Debug)]
+pub struct DtcSeverityFunctionalUnitIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> DtcSeverityFunctionalUnitIter<'a> {
+    /// Create an iterator over severity/functional unit/DTC/status tuples.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { remaining: data }
+    }
+
+    /// Number of complete records available.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.remaining.len() / 6
+    }
+
+    /// Whether there are no complete records.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collect all tuples into a `Vec`.
+    ///
+    /// # Errors
+    /// Returns an error if the byte data contains a partial record.
+    #[cfg(feature = "alloc")]
+    pub fn collect_all(
+        self,
+    ) -> Result<alloc::vec::Vec<(DtcSeverityMask, u8, DtcRecord, DtcStatusMask)>, Error> {
+        self.collect()
+    }
+}
+
+impl Iterator for DtcSeverityFunctionalUnitIter<'_> {
+    type Item = Result<(DtcSeverityMask, u8, DtcRecord, DtcStatusMask), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        if self.remaining.len() < 6 {
+            return Some(Err(Error::IncorrectMessageLengthOrInvalidFormat));
+        }
+        let severity = DtcSeverityMask::from(self.remaining[0]);
+        let functional_unit = self.remaining[1];
+        let record = DtcRecord::new(self.remaining[2], self.remaining[3], self.remaining[4]);
+        let status = DtcStatusMask::from(self.remaining[5]);
+        self.remaining = &self.remaining[6..];
+        Some(Ok((severity, functional_unit, record, status)))
+    }
+}
+
+/// Lazy iterator over `(DtcRecord, DtcSnapshotRecordNumber)` pairs from raw bytes.
+///
+/// Each pair is 4 bytes: 3 for the DTC record + 1 for the snapshot record number.
+#[derive(Clone, Debug)]
+pub struct DtcSnapshotIdentificationIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> DtcSnapshotIdentificationIter<'a> {
+    /// Create an iterator over `(DtcRecord, DtcSnapshotRecordNumber)` pairs.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { remaining: data }
+    }
+
+    /// Number of complete records available.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.remaining.len() / 4
+    }
+
+    /// Whether there are no complete records.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collect all records into a `Vec`.
+    ///
+    /// # Errors
+    /// Returns an error if the byte data contains a partial record.
+    #[cfg(feature = "alloc")]
+    pub fn collect_all(
+        self,
+    ) -> Result<alloc::vec::Vec<(DtcRecord, DtcSnapshotRecordNumber)>, Error> {
+        self.collect()
+    }
+}
+
+impl Iterator for DtcSnapshotIdentificationIter<'_> {
+    type Item = Result<(DtcRecord, DtcSnapshotRecordNumber), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        if self.remaining.len() < 4 {
+            return Some(Err(Error::IncorrectMessageLengthOrInvalidFormat));
+        }
+        let record = DtcRecord::new(self.remaining[0], self.remaining[1], self.remaining[2]);
+        let number = DtcSnapshotRecordNumber::new(self.remaining[3]);
+        self.remaining = &self.remaining[4..];
+        Some(Ok((record, number)))
+    }
+}
+
+/// Split the `numberOfIdentifiers` DID + data pairs of one snapshot or stored data record off
+/// the front of `buf`, returning `(pairs, rest)`.
+///
+/// A count of `0x00` means the server has more than 255 identifiers to report, in which case
+/// the pairs run to the end of the response.
+fn split_did_records<'a, R: DidRegistry + ?Sized>(
+    buf: &'a [u8],
+    number_of_identifiers: u8,
+    registry: &R,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    let mut records = DataRecordIter::new(buf, registry);
+    if number_of_identifiers == 0 {
+        for record in records.by_ref() {
+            record?;
+        }
+    } else {
+        for _ in 0..number_of_identifiers {
+            records
+                .next()
+                .ok_or(Error::IncorrectMessageLengthOrInvalidFormat)??;
+        }
+    }
+    Ok(buf.split_at(buf.len() - records.remaining().len()))
+}
+
+/// One `DTCSnapshotRecord` of a `reportDTCSnapshotRecordByDTCNumber` (0x04) response
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DtcSnapshotRecord<'a> {
+    /// `DTCSnapshotRecordNumber`
+    pub record_number: DtcSnapshotRecordNumber,
+    /// `DTCSnapshotRecordNumberOfIdentifiers`; `0` means more than 255.
+    pub number_of_identifiers: u8,
+    /// Raw DID + data pairs — use [`data`](Self::data) to iterate.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub raw_data: &'a [u8],
+}
+
+impl<'a> DtcSnapshotRecord<'a> {
+    /// Iterate the `(data identifier, data)` pairs of this record, taking each data length
+    /// from `registry`.
+    pub fn data<'r, R: DidRegistry + ?Sized>(&self, registry: &'r R) -> DataRecordIter<'a, 'r, R> {
+        DataRecordIter::new(self.raw_data, registry)
+    }
+}
+
+/// Lazy iterator over the [`DtcSnapshotRecord`]s of a `reportDTCSnapshotRecordByDTCNumber`
+/// (0x04) response.
+///
+/// Each data length is taken from a [`DidRegistry`]; see [`DataRecordIter`] for the errors.
+/// The iterator stops after the first error.
+#[derive(Debug)]
+pub struct DtcSnapshotRecordIter<'a, 'r, R: ?Sized> {
+    remaining: &'a [u8],
+    registry: &'r R,
+}
+
+impl_registry_iter_clone!(DtcSnapshotRecordIter);
+
+impl<'a, R: DidRegistry + ?Sized> Iterator for DtcSnapshotRecordIter<'a, '_, R> {
+    type Item = Result<DtcSnapshotRecord<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let registry = self.registry;
+        step(&mut self.remaining, |buf| {
+            let (record_number, rest) = DtcSnapshotRecordNumber::decode(buf)?;
+            let (number_of_identifiers, rest) = read_u8(rest)?;
+            let (raw_data, rest) = split_did_records(rest, number_of_identifiers, registry)?;
+            Ok((
+                DtcSnapshotRecord {
+                    record_number,
+                    number_of_identifiers,
+                    raw_data,
+                },
+                rest,
+            ))
+        })
+    }
+}
+
+/// One `DTCStoredDataRecord` of a `reportDTCStoredDataByRecordNumber` (0x05) response
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DtcStoredDataRecord<'a> {
+    /// `DTCStoredDataRecordNumber`
+    pub record_number: DtcStoredDataRecordNumber,
+    /// The DTC the data was stored for.
+    pub dtc_record: DtcRecord,
+    /// Status of that DTC.
+    pub status: DtcStatusMask,
+    /// `DTCStoredDataRecordNumberOfIdentifiers`; `0` means more than 255.
+    pub number_of_identifiers: u8,
+    /// Raw DID + data pairs — use [`data`](Self::data) to iterate.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub raw_data: &'a [u8],
+}
+
+impl<'a> DtcStoredDataRecord<'a> {
+    /// Iterate the `(data identifier, data)` pairs of this record, taking each data length
+    /// from `registry`.
+    pub fn data<'r, R: DidRegistry + ?Sized>(&self, registry: &'r R) -> DataRecordIter<'a, 'r, R> {
+        DataRecordIter::new(self.raw_data, registry)
+    }
+}
+
+/// Lazy iterator over the [`DtcStoredDataRecord`]s of a `reportDTCStoredDataByRecordNumber`
+/// (0x05) response.
+///
+/// Each data length is taken from a [`DidRegistry`]; see [`DataRecordIter`] for the errors.
+/// The iterator stops after the first error.
+#[derive(Debug)]
+pub struct DtcStoredDataRecordIter<'a, 'r, R: ?Sized> {
+    remaining: &'a [u8],
+    registry: &'r R,
+}
+
+impl_registry_iter_clone!(DtcStoredDataRecordIter);
+
+impl<'a, R: DidRegistry + ?Sized> Iterator for DtcStoredDataRecordIter<'a, '_, R> {
+    type Item = Result<DtcStoredDataRecord<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let registry = self.registry;
+        step(&mut self.remaining, |buf| {
+            let (record_number, rest) = DtcStoredDataRecordNumber::decode(buf)?;
+            let (dtc_record, rest) = DtcRecord::decode(rest)?;
+            let (status, rest) = DtcStatusMask::decode(rest)?;
+            let (number_of_identifiers, rest) = read_u8(rest)?;
+            let (raw_data, rest) = split_did_records(rest, number_of_identifiers, registry)?;
+            Ok((
+                DtcStoredDataRecord {
+                    record_number,
+                    dtc_record,
+                    status,
+                    number_of_identifiers,
+                    raw_data,
+                },
+                rest,
+            ))
+        })
+    }
+}
+
+/// Maps a `DTCExtDataRecordNumber` to the size of its `DTCExtDataRecord`.
+///
+/// `ReadDTCInformation` extended data responses do not carry record sizes; a registry supplies
+/// them, typically from the ECU's diagnostic description. Implemented for static
+/// `(record number, size)` tables, which work in `no_std`, and for closures:
+///
+/// ```
+/// use uds_protocol::{DtcExtDataRecordNumber, DtcExtDataRegistry};
+/// static EXT_DATA: [(u8, usize); 2] = [(0x01, 1), (0x02, 2)];
+/// assert_eq!(EXT_DATA.record_len(DtcExtDataRecordNumber::new(0x02)), Some(2));
+/// assert_eq!(EXT_DATA.record_len(DtcExtDataRecordNumber::new(0x03)), None);
+///
+/// let by_fn = |number: DtcExtDataRecordNumber| (number == 0x90).then_some(4);
+/// assert_eq!(by_fn.record_len(DtcExtDataRecordNumber::new(0x90)), Some(4));
+/// ```
+pub trait DtcExtDataRegistry {
+    /// The `DTCExtDataRecord` size of `number`, or `None` if the record number is unknown.
+    fn record_len(&self, number: DtcExtDataRecordNumber) -> Option<usize>;
+}
+
+impl DtcExtDataRegistry for [(u8, usize)] {
+    fn record_len(&self, number: DtcExtDataRecordNumber) -> Option<usize> {
+        self.iter()
+            .find_map(|&(entry, len)| (number == entry).then_some(len))
+    }
+}
+
+impl<const N: usize> DtcExtDataRegistry for [(u8, usize); N] {
+    fn record_len(&self, number: DtcExtDataRecordNumber) -> Option<usize> {
+        self.as_slice().record_len(number)
+    }
+}
+
+impl<F: Fn(DtcExtDataRecordNumber) -> Option<usize>> DtcExtDataRegistry for F {
+    fn record_len(&self, number: DtcExtDataRecordNumber) -> Option<usize> {
+        self(number)
+    }
+}
+
+/// Lazy iterator over the `(DtcExtDataRecordNumber, data)` entries of a
+/// `reportDTCExtDataRecordByDTCNumber` (0x06) or `reportUserDefMemoryDTCExtDataRecordByDTCNumber`
+/// (0x19) response.
+///
+/// Each record size is taken from a [`DtcExtDataRegistry`]; an unknown record number yields
+/// [`Error::UnknownExtDataRecordNumber`], and a truncated record
+/// [`Error::IncorrectMessageLengthOrInvalidFormat`]. The iterator stops after the first error.
+#[derive(Debug)]
+pub struct DtcExtDataIter<'a, 'r, R: ?Sized> {
+    remaining: &'a [u8],
+    registry: &'r R,
+}
+
+impl_registry_iter_clone!(DtcExtDataIter);
+
+impl<'a, R: DtcExtDataRegistry + ?Sized> Iterator for DtcExtDataIter<'a, '_, R> {
+    type Item = Result<(DtcExtDataRecordNumber, &'a [u8]), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let registry = self.registry;
+        step(&mut self.remaining, |buf| {
+            let (number, rest) = DtcExtDataRecordNumber::decode(buf)?;
+            let len = registry
+                .record_len(number)
+                .ok_or(Error::UnknownExtDataRecordNumber(number.value()))?;
+            let (data, rest) =
+                take(rest, len).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+            Ok(((number, data), rest))
+        })
+    }
+}
+
+/// Lazy iterator over the `(DtcRecord, DtcStatusMask, data)` entries of a
+/// `reportDTCExtDataRecordByRecordNumber` (0x16) response.
+///
+/// Every entry carries the same `DTCExtDataRecord`, so its size is looked up once when the
+/// iterator is created; an unknown record number yields a single
+/// [`Error::UnknownExtDataRecordNumber`], and a truncated record
+/// [`Error::IncorrectMessageLengthOrInvalidFormat`].
+#[derive(Clone, Debug)]
+pub struct DtcAndExtDataIter<'a> {
+    remaining: &'a [u8],
+    record_number: DtcExtDataRecordNumber,
+    record_len: Option<usize>,
+}
+
+impl<'a> Iterator for DtcAndExtDataIter<'a> {
+    type Item = Result<(DtcRecord, DtcStatusMask, &'a [u8]), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (record_number, record_len) = (self.record_number, self.record_len);
+        step(&mut self.remaining, |buf| {
+            let len = record_len.ok_or(Error::UnknownExtDataRecordNumber(record_number.value()))?;
+            let (dtc_record, rest) = DtcRecord::decode(buf)?;
+            let (status, rest) = DtcStatusMask::decode(rest)?;
+            let (data, rest) =
+                take(rest, len).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+            Ok(((dtc_record, status, data), rest))
+        })
+    }
+}
+
+/// Split the `MemorySelection` echo off the front of a user-defined DTC memory response.
+fn split_memory_selection(user_defined: bool, buf: &[u8]) -> Result<(Option<u8>, &[u8]), Error> {
+    if !user_defined {
+        return Ok((None, buf));
+    }
+    let (memory_selection, rest) = read_u8(buf)?;
+    Ok((Some(memory_selection), rest))
+}
+
+/// Write the optional `MemorySelection` echo followed by a DTC and its status.
+fn encode_dtc_record_header(
+    writer: &mut impl embedded_io::Write,
+    memory_selection: Option<u8>,
+    dtc_record: DtcRecord,
+    status: DtcStatusMask,
+) -> Result<usize, Error> {
+    let mut written = 0;
+    if let Some(memory_selection) = memory_selection {
+        written += write_u8(writer, memory_selection).map_err(Error::io)?;
+    }
+    written += dtc_record.encode(writer)?;
+    written += status.encode(writer)?;
+    Ok(written)
+}
+
 /// Zero-copy parsed response for `ReadDTCInformation` (0x19).
 ///
 /// Stores raw bytes for record collections and provides lazy iterators
@@ -657,12 +1047,13 @@ impl Iterator for DtcSeverityAndStatusIter<'_> {
 ///
 /// # Coverage
 ///
-/// This enum models the sub-functions the library currently parses: `0x01`/`0x07`
-/// (number of DTCs), `0x02`/`0x0A`–`0x0E`/`0x15` (DTC + status lists), `0x14` (fault
-/// detection counters), `0x08`/`0x09` (DTC severity lists), and `0x42` (WWH-OBD by mask).
-/// `ReadDTCInformation` defines further sub-functions that are **not yet modeled**;
-/// [`decode`](Self::decode) returns [`Error::InvalidDtcSubfunctionType`] for those. See the
-/// support table in the crate README. This is the "Partial" coverage noted there, not a bug.
+/// This enum models every sub-function defined by ISO-14229-1:2020: `0x01`/`0x07` (number of
+/// DTCs), `0x02`/`0x0A`–`0x0E`/`0x15` (DTC + status lists), `0x03`–`0x05`/`0x18` (snapshot
+/// identification, snapshot records and stored data records), `0x06`/`0x16`/`0x19`/`0x1A`
+/// (extended data records), `0x14` (fault detection counters), `0x08`/`0x09` (DTC severity
+/// lists), `0x17` (user-defined memory DTC list), and `0x42`/`0x55`/`0x56` (WWH-OBD).
+/// [`decode`](Self::decode) returns [`Error::InvalidDtcSubfunctionType`] for reserved
+/// sub-function bytes.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -693,6 +1084,83 @@ pub enum ReadDtcInfoResponse<'a> {
         #[cfg_attr(feature = "serde", serde(borrow))]
         raw_records: &'a [u8],
     },
+    /// Sub-function 0x03: list of `(DtcRecord, DtcSnapshotRecordNumber)` pairs.
+    DtcSnapshotIdentification {
+        /// Raw record bytes — use [`DtcSnapshotIdentificationIter`] to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-functions 0x04, 0x18: the snapshot records of one DTC.
+    DtcSnapshotRecordByDtcNumber {
+        /// `MemorySelection` echo of 0x18 (user-defined DTC memory); `None` for 0x04. The
+        /// sub-function byte is derived from it.
+        memory_selection: Option<u8>,
+        /// The DTC the snapshots belong to.
+        dtc_record: DtcRecord,
+        /// Status of that DTC.
+        status: DtcStatusMask,
+        /// Raw `DTCSnapshotRecord` bytes — use
+        /// [`snapshot_record_iter`](ReadDtcInfoResponse::snapshot_record_iter) to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-function 0x05: stored data records.
+    DtcStoredDataByRecordNumber {
+        /// Raw `DTCStoredDataRecord` bytes — use
+        /// [`stored_data_record_iter`](ReadDtcInfoResponse::stored_data_record_iter) to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-functions 0x06, 0x19: the extended data records of one DTC.
+    DtcExtDataRecordByDtcNumber {
+        /// `MemorySelection` echo of 0x19 (user-defined DTC memory); `None` for 0x06. The
+        /// sub-function byte is derived from it.
+        memory_selection: Option<u8>,
+        /// The DTC the extended data belongs to.
+        dtc_record: DtcRecord,
+        /// Status of that DTC.
+        status: DtcStatusMask,
+        /// Raw `DTCExtDataRecordNumber` + `DTCExtDataRecord` bytes — use
+        /// [`ext_data_iter`](ReadDtcInfoResponse::ext_data_iter) to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-function 0x16: one extended data record for every DTC that stores it.
+    DtcExtDataRecordByRecordNumber {
+        /// The `DTCExtDataRecordNumber` echo.
+        record_number: DtcExtDataRecordNumber,
+        /// Raw DTC + status + `DTCExtDataRecord` bytes — use
+        /// [`dtc_and_ext_data_iter`](ReadDtcInfoResponse::dtc_and_ext_data_iter) to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-function 0x1A: DTCs that support an extended data record.
+    SupportedDtcExtDataRecord {
+        /// Which status bits this server supports reporting. Same representation as
+        /// [`DtcStatusMask`], but a bit is 'on' when the server supports that status — a server
+        /// that does not support [`DtcStatusMask::WarningIndicatorRequested`] leaves that bit
+        /// 'off' and sets the rest.
+        status_availability_mask: DtcStatusMask,
+        /// The `DTCExtDataRecordNumber` echo.
+        record_number: DtcExtDataRecordNumber,
+        /// Raw record bytes — use [`DtcAndStatusIter`] to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-function 0x17: list of `(DtcRecord, DtcStatusMask)` pairs from a user-defined DTC
+    /// memory.
+    UserDefMemoryDtcList {
+        /// `MemorySelection` echo.
+        memory_selection: u8,
+        /// Which status bits this server supports reporting. Same representation as
+        /// [`DtcStatusMask`], but a bit is 'on' when the server supports that status — a server
+        /// that does not support [`DtcStatusMask::WarningIndicatorRequested`] leaves that bit
+        /// 'off' and sets the rest.
+        status_availability_mask: DtcStatusMask,
+        /// Raw record bytes — use [`DtcAndStatusIter`] to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
     /// Sub-function 0x14: list of DTC fault detection counter records.
     DtcFaultDetectionCounterList {
         /// Raw record bytes — use [`DtcFaultDetectionIter`] to iterate.
@@ -709,9 +1177,8 @@ pub enum ReadDtcInfoResponse<'a> {
         /// 'off' and sets the rest.
         status_availability_mask: DtcStatusMask,
         /// Raw `DTCAndSeverityRecord` bytes (6 bytes each: severity + DTC functional unit +
-        /// 3-byte DTC + status). These differ from the 5-byte WWH-OBD records, so
-        /// [`DtcSeverityAndStatusIter`] does **not** apply here; no severity-list iterator is
-        /// wired yet, so parse these bytes caller-side until one is added.
+        /// 3-byte DTC + status) — use [`DtcSeverityFunctionalUnitIter`] to iterate. These differ
+        /// from the 5-byte WWH-OBD records, so [`DtcSeverityAndStatusIter`] does **not** apply.
         #[cfg_attr(feature = "serde", serde(borrow))]
         raw_records: &'a [u8],
     },
@@ -732,16 +1199,141 @@ pub enum ReadDtcInfoResponse<'a> {
         #[cfg_attr(feature = "serde", serde(borrow))]
         raw_records: &'a [u8],
     },
+    /// Sub-function 0x55: WWH-OBD DTCs with permanent status.
+    WwhObdDtcWithPermanentStatus {
+        /// Functional group identifier echo.
+        functional_group_identifier: FunctionalGroupIdentifier,
+        /// Which status bits this server supports reporting. Same representation as
+        /// [`DtcStatusMask`], but a bit is 'on' when the server supports that status — a server
+        /// that does not support [`DtcStatusMask::WarningIndicatorRequested`] leaves that bit
+        /// 'off' and sets the rest.
+        status_availability_mask: DtcStatusMask,
+        /// DTC format identifier.
+        format_identifier: DtcFormatIdentifier,
+        /// Raw record bytes — use [`DtcAndStatusIter`] to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
+    /// Sub-function 0x56: DTCs of one readiness group.
+    DtcByReadinessGroupIdentifier {
+        /// Functional group identifier echo.
+        functional_group_identifier: FunctionalGroupIdentifier,
+        /// Which status bits this server supports reporting. Same representation as
+        /// [`DtcStatusMask`], but a bit is 'on' when the server supports that status — a server
+        /// that does not support [`DtcStatusMask::WarningIndicatorRequested`] leaves that bit
+        /// 'off' and sets the rest.
+        status_availability_mask: DtcStatusMask,
+        /// DTC format identifier.
+        format_identifier: DtcFormatIdentifier,
+        /// `DTCReadinessGroupIdentifier` (RGID) echo.
+        readiness_group_identifier: u8,
+        /// Raw record bytes — use [`DtcAndStatusIter`] to iterate.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        raw_records: &'a [u8],
+    },
 }
 
 impl<'a> ReadDtcInfoResponse<'a> {
-    /// Iterate `(DtcRecord, DtcStatusMask)` pairs for `DtcList` variants.
+    /// Iterate `(DtcRecord, DtcStatusMask)` pairs for the `DtcList`,
+    /// `SupportedDtcExtDataRecord`, `UserDefMemoryDtcList`, `WwhObdDtcWithPermanentStatus`
+    /// and `DtcByReadinessGroupIdentifier` variants.
     ///
-    /// Returns `None` if this is not a `DtcList` variant.
+    /// Returns `None` for any other variant.
     #[must_use]
     pub fn dtc_and_status_iter(&self) -> Option<DtcAndStatusIter<'a>> {
         match self {
-            Self::DtcList { raw_records, .. } => Some(DtcAndStatusIter::new(raw_records)),
+            Self::DtcList { raw_records, .. }
+            | Self::SupportedDtcExtDataRecord { raw_records, .. }
+            | Self::UserDefMemoryDtcList { raw_records, .. }
+            | Self::WwhObdDtcWithPermanentStatus { raw_records, .. }
+            | Self::DtcByReadinessGroupIdentifier { raw_records, .. } => {
+                Some(DtcAndStatusIter::new(raw_records))
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate the `(DtcExtDataRecordNumber, data)` entries for the
+    /// `DtcExtDataRecordByDtcNumber` variant.
+    ///
+    /// Returns `None` if this is not that variant. Each record size is taken from `registry`.
+    pub fn ext_data_iter<'r, R: DtcExtDataRegistry + ?Sized>(
+        &self,
+        registry: &'r R,
+    ) -> Option<DtcExtDataIter<'a, 'r, R>> {
+        match self {
+            Self::DtcExtDataRecordByDtcNumber { raw_records, .. } => Some(DtcExtDataIter {
+                remaining: raw_records,
+                registry,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Iterate the `(DtcRecord, DtcStatusMask, data)` entries for the
+    /// `DtcExtDataRecordByRecordNumber` variant.
+    ///
+    /// Returns `None` if this is not that variant. The size of the echoed record number is
+    /// looked up once in `registry`.
+    pub fn dtc_and_ext_data_iter<R: DtcExtDataRegistry + ?Sized>(
+        &self,
+        registry: &R,
+    ) -> Option<DtcAndExtDataIter<'a>> {
+        match self {
+            Self::DtcExtDataRecordByRecordNumber {
+                record_number,
+                raw_records,
+            } => Some(DtcAndExtDataIter {
+                remaining: raw_records,
+                record_number: *record_number,
+                record_len: registry.record_len(*record_number),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Iterate `(DtcRecord, DtcSnapshotRecordNumber)` pairs for the
+    /// `DtcSnapshotIdentification` variant.
+    ///
+    /// Returns `None` if this is not that variant.
+    #[must_use]
+    pub fn snapshot_identification_iter(&self) -> Option<DtcSnapshotIdentificationIter<'a>> {
+        match self {
+            Self::DtcSnapshotIdentification { raw_records } => {
+                Some(DtcSnapshotIdentificationIter::new(raw_records))
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate the snapshot records for the `DtcSnapshotRecordByDtcNumber` variant.
+    ///
+    /// Returns `None` if this is not that variant. Each data length is taken from `registry`.
+    pub fn snapshot_record_iter<'r, R: DidRegistry + ?Sized>(
+        &self,
+        registry: &'r R,
+    ) -> Option<DtcSnapshotRecordIter<'a, 'r, R>> {
+        match self {
+            Self::DtcSnapshotRecordByDtcNumber { raw_records, .. } => Some(DtcSnapshotRecordIter {
+                remaining: raw_records,
+                registry,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Iterate the stored data records for the `DtcStoredDataByRecordNumber` variant.
+    ///
+    /// Returns `None` if this is not that variant. Each data length is taken from `registry`.
+    pub fn stored_data_record_iter<'r, R: DidRegistry + ?Sized>(
+        &self,
+        registry: &'r R,
+    ) -> Option<DtcStoredDataRecordIter<'a, 'r, R>> {
+        match self {
+            Self::DtcStoredDataByRecordNumber { raw_records } => Some(DtcStoredDataRecordIter {
+                remaining: raw_records,
+                registry,
+            }),
             _ => None,
         }
     }
@@ -764,7 +1356,8 @@ impl<'a> ReadDtcInfoResponse<'a> {
     /// Returns `None` for every other variant, including
     /// [`DtcSeverityList`](ReadDtcInfoResponse::DtcSeverityList) (0x08/0x09), whose
     /// records are 6 bytes because they carry a `DTCFunctionalUnit` byte this one
-    /// does not.
+    /// does not; use [`severity_functional_unit_iter`](Self::severity_functional_unit_iter)
+    /// for those.
     #[must_use]
     pub fn severity_and_status_iter(&self) -> Option<DtcSeverityAndStatusIter<'a>> {
         match self {
@@ -774,11 +1367,26 @@ impl<'a> ReadDtcInfoResponse<'a> {
             _ => None,
         }
     }
+
+    /// Iterate the severity/functional unit/DTC/status tuples for the `DtcSeverityList`
+    /// variant (0x08/0x09).
+    ///
+    /// Returns `None` if this is not that variant.
+    #[must_use]
+    pub fn severity_functional_unit_iter(&self) -> Option<DtcSeverityFunctionalUnitIter<'a>> {
+        match self {
+            Self::DtcSeverityList { raw_records, .. } => {
+                Some(DtcSeverityFunctionalUnitIter::new(raw_records))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl<'a> Decode<'a> for ReadDtcInfoResponse<'a> {
     type Error = crate::Error;
 
+    #[allow(clippy::too_many_lines)]
     fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
         if buf.is_empty() {
             return Err(Error::InsufficientData(Incomplete {
@@ -825,6 +1433,70 @@ impl<'a> Decode<'a> for ReadDtcInfoResponse<'a> {
                     &[],
                 ))
             }
+            0x03 => Ok((Self::DtcSnapshotIdentification { raw_records: buf }, &[])),
+            0x04 | 0x18 => {
+                let (memory_selection, rest) = split_memory_selection(subfunction_id == 0x18, buf)?;
+                let (dtc_record, rest) = DtcRecord::decode(rest)?;
+                let (status, rest) = DtcStatusMask::decode(rest)?;
+                Ok((
+                    Self::DtcSnapshotRecordByDtcNumber {
+                        memory_selection,
+                        dtc_record,
+                        status,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
+            0x05 => Ok((Self::DtcStoredDataByRecordNumber { raw_records: buf }, &[])),
+            0x06 | 0x19 => {
+                let (memory_selection, rest) = split_memory_selection(subfunction_id == 0x19, buf)?;
+                let (dtc_record, rest) = DtcRecord::decode(rest)?;
+                let (status, rest) = DtcStatusMask::decode(rest)?;
+                Ok((
+                    Self::DtcExtDataRecordByDtcNumber {
+                        memory_selection,
+                        dtc_record,
+                        status,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
+            0x16 => {
+                let (record_number, rest) = DtcExtDataRecordNumber::decode(buf)?;
+                Ok((
+                    Self::DtcExtDataRecordByRecordNumber {
+                        record_number,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
+            0x1A => {
+                let (status_availability_mask, rest) = DtcStatusMask::decode(buf)?;
+                let (record_number, rest) = DtcExtDataRecordNumber::decode(rest)?;
+                Ok((
+                    Self::SupportedDtcExtDataRecord {
+                        status_availability_mask,
+                        record_number,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
+            0x17 => {
+                let (memory_selection, rest) = read_u8(buf)?;
+                let (status_availability_mask, rest) = DtcStatusMask::decode(rest)?;
+                Ok((
+                    Self::UserDefMemoryDtcList {
+                        memory_selection,
+                        status_availability_mask,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
             0x14 => Ok((Self::DtcFaultDetectionCounterList { raw_records: buf }, &[])),
             0x08 | 0x09 => {
                 if buf.is_empty() {
@@ -865,6 +1537,34 @@ impl<'a> Decode<'a> for ReadDtcInfoResponse<'a> {
                     &[],
                 ))
             }
+            0x55 | 0x56 => {
+                let (functional_group_identifier, rest) = FunctionalGroupIdentifier::decode(buf)?;
+                let (status_availability_mask, rest) = DtcStatusMask::decode(rest)?;
+                let (format_identifier, rest) = read_u8(rest)?;
+                let format_identifier = DtcFormatIdentifier::from(format_identifier);
+                if subfunction_id == 0x55 {
+                    return Ok((
+                        Self::WwhObdDtcWithPermanentStatus {
+                            functional_group_identifier,
+                            status_availability_mask,
+                            format_identifier,
+                            raw_records: rest,
+                        },
+                        &[],
+                    ));
+                }
+                let (readiness_group_identifier, rest) = read_u8(rest)?;
+                Ok((
+                    Self::DtcByReadinessGroupIdentifier {
+                        functional_group_identifier,
+                        status_availability_mask,
+                        format_identifier,
+                        readiness_group_identifier,
+                        raw_records: rest,
+                    },
+                    &[],
+                ))
+            }
             _ => Err(Error::InvalidDtcSubfunctionType(subfunction_id)),
         }
     }
@@ -873,6 +1573,7 @@ impl<'a> Decode<'a> for ReadDtcInfoResponse<'a> {
 impl Encode for ReadDtcInfoResponse<'_> {
     type Error = crate::Error;
 
+    #[allow(clippy::too_many_lines)]
     fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
         let mut written = 0;
         match self {
@@ -899,6 +1600,115 @@ impl Encode for ReadDtcInfoResponse<'_> {
                     .map_err(Error::io)?;
                 written += write_all(writer, raw_records).map_err(Error::io)?;
             }
+            Self::DtcSnapshotIdentification { raw_records } => {
+                written += write_u8(writer, 0x03).map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::DtcSnapshotRecordByDtcNumber {
+                memory_selection,
+                dtc_record,
+                status,
+                raw_records,
+            } => {
+                let sub_function_id = if memory_selection.is_some() {
+                    0x18
+                } else {
+                    0x04
+                };
+                written += write_u8(writer, sub_function_id).map_err(Error::io)?;
+                written +=
+                    encode_dtc_record_header(writer, *memory_selection, *dtc_record, *status)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::DtcExtDataRecordByDtcNumber {
+                memory_selection,
+                dtc_record,
+                status,
+                raw_records,
+            } => {
+                let sub_function_id = if memory_selection.is_some() {
+                    0x19
+                } else {
+                    0x06
+                };
+                written += write_u8(writer, sub_function_id).map_err(Error::io)?;
+                written +=
+                    encode_dtc_record_header(writer, *memory_selection, *dtc_record, *status)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::DtcStoredDataByRecordNumber { raw_records } => {
+                written += write_u8(writer, 0x05).map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::DtcExtDataRecordByRecordNumber {
+                record_number,
+                raw_records,
+            } => {
+                written += write_all(writer, &[0x16, record_number.value()]).map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::SupportedDtcExtDataRecord {
+                status_availability_mask,
+                record_number,
+                raw_records,
+            } => {
+                written += write_all(
+                    writer,
+                    &[0x1A, status_availability_mask.bits(), record_number.value()],
+                )
+                .map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::UserDefMemoryDtcList {
+                memory_selection,
+                status_availability_mask,
+                raw_records,
+            } => {
+                written += write_all(
+                    writer,
+                    &[0x17, *memory_selection, status_availability_mask.bits()],
+                )
+                .map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::WwhObdDtcWithPermanentStatus {
+                functional_group_identifier,
+                status_availability_mask,
+                format_identifier,
+                raw_records,
+            } => {
+                written += write_all(
+                    writer,
+                    &[
+                        0x55,
+                        u8::from(*functional_group_identifier),
+                        status_availability_mask.bits(),
+                        u8::from(*format_identifier),
+                    ],
+                )
+                .map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
+            Self::DtcByReadinessGroupIdentifier {
+                functional_group_identifier,
+                status_availability_mask,
+                format_identifier,
+                readiness_group_identifier,
+                raw_records,
+            } => {
+                written += write_all(
+                    writer,
+                    &[
+                        0x56,
+                        u8::from(*functional_group_identifier),
+                        status_availability_mask.bits(),
+                        u8::from(*format_identifier),
+                        *readiness_group_identifier,
+                    ],
+                )
+                .map_err(Error::io)?;
+                written += write_all(writer, raw_records).map_err(Error::io)?;
+            }
             Self::DtcFaultDetectionCounterList { raw_records } => {
                 written += write_u8(writer, 0x14).map_err(Error::io)?;
                 written += write_all(writer, raw_records).map_err(Error::io)?;
@@ -944,18 +1754,23 @@ mod derive_contract {
         assert_impl_eq::<ReadDtcInfoRequest>();
         assert_impl_eq::<DtcFaultDetectionCounterRecord>();
         assert_impl_eq::<ReadDtcInfoResponse<'static>>();
+        assert_impl_eq::<DtcSnapshotRecord<'static>>();
+        assert_impl_eq::<DtcStoredDataRecord<'static>>();
     }
 
     #[cfg(feature = "serde")]
     #[test]
     fn serde_impls() {
         assert_impl_serde::<ReadDtcInfoResponse<'static>>();
+        assert_impl_serde::<DtcSnapshotRecord<'static>>();
+        assert_impl_serde::<DtcStoredDataRecord<'static>>();
     }
 }
 
 #[cfg(test)]
 mod iter_tests {
     use super::*;
+    use crate::UdsIdentifier;
 
     #[test]
     fn len_counts_complete_records_and_is_empty_agrees() {
@@ -974,6 +1789,430 @@ mod iter_tests {
         assert!(iter.is_empty());
     }
 
+    /// DID lengths for the snapshot tests: 0xF190 is 2 bytes, 0x0102 is 1 byte.
+    fn did_len(identifier: UdsIdentifier) -> Option<usize> {
+        match u16::from(identifier) {
+            0xF190 => Some(2),
+            0x0102 => Some(1),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn snapshot_identification_iterates_pairs() {
+        let wire = [0x03, 0x12, 0x34, 0x56, 0x01, 0x12, 0x34, 0x56, 0x02];
+        let (response, rest) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let mut iter = response.snapshot_identification_iter().unwrap();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            (
+                DtcRecord::new(0x12, 0x34, 0x56),
+                DtcSnapshotRecordNumber::Number(0x01)
+            )
+        );
+        assert_eq!(iter.next().unwrap().unwrap().1, 0x02);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn snapshot_records_split_by_did_length() {
+        let wire = [
+            0x04, 0x12, 0x34, 0x56, 0x24, // DTC + status
+            0x01, 0x02, 0xF1, 0x90, 0xAA, 0xBB, 0x01, 0x02, 0xCC, // record 1, two DIDs
+            0x02, 0x01, 0x01, 0x02, 0xDD, // record 2, one DID
+        ];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let ReadDtcInfoResponse::DtcSnapshotRecordByDtcNumber {
+            dtc_record, status, ..
+        } = response
+        else {
+            panic!("expected DtcSnapshotRecordByDtcNumber");
+        };
+        assert_eq!(dtc_record, DtcRecord::new(0x12, 0x34, 0x56));
+        assert_eq!(status.bits(), 0x24);
+
+        let mut records = response.snapshot_record_iter(&did_len).unwrap();
+        let first = records.next().unwrap().unwrap();
+        assert_eq!(first.record_number, 0x01);
+        assert_eq!(first.number_of_identifiers, 2);
+        let mut data = first.data(&did_len);
+        assert_eq!(
+            data.next().unwrap().unwrap(),
+            (UdsIdentifier::from(0xF190), &[0xAA, 0xBB][..])
+        );
+        assert_eq!(
+            data.next().unwrap().unwrap(),
+            (UdsIdentifier::from(0x0102), &[0xCC][..])
+        );
+        assert!(data.next().is_none());
+        let second = records.next().unwrap().unwrap();
+        assert_eq!(second.raw_data, &[0x01, 0x02, 0xDD]);
+        assert!(records.next().is_none());
+
+        let mut buf = [0u8; 32];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn snapshot_records_error_on_unknown_did() {
+        let wire = [0x04, 0x12, 0x34, 0x56, 0x24, 0x01, 0x01, 0xAB, 0xCD, 0x00];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut records = response.snapshot_record_iter(&did_len).unwrap();
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::UnknownDataIdentifier(0xABCD)))
+        ));
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn snapshot_records_take_a_static_registry() {
+        static DIDS: [(u16, usize); 2] = [(0xF190, 2), (0x0102, 1)];
+        let wire = [0x04, 0x12, 0x34, 0x56, 0x24, 0x01, 0x01, 0x01, 0x02, 0xCC];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let record = response
+            .snapshot_record_iter(&DIDS)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            record.data(&DIDS).next().unwrap().unwrap(),
+            (UdsIdentifier::from(0x0102), &[0xCC][..])
+        );
+
+        // Two identifiers declared, one present.
+        let wire = [0x04, 0x12, 0x34, 0x56, 0x24, 0x01, 0x02, 0x01, 0x02, 0xCC];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(matches!(
+            response.snapshot_record_iter(&DIDS).unwrap().next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+    }
+
+    #[test]
+    fn stored_data_records_split_by_did_length() {
+        let wire = [
+            0x05, // sub-function
+            0x01, 0x12, 0x34, 0x56, 0x09, 0x01, 0x01, 0x02, 0xEE, // record 1
+            0x02, 0x65, 0x43, 0x21, 0x08, 0x00, 0xF1, 0x90, 0x01, 0x02, // record 2, count 0
+        ];
+        let (response, rest) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let mut records = response.stored_data_record_iter(&did_len).unwrap();
+        let first = records.next().unwrap().unwrap();
+        assert_eq!(first.record_number.value(), 0x01);
+        assert_eq!(first.dtc_record, DtcRecord::new(0x12, 0x34, 0x56));
+        assert_eq!(first.status.bits(), 0x09);
+        assert_eq!(first.data(&did_len).count(), 1);
+        // A zero identifier count runs to the end of the response.
+        let second = records.next().unwrap().unwrap();
+        assert_eq!(second.number_of_identifiers, 0);
+        assert_eq!(
+            second.data(&did_len).next().unwrap().unwrap(),
+            (UdsIdentifier::from(0xF190), &[0x01, 0x02][..])
+        );
+        assert!(records.next().is_none());
+
+        let mut buf = [0u8; 32];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn snapshot_record_by_dtc_number_requires_dtc_and_status() {
+        assert!(matches!(
+            ReadDtcInfoResponse::decode(&[0x04, 0x12, 0x34, 0x56]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    /// Extended data record sizes for the tests: 0x01 (occurrence counter) is 1 byte, 0x02
+    /// (aging counter) is 2 bytes.
+    static EXT_DATA: [(u8, usize); 2] = [(0x01, 1), (0x02, 2)];
+
+    #[test]
+    fn ext_data_by_dtc_number_iterates_records() {
+        let wire = [0x06, 0x12, 0x34, 0x56, 0x2F, 0x01, 0x05, 0x02, 0x00, 0x28];
+        let (response, rest) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let ReadDtcInfoResponse::DtcExtDataRecordByDtcNumber {
+            memory_selection,
+            dtc_record,
+            status,
+            ..
+        } = response
+        else {
+            panic!("expected DtcExtDataRecordByDtcNumber");
+        };
+        assert_eq!(memory_selection, None);
+        assert_eq!(dtc_record, DtcRecord::new(0x12, 0x34, 0x56));
+        assert_eq!(status.bits(), 0x2F);
+        let mut iter = response.ext_data_iter(&EXT_DATA).unwrap();
+        let (number, data) = iter.next().unwrap().unwrap();
+        assert_eq!((number.value(), data), (0x01, &[0x05][..]));
+        let (number, data) = iter.next().unwrap().unwrap();
+        assert_eq!((number.value(), data), (0x02, &[0x00, 0x28][..]));
+        assert!(iter.next().is_none());
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn memory_selection_selects_the_encoded_sub_function() {
+        for (response, sub_function_id) in [
+            (
+                ReadDtcInfoResponse::DtcSnapshotRecordByDtcNumber {
+                    memory_selection: None,
+                    dtc_record: DtcRecord::new(0x12, 0x34, 0x56),
+                    status: DtcStatusMask::from(0x24),
+                    raw_records: &[],
+                },
+                0x04,
+            ),
+            (
+                ReadDtcInfoResponse::DtcSnapshotRecordByDtcNumber {
+                    memory_selection: Some(0x10),
+                    dtc_record: DtcRecord::new(0x12, 0x34, 0x56),
+                    status: DtcStatusMask::from(0x24),
+                    raw_records: &[],
+                },
+                0x18,
+            ),
+            (
+                ReadDtcInfoResponse::DtcExtDataRecordByDtcNumber {
+                    memory_selection: None,
+                    dtc_record: DtcRecord::new(0x12, 0x34, 0x56),
+                    status: DtcStatusMask::from(0x24),
+                    raw_records: &[],
+                },
+                0x06,
+            ),
+            (
+                ReadDtcInfoResponse::DtcExtDataRecordByDtcNumber {
+                    memory_selection: Some(0x10),
+                    dtc_record: DtcRecord::new(0x12, 0x34, 0x56),
+                    status: DtcStatusMask::from(0x24),
+                    raw_records: &[],
+                },
+                0x19,
+            ),
+        ] {
+            let mut buf = [0u8; 8];
+            let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+            assert_eq!(buf[0], sub_function_id);
+            let (decoded, rest) = ReadDtcInfoResponse::decode(&buf[..written]).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(decoded, response);
+        }
+    }
+
+    #[test]
+    fn user_def_memory_ext_data_carries_memory_selection() {
+        let wire = [0x19, 0x10, 0x12, 0x34, 0x56, 0x2F, 0x01, 0x05];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(matches!(
+            response,
+            ReadDtcInfoResponse::DtcExtDataRecordByDtcNumber {
+                memory_selection: Some(0x10),
+                ..
+            }
+        ));
+        assert_eq!(response.ext_data_iter(&EXT_DATA).unwrap().count(), 1);
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn ext_data_errors_on_unknown_record_number() {
+        let wire = [0x06, 0x12, 0x34, 0x56, 0x2F, 0x03, 0x05];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.ext_data_iter(&EXT_DATA).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::UnknownExtDataRecordNumber(0x03)))
+        ));
+        assert!(iter.next().is_none());
+
+        let wire = [0x16, 0x03, 0x12, 0x34, 0x56, 0x2F, 0x05];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.dtc_and_ext_data_iter(&EXT_DATA).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::UnknownExtDataRecordNumber(0x03)))
+        ));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn ext_data_errors_on_truncated_record() {
+        let wire = [0x06, 0x12, 0x34, 0x56, 0x2F, 0x02, 0x00];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.ext_data_iter(&EXT_DATA).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+        assert!(iter.next().is_none());
+
+        let wire = [0x16, 0x02, 0x12, 0x34, 0x56, 0x2F, 0x00];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.dtc_and_ext_data_iter(&EXT_DATA).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+        assert!(iter.next().is_none());
+
+        // A truncated DTC keeps the decode error.
+        let wire = [0x16, 0x02, 0x12];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.dtc_and_ext_data_iter(&EXT_DATA).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::InsufficientData(Incomplete {
+                needed: 3,
+                available: 1
+            })))
+        ));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn ext_data_by_record_number_iterates_dtcs() {
+        let wire = [
+            0x16, 0x02, // record number
+            0x12, 0x34, 0x56, 0x2F, 0x00, 0x28, // DTC 1
+            0x65, 0x43, 0x21, 0x08, 0x00, 0x01, // DTC 2
+        ];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.dtc_and_ext_data_iter(&EXT_DATA).unwrap();
+        let (dtc_record, status, data) = iter.next().unwrap().unwrap();
+        assert_eq!(dtc_record, DtcRecord::new(0x12, 0x34, 0x56));
+        assert_eq!(status.bits(), 0x2F);
+        assert_eq!(data, &[0x00, 0x28]);
+        assert_eq!(iter.next().unwrap().unwrap().2, &[0x00, 0x01]);
+        assert!(iter.next().is_none());
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn supported_ext_data_record_lists_dtcs() {
+        let wire = [0x1A, 0xFF, 0x01, 0x12, 0x34, 0x56, 0x2F];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        let mut iter = response.dtc_and_status_iter().unwrap();
+        assert_eq!(iter.len(), 1);
+        assert_eq!(
+            iter.next().unwrap().unwrap().0,
+            DtcRecord::new(0x12, 0x34, 0x56)
+        );
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn user_def_memory_snapshot_carries_memory_selection() {
+        let wire = [
+            0x18, 0x10, 0x12, 0x34, 0x56, 0x24, // memory selection, DTC + status
+            0x01, 0x01, 0x01, 0x02, 0xCC, // record 1, one DID
+        ];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(matches!(
+            response,
+            ReadDtcInfoResponse::DtcSnapshotRecordByDtcNumber {
+                memory_selection: Some(0x10),
+                ..
+            }
+        ));
+        let record = response
+            .snapshot_record_iter(&did_len)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(record.unwrap().data(&did_len).count(), 1);
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn user_def_memory_dtc_list_iterates_pairs() {
+        let wire = [0x17, 0x10, 0xFF, 0x12, 0x34, 0x56, 0x2F];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(matches!(
+            response,
+            ReadDtcInfoResponse::UserDefMemoryDtcList {
+                memory_selection: 0x10,
+                ..
+            }
+        ));
+        assert_eq!(response.dtc_and_status_iter().unwrap().len(), 1);
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn wwh_obd_permanent_and_readiness_group_lists() {
+        let permanent = [0x55, 0x33, 0xFF, 0x04, 0x12, 0x34, 0x56, 0x2F];
+        let (response, _) = ReadDtcInfoResponse::decode(&permanent).unwrap();
+        let ReadDtcInfoResponse::WwhObdDtcWithPermanentStatus {
+            functional_group_identifier,
+            ..
+        } = response
+        else {
+            panic!("expected WwhObdDtcWithPermanentStatus");
+        };
+        assert_eq!(
+            functional_group_identifier,
+            FunctionalGroupIdentifier::EmissionsSystemGroup
+        );
+        assert_eq!(response.dtc_and_status_iter().unwrap().len(), 1);
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &permanent);
+
+        let readiness = [0x56, 0x33, 0xFF, 0x04, 0x07, 0x12, 0x34, 0x56, 0x2F];
+        let (response, _) = ReadDtcInfoResponse::decode(&readiness).unwrap();
+        assert!(matches!(
+            response,
+            ReadDtcInfoResponse::DtcByReadinessGroupIdentifier {
+                readiness_group_identifier: 0x07,
+                ..
+            }
+        ));
+        assert_eq!(response.dtc_and_status_iter().unwrap().len(), 1);
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &readiness);
+
+        assert!(matches!(
+            ReadDtcInfoResponse::decode(&[0x56, 0x33, 0xFF, 0x04]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn reserved_sub_function_is_rejected() {
+        assert!(matches!(
+            ReadDtcInfoResponse::decode(&[0x57]),
+            Err(Error::InvalidDtcSubfunctionType(0x57))
+        ));
+    }
+
     #[test]
     fn all_three_iterators_expose_consistent_len() {
         // 4-byte fault-detection records.
@@ -982,5 +2221,32 @@ mod iter_tests {
         // 5-byte severity/DTC/status records.
         assert_eq!(DtcSeverityAndStatusIter::new(&[0u8; 10]).len(), 2);
         assert!(DtcSeverityAndStatusIter::new(&[0u8; 4]).is_empty());
+        // 6-byte severity/functional unit/DTC/status records.
+        assert_eq!(DtcSeverityFunctionalUnitIter::new(&[0u8; 12]).len(), 2);
+        assert!(DtcSeverityFunctionalUnitIter::new(&[0u8; 5]).is_empty());
+    }
+
+    #[test]
+    fn severity_list_iterates_six_byte_records() {
+        let wire = [
+            0x08, 0xFF, // sub-function, status availability mask
+            0x20, 0x10, 0x12, 0x34, 0x56, 0x2F, // record 1
+            0x40, 0x11, 0x65, 0x43, 0x21, 0x08, // record 2
+            0x80, // partial
+        ];
+        let (response, _) = ReadDtcInfoResponse::decode(&wire).unwrap();
+        assert!(response.severity_and_status_iter().is_none());
+        let mut iter = response.severity_functional_unit_iter().unwrap();
+        assert_eq!(iter.len(), 2);
+        let (severity, functional_unit, record, status) = iter.next().unwrap().unwrap();
+        assert_eq!(severity, DtcSeverityMask::MaintenanceOnly);
+        assert_eq!(functional_unit, 0x10);
+        assert_eq!(record, DtcRecord::new(0x12, 0x34, 0x56));
+        assert_eq!(status.bits(), 0x2F);
+        assert_eq!(iter.next().unwrap().unwrap().1, 0x11);
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
     }
 }
@@ -0,0 +1,248 @@
+//! `ReadMemoryByAddress` (0x23) service implementation
+
+use crate::shared::MemoryFormatIdentifier;
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use automotive_wire_codec::write_all;
+
+const READ_MEMORY_BY_ADDRESS_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 6] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ResponseTooLong,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+    NegativeResponseCode::AuthenticationRequired,
+];
+
+/// A request to the server to read `memory_size` bytes of memory starting at `memory_address`
+///
+/// A positive response to this request ([`ReadMemoryByAddressResponse`]) carries the bytes read.
+///
+/// This is a variable length Request, determined by the `address_and_length_format_identifier` value
+/// See ISO-14229-1:2020, Section 11.3.2.1 and Table H.1 for format information
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadMemoryByAddressRequest {
+    /// 7-4: length (# of bytes) of `memory_size` param, 3-0: length (# of bytes) of `memory_address` param
+    address_and_length_format_identifier: MemoryFormatIdentifier,
+    /// Starting address of the server memory. The on-wire byte width is derived from this
+    /// value (max 5 bytes), so it is private to keep it in sync with the format identifier.
+    memory_address: u64,
+    /// Number of bytes to read. The on-wire byte width is derived from this value
+    /// (max 4 bytes), so it is private to keep it in sync with the format identifier.
+    memory_size: u32,
+}
+
+impl ReadMemoryByAddressRequest {
+    /// Create a new `ReadMemoryByAddressRequest`
+    ///
+    /// # Errors
+    /// Returns an error if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    pub fn new(memory_address: u64, memory_size: u32) -> Result<Self, Error> {
+        Ok(Self {
+            address_and_length_format_identifier: MemoryFormatIdentifier::for_address_and_size(
+                memory_address,
+                memory_size,
+            )?,
+            memory_address,
+            memory_size,
+        })
+    }
+
+    /// Starting address of the server memory.
+    #[must_use]
+    pub const fn memory_address(&self) -> u64 {
+        self.memory_address
+    }
+
+    /// Number of bytes to read.
+    #[must_use]
+    pub const fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &READ_MEMORY_BY_ADDRESS_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for ReadMemoryByAddressRequest {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        self.address_and_length_format_identifier.encode_with(
+            writer,
+            self.memory_address,
+            self.memory_size,
+        )
+    }
+}
+
+impl<'a> Decode<'a> for ReadMemoryByAddressRequest {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (address_and_length_format_identifier, memory_address, memory_size, rest) =
+            MemoryFormatIdentifier::decode_with(buf)?;
+        Ok((
+            Self {
+                address_and_length_format_identifier,
+                memory_address,
+                memory_size,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Positive response to a [`ReadMemoryByAddressRequest`]. Borrows the data record from the caller.
+///
+/// The response carries no length field of its own: the data record is every byte after the
+/// service identifier, and its length should match the requested `memory_size`.
+///
+/// See ISO-14229-1:2020, Section 11.3.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadMemoryByAddressResponse<'d> {
+    /// The memory contents read from the server.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub data_record: &'d [u8],
+}
+
+impl<'d> ReadMemoryByAddressResponse<'d> {
+    /// Create a new response carrying the memory contents that were read.
+    #[must_use]
+    pub const fn new(data_record: &'d [u8]) -> Self {
+        Self { data_record }
+    }
+}
+
+impl Encode for ReadMemoryByAddressResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        write_all(writer, self.data_record).map_err(Error::io)
+    }
+}
+
+impl<'a> Decode<'a> for ReadMemoryByAddressResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        // ISO 14229-1 requires at least one byte of data in a positive response.
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        Ok((Self { data_record: buf }, &[]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn request_roundtrip() {
+        let req = ReadMemoryByAddressRequest::new(0x2048_1392, 0x0103).unwrap();
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x24, 0x20, 0x48, 0x13, 0x92, 0x01, 0x03]);
+        let (decoded, rest) =
+            <ReadMemoryByAddressRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.memory_address(), 0x2048_1392);
+        assert_eq!(decoded.memory_size(), 0x0103);
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn request_roundtrip_maximum_widths() {
+        for (req, wire) in [
+            (
+                ReadMemoryByAddressRequest::new(0x12, 0xFFFF_FFFF).unwrap(),
+                &[0x41, 0x12, 0xFF, 0xFF, 0xFF, 0xFF][..],
+            ),
+            (
+                ReadMemoryByAddressRequest::new(0x12_3456_7890, 1).unwrap(),
+                &[0x15, 0x12, 0x34, 0x56, 0x78, 0x90, 0x01][..],
+            ),
+        ] {
+            let mut buf = [0u8; 8];
+            let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+            assert_eq!(&buf[..written], wire);
+            let (decoded, rest) = <ReadMemoryByAddressRequest as Decode>::decode(wire).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(decoded, req);
+        }
+    }
+
+    #[test]
+    fn request_rejects_oversized_address() {
+        assert!(matches!(
+            ReadMemoryByAddressRequest::new(0x100_0000_0000, 1),
+            Err(Error::InvalidMemoryAddress(0x100_0000_0000))
+        ));
+    }
+
+    #[test]
+    fn request_rejects_short_buffer() {
+        // 0x12: 1 byte of size, 2 bytes of address, but only 2 parameter bytes follow
+        let err = <ReadMemoryByAddressRequest as Decode>::decode(&[0x12, 0x20, 0x48]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 4 && i.available == 3)
+        );
+    }
+
+    #[test]
+    fn request_rejects_invalid_format_identifier() {
+        assert!(matches!(
+            <ReadMemoryByAddressRequest as Decode>::decode(&[0x00]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let data = [0x00, 0x01, 0x8C];
+        let response = ReadMemoryByAddressResponse::new(&data);
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &data);
+        let (decoded, rest) =
+            <ReadMemoryByAddressResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn response_rejects_empty_data_record() {
+        assert!(matches!(
+            <ReadMemoryByAddressResponse as Decode>::decode(&[]),
+            Err(Error::InsufficientData(i)) if i.needed == 1 && i.available == 0
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<ReadMemoryByAddressRequest>();
+        assert_impl_eq::<ReadMemoryByAddressResponse<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<ReadMemoryByAddressRequest>();
+            assert_impl_serde::<ReadMemoryByAddressResponse<'static>>();
+        }
+    }
+}
@@ -0,0 +1,599 @@
+//! `ReadScalingDataByIdentifier` (0x24) service implementation
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode, UdsIdentifier};
+use automotive_wire_codec::{read_u16_be, take, write_all, write_u16_be};
+
+const READ_SCALING_DID_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 4] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+];
+
+/// Size of one formula constant in a `formula` scaling byte extension.
+const FORMULA_CONSTANT_LEN: usize = 2;
+
+/// Data type encoded in the high nibble of a `scalingByte`
+///
+/// See ISO-14229-1:2020, Annex C.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ScalingDataType {
+    /// Unsigned numeric integer
+    UnsignedNumeric,
+    /// Signed numeric integer (two's complement)
+    SignedNumeric,
+    /// Bit-mapped value; the extension holds a validity mask of the same length
+    BitMappedReportedWithoutMask,
+    /// Bit-mapped value followed by its own validity mask
+    BitMappedReportedWithMask,
+    /// Binary coded decimal
+    BinaryCodedDecimal,
+    /// State encoded variable
+    StateEncodedVariable,
+    /// ASCII text
+    Ascii,
+    /// Signed floating point (IEEE 754)
+    SignedFloatingPoint,
+    /// Packet of several values
+    Packet,
+    /// Physical value computed by a formula; see [`FormulaIdentifier`]
+    Formula,
+    /// Unit or display format of the previous value
+    UnitFormat,
+    /// State and connection type of an input/output
+    StateAndConnectionType,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl From<ScalingDataType> for u8 {
+    fn from(value: ScalingDataType) -> Self {
+        match value {
+            ScalingDataType::UnsignedNumeric => 0x0,
+            ScalingDataType::SignedNumeric => 0x1,
+            ScalingDataType::BitMappedReportedWithoutMask => 0x2,
+            ScalingDataType::BitMappedReportedWithMask => 0x3,
+            ScalingDataType::BinaryCodedDecimal => 0x4,
+            ScalingDataType::StateEncodedVariable => 0x5,
+            ScalingDataType::Ascii => 0x6,
+            ScalingDataType::SignedFloatingPoint => 0x7,
+            ScalingDataType::Packet => 0x8,
+            ScalingDataType::Formula => 0x9,
+            ScalingDataType::UnitFormat => 0xA,
+            ScalingDataType::StateAndConnectionType => 0xB,
+            ScalingDataType::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for ScalingDataType {
+    /// Converts the high nibble of a `scalingByte` (already shifted down to `0x0..=0xF`).
+    fn from(value: u8) -> Self {
+        match value {
+            0x0 => Self::UnsignedNumeric,
+            0x1 => Self::SignedNumeric,
+            0x2 => Self::BitMappedReportedWithoutMask,
+            0x3 => Self::BitMappedReportedWithMask,
+            0x4 => Self::BinaryCodedDecimal,
+            0x5 => Self::StateEncodedVariable,
+            0x6 => Self::Ascii,
+            0x7 => Self::SignedFloatingPoint,
+            0x8 => Self::Packet,
+            0x9 => Self::Formula,
+            0xA => Self::UnitFormat,
+            0xB => Self::StateAndConnectionType,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// Formula of a `formula` scaling byte extension, in terms of the internal value `x` and the
+/// constants `C0`, `C1`, … that follow it
+///
+/// See ISO-14229-1:2020, Annex C.2
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FormulaIdentifier {
+    /// `y = C0 * x + C1`
+    Linear,
+    /// `y = C0 * (x + C1)`
+    ScaledOffset,
+    /// `y = C0 / (x + C1) + C2`
+    Reciprocal,
+    /// `y = x / C0 + C1`
+    DividedOffset,
+    /// `y = (x + C0) / C1`
+    OffsetDivided,
+    /// `y = (x + C0) / C1 + C2`
+    OffsetDividedOffset,
+    /// `y = C0 * x`
+    Multiplied,
+    /// `y = x / C0`
+    Divided,
+    /// `y = x + C0`
+    Offset,
+    /// `y = x * C0 / C1`
+    Ratio,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+    /// Reserved for use by vehicle manufacturers (`0x80..=0xFF`).
+    #[cfg_attr(feature = "clap", clap(skip))]
+    VehicleManufacturerSpecific(u8),
+}
+
+impl FormulaIdentifier {
+    /// Number of constants the formula uses, or `None` for reserved and
+    /// manufacturer-specific formulas.
+    #[must_use]
+    pub const fn constant_count(&self) -> Option<usize> {
+        match self {
+            Self::Multiplied | Self::Divided | Self::Offset => Some(1),
+            Self::Linear
+            | Self::ScaledOffset
+            | Self::DividedOffset
+            | Self::OffsetDivided
+            | Self::Ratio => Some(2),
+            Self::Reciprocal | Self::OffsetDividedOffset => Some(3),
+            Self::IsoSaeReserved(_) | Self::VehicleManufacturerSpecific(_) => None,
+        }
+    }
+}
+
+impl From<FormulaIdentifier> for u8 {
+    #[allow(clippy::match_same_arms)]
+    fn from(value: FormulaIdentifier) -> Self {
+        match value {
+            FormulaIdentifier::Linear => 0x00,
+            FormulaIdentifier::ScaledOffset => 0x01,
+            FormulaIdentifier::Reciprocal => 0x02,
+            FormulaIdentifier::DividedOffset => 0x03,
+            FormulaIdentifier::OffsetDivided => 0x04,
+            FormulaIdentifier::OffsetDividedOffset => 0x05,
+            FormulaIdentifier::Multiplied => 0x06,
+            FormulaIdentifier::Divided => 0x07,
+            FormulaIdentifier::Offset => 0x08,
+            FormulaIdentifier::Ratio => 0x09,
+            FormulaIdentifier::IsoSaeReserved(val) => val,
+            FormulaIdentifier::VehicleManufacturerSpecific(val) => val,
+        }
+    }
+}
+
+impl From<u8> for FormulaIdentifier {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Linear,
+            0x01 => Self::ScaledOffset,
+            0x02 => Self::Reciprocal,
+            0x03 => Self::DividedOffset,
+            0x04 => Self::OffsetDivided,
+            0x05 => Self::OffsetDividedOffset,
+            0x06 => Self::Multiplied,
+            0x07 => Self::Divided,
+            0x08 => Self::Offset,
+            0x09 => Self::Ratio,
+            0x0A..=0x7F => Self::IsoSaeReserved(value),
+            _ => Self::VehicleManufacturerSpecific(value),
+        }
+    }
+}
+
+/// A formula constant in the two-byte real number format: `mantissa * 10^exponent`, with a
+/// 4-bit signed exponent in the high nibble and a 12-bit signed mantissa below it
+///
+/// See ISO-14229-1:2020, Annex C.3
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FormulaConstant(pub u16);
+
+impl FormulaConstant {
+    /// The signed 12-bit mantissa.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn mantissa(self) -> i16 {
+        // Shift the 12-bit field to the top, then arithmetic-shift back to sign-extend.
+        ((self.0 << 4) as i16) >> 4
+    }
+
+    /// The signed 4-bit power of ten.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub const fn exponent(self) -> i8 {
+        ((self.0 >> 8) as i8) >> 4
+    }
+}
+
+/// `scalingByteExtension` of one scaling record
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ScalingByteExtension<'a> {
+    /// The data type has no extension.
+    None,
+    /// `bitMappedReportedWithOutMask`: which bits of the value are valid.
+    ValidityMask(#[cfg_attr(feature = "serde", serde(borrow))] &'a [u8]),
+    /// `formula`: the formula and its raw two-byte constants.
+    Formula {
+        /// The formula to apply.
+        formula: FormulaIdentifier,
+        /// The constants `C0`, `C1`, …; see [`ScalingRecord::formula_constants`].
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        constants: &'a [u8],
+    },
+    /// `unit/format`: the unit or display format identifier.
+    UnitFormat(u8),
+}
+
+/// One `scalingByte` and its `scalingByteExtension`
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ScalingRecord<'a> {
+    /// The data type, from the high nibble of the scaling byte.
+    pub data_type: ScalingDataType,
+    /// The low nibble of the scaling byte: the number of bytes of the value, or of the
+    /// extension for `formula` and `unit/format`.
+    pub length: u8,
+    /// The scaling byte extension.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub extension: ScalingByteExtension<'a>,
+}
+
+impl ScalingRecord<'_> {
+    /// The `scalingByte` as sent on the wire.
+    #[must_use]
+    pub fn scaling_byte(&self) -> u8 {
+        (u8::from(self.data_type) << 4) | (self.length & 0x0F)
+    }
+
+    /// Iterate the constants of a `formula` record.
+    ///
+    /// `None` for any other record.
+    #[must_use]
+    pub fn formula_constants(&self) -> Option<impl ExactSizeIterator<Item = FormulaConstant>> {
+        match self.extension {
+            ScalingByteExtension::Formula { constants, .. } => Some(
+                constants
+                    .chunks_exact(FORMULA_CONSTANT_LEN)
+                    .map(|c| FormulaConstant(u16::from_be_bytes([c[0], c[1]]))),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Split one scaling record off the front of `buf`.
+    fn decode(buf: &[u8]) -> Result<(ScalingRecord<'_>, &[u8]), Error> {
+        let Some((&scaling_byte, rest)) = buf.split_first() else {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: 0,
+            }));
+        };
+        let data_type = ScalingDataType::from(scaling_byte >> 4);
+        let length = scaling_byte & 0x0F;
+        let (extension, rest) = match data_type {
+            ScalingDataType::BitMappedReportedWithoutMask => {
+                let (mask, rest) = take(rest, usize::from(length))?;
+                (ScalingByteExtension::ValidityMask(mask), rest)
+            }
+            ScalingDataType::Formula => {
+                let (extension, rest) = take(rest, usize::from(length))?;
+                let Some((&formula, constants)) = extension.split_first() else {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                };
+                if constants.len() % FORMULA_CONSTANT_LEN != 0 {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                }
+                (
+                    ScalingByteExtension::Formula {
+                        formula: FormulaIdentifier::from(formula),
+                        constants,
+                    },
+                    rest,
+                )
+            }
+            ScalingDataType::UnitFormat => {
+                let (extension, rest) = take(rest, usize::from(length))?;
+                let [unit] = *extension else {
+                    return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+                };
+                (ScalingByteExtension::UnitFormat(unit), rest)
+            }
+            _ => (ScalingByteExtension::None, rest),
+        };
+        Ok((
+            ScalingRecord {
+                data_type,
+                length,
+                extension,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Lazy iterator over the [`ScalingRecord`]s of a `ReadScalingDataByIdentifier` response.
+///
+/// Decoding validated the records; on hand-built malformed bytes the iterator stops early.
+#[derive(Clone, Debug)]
+pub struct ScalingRecordIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Iterator for ScalingRecordIter<'a> {
+    type Item = ScalingRecord<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let Ok((record, rest)) = ScalingRecord::decode(self.remaining) else {
+            self.remaining = &[];
+            return None;
+        };
+        self.remaining = rest;
+        Some(record)
+    }
+}
+
+/// Request for the scaling information of a data identifier.
+///
+/// See ISO-14229-1:2020, Section 11.4.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadScalingDataByIdentifierRequest {
+    /// The data identifier whose scaling information is requested.
+    pub identifier: UdsIdentifier,
+}
+
+impl ReadScalingDataByIdentifierRequest {
+    /// Create a new request.
+    #[must_use]
+    pub const fn new(identifier: UdsIdentifier) -> Self {
+        Self { identifier }
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &READ_SCALING_DID_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for ReadScalingDataByIdentifierRequest {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        write_u16_be(writer, u16::from(self.identifier)).map_err(Error::io)
+    }
+}
+
+impl<'a> Decode<'a> for ReadScalingDataByIdentifierRequest {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (identifier, rest) = read_u16_be(buf)?;
+        Ok((Self::new(UdsIdentifier::from(identifier)), rest))
+    }
+}
+
+/// Positive response to `ReadScalingDataByIdentifier`: the echoed data identifier followed by
+/// its `scalingByte` / `scalingByteExtension` records.
+///
+/// See ISO-14229-1:2020, Section 11.4.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadScalingDataByIdentifierResponse<'a> {
+    /// The data identifier echoed from the request.
+    pub identifier: UdsIdentifier,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    records: &'a [u8],
+}
+
+impl<'a> ReadScalingDataByIdentifierResponse<'a> {
+    /// Wrap the raw scaling record bytes of a response.
+    #[must_use]
+    pub const fn new(identifier: UdsIdentifier, records: &'a [u8]) -> Self {
+        Self {
+            identifier,
+            records,
+        }
+    }
+
+    /// The raw `scalingByte` / `scalingByteExtension` bytes.
+    #[must_use]
+    pub const fn raw_records(&self) -> &'a [u8] {
+        self.records
+    }
+
+    /// Iterate the scaling records.
+    #[must_use]
+    pub const fn records(&self) -> ScalingRecordIter<'a> {
+        ScalingRecordIter {
+            remaining: self.records,
+        }
+    }
+}
+
+impl Encode for ReadScalingDataByIdentifierResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let written = write_u16_be(writer, u16::from(self.identifier)).map_err(Error::io)?;
+        Ok(written + write_all(writer, self.records).map_err(Error::io)?)
+    }
+}
+
+impl<'a> Decode<'a> for ReadScalingDataByIdentifierResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (identifier, records) = read_u16_be(buf)?;
+        if records.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: 0,
+            }));
+        }
+        let mut rest = records;
+        while !rest.is_empty() {
+            (_, rest) = ScalingRecord::decode(rest)?;
+        }
+        Ok((Self::new(UdsIdentifier::from(identifier), records), &[]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn nibble_enums_round_trip_all_values() {
+        for i in 0..=0x0F {
+            assert_eq!(u8::from(ScalingDataType::from(i)), i);
+        }
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(FormulaIdentifier::from(i)), i);
+        }
+    }
+
+    #[test]
+    fn formula_constant_fields() {
+        // exponent -1, mantissa 5 => 0.5
+        let constant = FormulaConstant(0xF005);
+        assert_eq!(constant.exponent(), -1);
+        assert_eq!(constant.mantissa(), 5);
+        // exponent 2, mantissa -40
+        let constant = FormulaConstant(0x2FD8);
+        assert_eq!(constant.exponent(), 2);
+        assert_eq!(constant.mantissa(), -40);
+    }
+
+    #[test]
+    fn request_roundtrip() {
+        let req = ReadScalingDataByIdentifierRequest::new(UdsIdentifier::from(0xF190));
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0xF1, 0x90]);
+        assert_encode_size_agrees(&req);
+        let (decoded, rest) =
+            <ReadScalingDataByIdentifierRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert!(matches!(
+            <ReadScalingDataByIdentifierRequest as Decode>::decode(&[0xF1]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn response_iterates_records() {
+        let wire = [
+            0x01, 0x05, // DID
+            0x01, // unsigned numeric, 1 byte
+            0x95, 0x00, 0xE0, 0x01, 0x00, 0x00, // formula C0 * x + C1, C0 = 0.01, C1 = 0
+            0xA1, 0x0B, // unit/format 0x0B
+            0x22, 0x0F, 0xF0, // bit-mapped without mask, validity mask 0x0FF0
+        ];
+        let (response, rest) =
+            <ReadScalingDataByIdentifierResponse as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(response.identifier, UdsIdentifier::from(0x0105));
+
+        let mut records = response.records();
+        let numeric = records.next().unwrap();
+        assert_eq!(numeric.data_type, ScalingDataType::UnsignedNumeric);
+        assert_eq!(numeric.length, 1);
+        assert_eq!(numeric.extension, ScalingByteExtension::None);
+
+        let formula = records.next().unwrap();
+        assert_eq!(formula.data_type, ScalingDataType::Formula);
+        assert!(matches!(
+            formula.extension,
+            ScalingByteExtension::Formula {
+                formula: FormulaIdentifier::Linear,
+                ..
+            }
+        ));
+        let mut constants = formula.formula_constants().unwrap();
+        assert_eq!(constants.len(), 2);
+        let c0 = constants.next().unwrap();
+        assert_eq!((c0.mantissa(), c0.exponent()), (1, -2));
+
+        let unit = records.next().unwrap();
+        assert_eq!(unit.extension, ScalingByteExtension::UnitFormat(0x0B));
+        assert_eq!(unit.scaling_byte(), 0xA1);
+
+        let bit_mapped = records.next().unwrap();
+        assert_eq!(
+            bit_mapped.extension,
+            ScalingByteExtension::ValidityMask(&[0x0F, 0xF0])
+        );
+        assert!(bit_mapped.formula_constants().is_none());
+        assert!(records.next().is_none());
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn response_rejects_malformed_records() {
+        // DID only, no scaling byte
+        assert!(matches!(
+            <ReadScalingDataByIdentifierResponse as Decode>::decode(&[0x01, 0x05]),
+            Err(Error::InsufficientData(_))
+        ));
+        // formula extension cut short
+        assert!(matches!(
+            <ReadScalingDataByIdentifierResponse as Decode>::decode(&[0x01, 0x05, 0x93, 0x00]),
+            Err(Error::InsufficientData(_))
+        ));
+        // formula constants not a whole number of 2-byte values
+        assert!(matches!(
+            <ReadScalingDataByIdentifierResponse as Decode>::decode(&[
+                0x01, 0x05, 0x92, 0x00, 0x01
+            ]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        // unit/format extension longer than one byte
+        assert!(matches!(
+            <ReadScalingDataByIdentifierResponse as Decode>::decode(&[
+                0x01, 0x05, 0xA2, 0x0B, 0x0C
+            ]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<ReadScalingDataByIdentifierRequest>();
+        assert_impl_eq::<ReadScalingDataByIdentifierResponse<'static>>();
+        assert_impl_eq::<ScalingRecord<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<ReadScalingDataByIdentifierRequest>();
+            assert_impl_serde::<ReadScalingDataByIdentifierResponse<'static>>();
+            assert_impl_serde::<ScalingRecord<'static>>();
+        }
+    }
+}
@@ -1,8 +1,8 @@
 //! `RequestDownload` (0x34) service implementation
 
 use crate::shared::{DataFormatIdentifier, LengthFormatIdentifier, MemoryFormatIdentifier};
-use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
-use automotive_wire_codec::{read_be_uint_into, write_all, write_be_uint, write_u8};
+use crate::{Decode, Encode, Error, NegativeResponseCode};
+use automotive_wire_codec::write_u8;
 
 const REQUEST_DOWNLOAD_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 6] = [
     NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
@@ -42,26 +42,13 @@ impl RequestDownloadRequest {
     ///
     /// # Errors
     /// Returns an error if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
-    #[allow(clippy::cast_possible_truncation)]
     pub fn new(
         data_format_identifier: DataFormatIdentifier,
         memory_address: u64,
         memory_size: u32,
     ) -> Result<Self, Error> {
-        if memory_address > 0xFF_FFFF_FFFF {
-            return Err(Error::InvalidMemoryAddress(memory_address));
-        }
-        // A length of 0 produces an invalid `MemoryFormatIdentifier` (the nibbles
-        // must be >=1 per ISO-14229), so clamp to at least one byte even when the
-        // address or size is 0.
-        let memory_address_length =
-            ((u64::BITS - memory_address.leading_zeros()).div_ceil(8) as u8).max(1);
-        let memory_size_length =
-            ((u32::BITS - memory_size.leading_zeros()).div_ceil(8) as u8).max(1);
-        let address_and_length_format_identifier = MemoryFormatIdentifier {
-            memory_size_length,
-            memory_address_length,
-        };
+        let address_and_length_format_identifier =
+            MemoryFormatIdentifier::for_address_and_size(memory_address, memory_size)?;
         Ok(Self {
             data_format_identifier,
             address_and_length_format_identifier,
@@ -92,23 +79,13 @@ impl Encode for RequestDownloadRequest {
     type Error = crate::Error;
 
     fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
-        let mut written = write_all(
-            writer,
-            &[
-                self.data_format_identifier.into(),
-                self.address_and_length_format_identifier.into(),
-            ],
-        )
-        .map_err(Error::io)?;
-
-        let addr_len = self
-            .address_and_length_format_identifier
-            .memory_address_length as usize;
-        let size_len = self.address_and_length_format_identifier.memory_size_length as usize;
-        written += write_be_uint(writer, u128::from(self.memory_address), addr_len)?;
-        written += write_be_uint(writer, u128::from(self.memory_size), size_len)?;
-
-        Ok(written)
+        let written = write_u8(writer, self.data_format_identifier.into()).map_err(Error::io)?;
+        Ok(written
+            + self.address_and_length_format_identifier.encode_with(
+                writer,
+                self.memory_address,
+                self.memory_size,
+            )?)
     }
 }
 
@@ -116,35 +93,21 @@ impl<'a> Decode<'a> for RequestDownloadRequest {
     type Error = crate::Error;
 
     fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
-        if buf.len() < 2 {
-            return Err(Error::InsufficientData(Incomplete {
-                needed: 2,
-                available: buf.len(),
-            }));
-        }
-        let data_format_identifier = DataFormatIdentifier::from(buf[0]);
-        let memory_identifier = MemoryFormatIdentifier::try_from(buf[1])?;
-        let addr_len = memory_identifier.memory_address_length as usize;
-        let size_len = memory_identifier.memory_size_length as usize;
-        let total = 2 + addr_len + size_len;
-        if buf.len() < total {
-            return Err(Error::InsufficientData(Incomplete {
-                needed: total,
-                available: buf.len(),
-            }));
-        }
-
-        let (memory_address, rest) = read_be_uint_into::<u64>(&buf[2..], addr_len)?;
-        let (memory_size, _rest) = read_be_uint_into::<u32>(rest, size_len)?;
-
+        let (
+            data_format_identifier,
+            address_and_length_format_identifier,
+            memory_address,
+            memory_size,
+            rest,
+        ) = MemoryFormatIdentifier::decode_with_data_format(buf)?;
         Ok((
             Self {
                 data_format_identifier,
-                address_and_length_format_identifier: memory_identifier,
+                address_and_length_format_identifier,
                 memory_address,
                 memory_size,
             },
-            &buf[total..],
+            rest,
         ))
     }
 }
@@ -180,18 +143,7 @@ impl Encode for RequestDownloadResponse<'_> {
     type Error = crate::Error;
 
     fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
-        // The block-length field width is carried in a single nibble, so the slice
-        // can be at most 0x0F bytes long.
-        let nibble = u8::try_from(self.max_number_of_block_length.len())
-            .ok()
-            .filter(|n| *n <= 0x0F)
-            .ok_or(Error::IncorrectMessageLengthOrInvalidFormat)?;
-        let length_format_identifier = LengthFormatIdentifier {
-            max_number_of_block_length: nibble,
-        };
-        let mut written = write_u8(writer, length_format_identifier.into()).map_err(Error::io)?;
-        written += write_all(writer, self.max_number_of_block_length).map_err(Error::io)?;
-        Ok(written)
+        LengthFormatIdentifier::encode_block_length(writer, self.max_number_of_block_length)
     }
 }
 
@@ -199,26 +151,12 @@ impl<'a> Decode<'a> for RequestDownloadResponse<'a> {
     type Error = crate::Error;
 
     fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
-        if buf.is_empty() {
-            return Err(Error::InsufficientData(Incomplete {
-                needed: 1,
-                available: buf.len(),
-            }));
-        }
-        let length_format_identifier = LengthFormatIdentifier::from(buf[0]);
-        let len = length_format_identifier.max_number_of_block_length as usize;
-        let total = 1 + len;
-        if buf.len() < total {
-            return Err(Error::InsufficientData(Incomplete {
-                needed: total,
-                available: buf.len(),
-            }));
-        }
+        let (max_number_of_block_length, rest) = LengthFormatIdentifier::decode_block_length(buf)?;
         Ok((
             Self {
-                max_number_of_block_length: &buf[1..total],
+                max_number_of_block_length,
             },
-            &buf[total..],
+            rest,
         ))
     }
 }
@@ -267,6 +205,31 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn request_roundtrip_maximum_widths() {
+        for (req, wire) in [
+            (
+                RequestDownloadRequest::new(0x00.into(), 0x12, 0xFFFF_FFFF).unwrap(),
+                &[0x00, 0x41, 0x12, 0xFF, 0xFF, 0xFF, 0xFF][..],
+            ),
+            (
+                RequestDownloadRequest::new(0x00.into(), 0x12_3456_7890, 1).unwrap(),
+                &[0x00, 0x15, 0x12, 0x34, 0x56, 0x78, 0x90, 0x01][..],
+            ),
+        ] {
+            let mut buf = [0u8; 8];
+            let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+            assert_eq!(&buf[..written], wire);
+            let (decoded, rest) = <RequestDownloadRequest as Decode>::decode(wire).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(decoded, req);
+        }
+        assert!(matches!(
+            <RequestDownloadRequest as Decode>::decode(&[0x00, 0x11, 0x67]),
+            Err(Error::InsufficientData(i)) if i.needed == 4 && i.available == 3
+        ));
+    }
+
     #[test]
     fn read_memory_identifier() {
         let memory_format_identifier = MemoryFormatIdentifier::try_from(0x23).unwrap();
@@ -0,0 +1,264 @@
+//! `RequestUpload` (0x35) service implementation
+
+use crate::shared::{DataFormatIdentifier, LengthFormatIdentifier, MemoryFormatIdentifier};
+use crate::{Decode, Encode, Error, NegativeResponseCode};
+use automotive_wire_codec::write_u8;
+
+const REQUEST_UPLOAD_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 6] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+    NegativeResponseCode::AuthenticationRequired,
+    NegativeResponseCode::UploadDownloadNotAccepted,
+];
+
+/// A request to the server for it to upload data to the client
+///
+/// A positive response to this request ([`RequestUploadResponse`]) will happen
+/// after the server takes all necessary actions to send the data once the server is ready to send
+///
+/// This is a variable length Request, determined by the `address_and_length_format_identifier` value
+/// See ISO-14229-1:2020, Table H.1 for format information
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RequestUploadRequest {
+    /// compression method (high nibble) and encrypting method (low nibble). 0x00 is no compression or encryption
+    data_format_identifier: DataFormatIdentifier,
+    /// 7-4: length (# of bytes) of `memory_size` param, 3-0: length (# of bytes) of `memory_address` param
+    address_and_length_format_identifier: MemoryFormatIdentifier,
+    /// Starting address of the server memory. The on-wire byte width is derived from this
+    /// value (max 5 bytes), so it is private to keep it in sync with the format identifier.
+    memory_address: u64,
+    /// Size of the data to be uploaded. The on-wire byte width is derived from this value
+    /// (max 4 bytes), so it is private to keep it in sync with the format identifier.
+    memory_size: u32,
+}
+
+impl RequestUploadRequest {
+    /// Create a new `RequestUploadRequest`
+    ///
+    /// # Errors
+    /// Returns an error if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    pub fn new(
+        data_format_identifier: DataFormatIdentifier,
+        memory_address: u64,
+        memory_size: u32,
+    ) -> Result<Self, Error> {
+        let address_and_length_format_identifier =
+            MemoryFormatIdentifier::for_address_and_size(memory_address, memory_size)?;
+        Ok(Self {
+            data_format_identifier,
+            address_and_length_format_identifier,
+            memory_address,
+            memory_size,
+        })
+    }
+
+    /// Compression and encryption method the server should apply to the uploaded data.
+    #[must_use]
+    pub const fn data_format_identifier(&self) -> DataFormatIdentifier {
+        self.data_format_identifier
+    }
+
+    /// Starting address of the server memory.
+    #[must_use]
+    pub const fn memory_address(&self) -> u64 {
+        self.memory_address
+    }
+
+    /// Size of the data to be uploaded.
+    #[must_use]
+    pub const fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &REQUEST_UPLOAD_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for RequestUploadRequest {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let written = write_u8(writer, self.data_format_identifier.into()).map_err(Error::io)?;
+        Ok(written
+            + self.address_and_length_format_identifier.encode_with(
+                writer,
+                self.memory_address,
+                self.memory_size,
+            )?)
+    }
+}
+
+impl<'a> Decode<'a> for RequestUploadRequest {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (
+            data_format_identifier,
+            address_and_length_format_identifier,
+            memory_address,
+            memory_size,
+            rest,
+        ) = MemoryFormatIdentifier::decode_with_data_format(buf)?;
+        Ok((
+            Self {
+                data_format_identifier,
+                address_and_length_format_identifier,
+                memory_address,
+                memory_size,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Zero-alloc response for request upload. Borrows from the caller.
+///
+/// Positive response to a [`RequestUploadRequest`] indicating the server is ready to send data.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RequestUploadResponse<'d> {
+    /// Maximum number of bytes per [`TransferDataResponse`](crate::TransferDataResponse).
+    ///
+    /// The on-wire `lengthFormatIdentifier` nibble is derived from this slice's length
+    /// at encode time, so the declared length can never disagree with the bytes present.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub max_number_of_block_length: &'d [u8],
+}
+
+impl<'d> RequestUploadResponse<'d> {
+    /// Create a new request upload response. The `lengthFormatIdentifier` is derived
+    /// from `max_number_of_block_length` during encoding.
+    #[must_use]
+    pub const fn new(max_number_of_block_length: &'d [u8]) -> Self {
+        Self {
+            max_number_of_block_length,
+        }
+    }
+}
+
+impl Encode for RequestUploadResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        LengthFormatIdentifier::encode_block_length(writer, self.max_number_of_block_length)
+    }
+}
+
+impl<'a> Decode<'a> for RequestUploadResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (max_number_of_block_length, rest) = LengthFormatIdentifier::decode_block_length(buf)?;
+        Ok((
+            Self {
+                max_number_of_block_length,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn simple_request() {
+        let bytes: [u8; 7] = [
+            0x11, // compression method 1, encryption method 1
+            0x14, // 1 byte for memory size, 4 bytes for memory address
+            0x08, 0x00, 0x00, 0x00, // memory address
+            0x80,
+        ];
+        let (req, rest) = <RequestUploadRequest as Decode>::decode(&bytes).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(u8::from(req.data_format_identifier()), 0x11);
+        assert_eq!(req.memory_address(), 0x0800_0000);
+        assert_eq!(req.memory_size(), 0x80);
+
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &bytes);
+    }
+
+    #[test]
+    fn request_new_matches_decoded() {
+        let req = RequestUploadRequest::new(0x00.into(), 0x0800_0000, 0x0002_0000).unwrap();
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(
+            &buf[..written],
+            &[0x00, 0x34, 0x08, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00]
+        );
+        let (decoded, _) = <RequestUploadRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert_eq!(decoded, req);
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn request_roundtrip_maximum_widths() {
+        let req = RequestUploadRequest::new(0x00.into(), 0xFF_FFFF_FFFF, 0xFFFF_FFFF).unwrap();
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..2], &[0x00, 0x45]);
+        assert_eq!(written, 11);
+        let (decoded, rest) = <RequestUploadRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+    }
+
+    #[test]
+    fn bad_request() {
+        let err = <RequestUploadRequest as Decode>::decode(&[0x00, 0x11, 0x67]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 4 && i.available == 3)
+        );
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let block = [0x0F, 0xFA];
+        let response = RequestUploadResponse::new(&block);
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x20, 0x0F, 0xFA]);
+        let (decoded, rest) = <RequestUploadResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn response_rejects_oversized_block_length() {
+        let block = [0u8; 16];
+        let mut buf = [0u8; 32];
+        assert!(matches!(
+            Encode::encode(&RequestUploadResponse::new(&block), &mut buf.as_mut_slice()),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<RequestUploadRequest>();
+        assert_impl_eq::<RequestUploadResponse<'static>>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<RequestUploadRequest>();
+            assert_impl_serde::<RequestUploadResponse<'static>>();
+        }
+    }
+}
@@ -0,0 +1,1097 @@
+//! `ResponseOnEvent` (0x86) service implementation
+use super::step;
+use crate::shared::SuppressablePositiveResponse;
+use crate::{Decode, DtcStatusMask, Encode, Error, NegativeResponseCode, Request, UdsIdentifier};
+use automotive_wire_codec::{
+    read_u8, read_u16_be, read_u32_be, take, write_all, write_u8, write_u16_be, write_u32_be,
+};
+
+const RESPONSE_ON_EVENT_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 4] = [
+    NegativeResponseCode::SubFunctionNotSupported,
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+];
+
+/// `storageState` bit of the `eventType` sub-function byte.
+const STORE_EVENT_BIT: u8 = 0x40;
+
+/// Bits of the `eventType` sub-function byte that carry the [`EventType`].
+const EVENT_TYPE_MASK: u8 = 0x3F;
+
+/// `eventType` sub-function of the `ResponseOnEvent` service (bits 0–5)
+///
+/// See ISO-14229-1:2020, Section 10.11.2.2
+///
+/// *Note*:
+///
+/// Conversions from `u8` to `EventType` are fallible and will return an [`Error`](crate::Error) if
+/// the `storageState` or Suppress Positive Response bit is set.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EventType {
+    /// Stop the server sending responses on event; the set-up events are kept
+    StopResponseOnEvent,
+    /// Respond when the status of a DTC matching the `DTCStatusMask` changes
+    OnDtcStatusChange,
+    /// Respond when the value of a data identifier changes
+    OnChangeOfDataIdentifier,
+    /// Report the events that are currently active
+    ReportActivatedEvents,
+    /// Start the server sending responses on the set-up events
+    StartResponseOnEvent,
+    /// Clear all set-up events
+    ClearResponseOnEvent,
+    /// Respond when a data identifier value compares true against a reference value
+    OnComparisonOfValues,
+    /// Report the most recent DTC whose status changed to match the `DTCStatusMask`
+    ReportMostRecentDtcOnStatusChange,
+    /// Report the DTC record information of a DTC whose status changed to match the
+    /// `DTCStatusMask`
+    ReportDtcRecordInformationOnDtcStatusChange,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    ///
+    /// Construct through [`EventType::try_from`] so the raw byte is
+    /// range-checked and can never collide with the `storageState` or SPRMIB bits.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    #[non_exhaustive]
+    IsoSaeReserved(u8),
+}
+
+impl EventType {
+    /// Returns `true` for the event types that control the already set-up events
+    /// (start, stop, clear, report) rather than setting up a new one.
+    #[must_use]
+    pub const fn is_control(&self) -> bool {
+        matches!(
+            self,
+            Self::StopResponseOnEvent
+                | Self::StartResponseOnEvent
+                | Self::ClearResponseOnEvent
+                | Self::ReportActivatedEvents
+        )
+    }
+}
+
+impl From<EventType> for u8 {
+    fn from(value: EventType) -> Self {
+        match value {
+            EventType::StopResponseOnEvent => 0x00,
+            EventType::OnDtcStatusChange => 0x01,
+            EventType::OnChangeOfDataIdentifier => 0x03,
+            EventType::ReportActivatedEvents => 0x04,
+            EventType::StartResponseOnEvent => 0x05,
+            EventType::ClearResponseOnEvent => 0x06,
+            EventType::OnComparisonOfValues => 0x07,
+            EventType::ReportMostRecentDtcOnStatusChange => 0x08,
+            EventType::ReportDtcRecordInformationOnDtcStatusChange => 0x09,
+            EventType::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl TryFrom<u8> for EventType {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            0x00 => Ok(Self::StopResponseOnEvent),
+            0x01 => Ok(Self::OnDtcStatusChange),
+            0x03 => Ok(Self::OnChangeOfDataIdentifier),
+            0x04 => Ok(Self::ReportActivatedEvents),
+            0x05 => Ok(Self::StartResponseOnEvent),
+            0x06 => Ok(Self::ClearResponseOnEvent),
+            0x07 => Ok(Self::OnComparisonOfValues),
+            0x08 => Ok(Self::ReportMostRecentDtcOnStatusChange),
+            0x09 => Ok(Self::ReportDtcRecordInformationOnDtcStatusChange),
+            0x02 | 0x0A..=0x3F => Ok(Self::IsoSaeReserved(value)),
+            _ => Err(Error::InvalidEventType(value)),
+        }
+    }
+}
+
+/// The `eventType` sub-function byte without its SPRMIB bit: the [`EventType`] plus the
+/// `storageState` bit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct EventTypeAndStorage {
+    store_event: bool,
+    event_type: EventType,
+}
+
+impl From<EventTypeAndStorage> for u8 {
+    fn from(value: EventTypeAndStorage) -> Self {
+        let mut result = u8::from(value.event_type);
+        if value.store_event {
+            result |= STORE_EVENT_BIT;
+        }
+        result
+    }
+}
+
+impl TryFrom<u8> for EventTypeAndStorage {
+    type Error = Error;
+    fn try_from(value: u8) -> Result<Self, Error> {
+        if value > STORE_EVENT_BIT | EVENT_TYPE_MASK {
+            return Err(Error::InvalidEventType(value));
+        }
+        Ok(Self {
+            store_event: value & STORE_EVENT_BIT == STORE_EVENT_BIT,
+            event_type: EventType::try_from(value & EVENT_TYPE_MASK)?,
+        })
+    }
+}
+
+/// `eventWindowTime`: how long the server keeps responding to an event once started
+///
+/// The actual window lengths are defined by the vehicle manufacturer.
+///
+/// See ISO-14229-1:2020, Section 10.11.2.3
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EventWindowTime {
+    /// The event window never closes
+    InfiniteTimeToResponse,
+    /// Short event window
+    ShortEventWindowTime,
+    /// Medium event window
+    MediumEventWindowTime,
+    /// Long event window
+    LongEventWindowTime,
+    /// The event window lasts for the current power cycle
+    PowerWindowTime,
+    /// The event window lasts for the current ignition cycle
+    IgnitionWindowTime,
+    /// The event window closes on a manufacturer-defined trigger
+    ManufacturerTriggerEventWindowTime,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl From<EventWindowTime> for u8 {
+    fn from(value: EventWindowTime) -> Self {
+        match value {
+            EventWindowTime::InfiniteTimeToResponse => 0x02,
+            EventWindowTime::ShortEventWindowTime => 0x03,
+            EventWindowTime::MediumEventWindowTime => 0x04,
+            EventWindowTime::LongEventWindowTime => 0x05,
+            EventWindowTime::PowerWindowTime => 0x06,
+            EventWindowTime::IgnitionWindowTime => 0x07,
+            EventWindowTime::ManufacturerTriggerEventWindowTime => 0x08,
+            EventWindowTime::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for EventWindowTime {
+    fn from(value: u8) -> Self {
+        match value {
+            0x02 => Self::InfiniteTimeToResponse,
+            0x03 => Self::ShortEventWindowTime,
+            0x04 => Self::MediumEventWindowTime,
+            0x05 => Self::LongEventWindowTime,
+            0x06 => Self::PowerWindowTime,
+            0x07 => Self::IgnitionWindowTime,
+            0x08 => Self::ManufacturerTriggerEventWindowTime,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// Comparison applied by an `onComparisonOfValues` event
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ComparisonLogic {
+    /// The measured value is less than the comparison value
+    LessThan,
+    /// The measured value is larger than the comparison value
+    LargerThan,
+    /// The measured value equals the comparison value
+    Equal,
+    /// The measured value differs from the comparison value
+    NotEqual,
+    /// These values are reserved by the ISO 14229-1 Specification.
+    #[cfg_attr(feature = "clap", clap(skip))]
+    IsoSaeReserved(u8),
+}
+
+impl From<ComparisonLogic> for u8 {
+    fn from(value: ComparisonLogic) -> Self {
+        match value {
+            ComparisonLogic::LessThan => 0x01,
+            ComparisonLogic::LargerThan => 0x02,
+            ComparisonLogic::Equal => 0x03,
+            ComparisonLogic::NotEqual => 0x04,
+            ComparisonLogic::IsoSaeReserved(val) => val,
+        }
+    }
+}
+
+impl From<u8> for ComparisonLogic {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => Self::LessThan,
+            0x02 => Self::LargerThan,
+            0x03 => Self::Equal,
+            0x04 => Self::NotEqual,
+            _ => Self::IsoSaeReserved(value),
+        }
+    }
+}
+
+/// `eventTypeRecord` of an `onComparisonOfValues` event (10 bytes on the wire)
+///
+/// The server reads a value out of the record of `data_identifier` (see
+/// [`localization`](Self::localization)) and triggers when it compares true against
+/// `comparison_value`.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ComparisonOfValuesRecord {
+    /// The data identifier whose record holds the measured value.
+    pub data_identifier: UdsIdentifier,
+    /// How the measured value is compared against `comparison_value`.
+    pub comparison_logic: ComparisonLogic,
+    /// The reference value.
+    pub comparison_value: u32,
+    /// Hysteresis in percent of `comparison_value`.
+    pub hysteresis: u8,
+    /// Where the value sits in the data record: bit 15 is set for a signed value,
+    /// bits 14–10 are its length in bits (0 means 32) and bits 9–0 its bit offset.
+    pub localization: u16,
+}
+
+impl ComparisonOfValuesRecord {
+    /// Create a new comparison record.
+    #[must_use]
+    pub const fn new(
+        data_identifier: UdsIdentifier,
+        comparison_logic: ComparisonLogic,
+        comparison_value: u32,
+        hysteresis: u8,
+        localization: u16,
+    ) -> Self {
+        Self {
+            data_identifier,
+            comparison_logic,
+            comparison_value,
+            hysteresis,
+            localization,
+        }
+    }
+
+    /// Whether the measured value is signed.
+    #[must_use]
+    pub const fn is_signed(&self) -> bool {
+        self.localization & 0x8000 != 0
+    }
+
+    /// Length of the measured value in bits (1–32).
+    #[must_use]
+    pub const fn value_length(&self) -> u8 {
+        match ((self.localization >> 10) & 0x1F) as u8 {
+            0 => 32,
+            len => len,
+        }
+    }
+
+    /// Offset of the measured value in bits from the start of the data record.
+    #[must_use]
+    pub const fn bit_offset(&self) -> u16 {
+        self.localization & 0x03FF
+    }
+}
+
+impl Encode for ComparisonOfValuesRecord {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written =
+            write_u16_be(writer, u16::from(self.data_identifier)).map_err(Error::io)?;
+        written += write_u8(writer, u8::from(self.comparison_logic)).map_err(Error::io)?;
+        written += write_u32_be(writer, self.comparison_value).map_err(Error::io)?;
+        written += write_u8(writer, self.hysteresis).map_err(Error::io)?;
+        written += write_u16_be(writer, self.localization).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for ComparisonOfValuesRecord {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (data_identifier, rest) = read_u16_be(buf)?;
+        let (comparison_logic, rest) = read_u8(rest)?;
+        let (comparison_value, rest) = read_u32_be(rest)?;
+        let (hysteresis, rest) = read_u8(rest)?;
+        let (localization, rest) = read_u16_be(rest)?;
+        Ok((
+            Self {
+                data_identifier: UdsIdentifier::from(data_identifier),
+                comparison_logic: ComparisonLogic::from(comparison_logic),
+                comparison_value,
+                hysteresis,
+                localization,
+            },
+            rest,
+        ))
+    }
+}
+
+/// `eventTypeRecord`: the parameters of an event, whose layout depends on the [`EventType`]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EventTypeRecord<'a> {
+    /// No record: start, stop, clear and report requests.
+    None,
+    /// `onDTCStatusChange` and `reportMostRecentDtcOnStatusChange`: the DTC status mask to watch.
+    DtcStatusMask(DtcStatusMask),
+    /// `onChangeOfDataIdentifier`: the data identifier to watch.
+    DataIdentifier(UdsIdentifier),
+    /// `onComparisonOfValues`: the comparison to evaluate.
+    ComparisonOfValues(ComparisonOfValuesRecord),
+    /// Raw record bytes of an event type this crate does not model.
+    Raw(#[cfg_attr(feature = "serde", serde(borrow))] &'a [u8]),
+}
+
+impl Encode for EventTypeRecord<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        match self {
+            Self::None => Ok(0),
+            Self::DtcStatusMask(mask) => mask.encode(writer),
+            Self::DataIdentifier(identifier) => {
+                write_u16_be(writer, u16::from(*identifier)).map_err(Error::io)
+            }
+            Self::ComparisonOfValues(record) => record.encode(writer),
+            Self::Raw(data) => write_all(writer, data).map_err(Error::io),
+        }
+    }
+}
+
+/// One event as set up with `ResponseOnEvent`: its type, window, record and the service the
+/// server runs when it fires
+///
+/// Used by [`ResponseOnEventRequest`], echoed by [`ResponseOnEventResponse`] and yielded by
+/// [`ActivatedEventIter`]. `event_type_record` is expected to match `event_type`; the
+/// constructors on [`ResponseOnEventRequest`] keep them consistent.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EventConfiguration<'a> {
+    /// `storageState`: whether the server keeps the event across power cycles.
+    pub store_event: bool,
+    /// The type of event.
+    pub event_type: EventType,
+    /// `eventWindowTime`; optional on the wire for start, stop, clear and report requests.
+    pub event_window_time: Option<EventWindowTime>,
+    /// The event parameters.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub event_type_record: EventTypeRecord<'a>,
+    /// `serviceToRespondToRecord`: a complete request (service identifier first) the server
+    /// runs when the event fires. Empty if the event type carries none.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub service_to_respond_to: &'a [u8],
+}
+
+impl<'a> EventConfiguration<'a> {
+    /// Create a new event configuration.
+    #[must_use]
+    pub const fn new(
+        store_event: bool,
+        event_type: EventType,
+        event_window_time: Option<EventWindowTime>,
+        event_type_record: EventTypeRecord<'a>,
+        service_to_respond_to: &'a [u8],
+    ) -> Self {
+        Self {
+            store_event,
+            event_type,
+            event_window_time,
+            event_type_record,
+            service_to_respond_to,
+        }
+    }
+
+    /// Decode the `serviceToRespondToRecord` through the [`Request`] decoder.
+    ///
+    /// Returns `Ok(None)` if the event carries no service to respond to.
+    ///
+    /// # Errors
+    /// Returns any error from decoding the embedded request.
+    pub fn service_to_respond_to_request(&self) -> Result<Option<Request<'a>>, Error> {
+        if self.service_to_respond_to.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Request::decode_exact(self.service_to_respond_to)?))
+    }
+
+    fn event_type_and_storage(&self) -> EventTypeAndStorage {
+        EventTypeAndStorage {
+            store_event: self.store_event,
+            event_type: self.event_type,
+        }
+    }
+
+    /// Encode everything after the `eventType` byte.
+    fn encode_parameters(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = 0;
+        if let Some(event_window_time) = self.event_window_time {
+            written += write_u8(writer, u8::from(event_window_time)).map_err(Error::io)?;
+        }
+        written += self.event_type_record.encode(writer)?;
+        written += write_all(writer, self.service_to_respond_to).map_err(Error::io)?;
+        Ok(written)
+    }
+
+    /// Decode everything after the `eventType` byte.
+    ///
+    /// Without `service_len` the `serviceToRespondToRecord` (or raw record) runs to the end
+    /// of `buf`. With it, `service_len` delimits the record so that further events can follow;
+    /// event types whose record length is unknown are then rejected.
+    fn decode_parameters(
+        event_type_and_storage: EventTypeAndStorage,
+        buf: &'a [u8],
+        service_len: Option<&mut dyn FnMut(&'a [u8]) -> Option<usize>>,
+    ) -> Result<(Self, &'a [u8]), Error> {
+        let EventTypeAndStorage {
+            store_event,
+            event_type,
+        } = event_type_and_storage;
+        if event_type.is_control() {
+            if service_len.is_some() {
+                return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+            }
+            let (event_window_time, rest) = match buf.split_first() {
+                Some((&window, rest)) => (Some(EventWindowTime::from(window)), rest),
+                None => (None, buf),
+            };
+            let configuration = Self::new(
+                store_event,
+                event_type,
+                event_window_time,
+                EventTypeRecord::None,
+                &[],
+            );
+            return Ok((configuration, rest));
+        }
+
+        let (event_window_time, rest) = read_u8(buf)?;
+        let (event_type_record, rest) = match event_type {
+            EventType::OnDtcStatusChange | EventType::ReportMostRecentDtcOnStatusChange => {
+                let (mask, rest) = DtcStatusMask::decode(rest)?;
+                (EventTypeRecord::DtcStatusMask(mask), rest)
+            }
+            EventType::OnChangeOfDataIdentifier => {
+                let (identifier, rest) = read_u16_be(rest)?;
+                (
+                    EventTypeRecord::DataIdentifier(UdsIdentifier::from(identifier)),
+                    rest,
+                )
+            }
+            EventType::OnComparisonOfValues => {
+                let (record, rest) = ComparisonOfValuesRecord::decode(rest)?;
+                (EventTypeRecord::ComparisonOfValues(record), rest)
+            }
+            _ if service_len.is_some() => {
+                return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+            }
+            _ => (EventTypeRecord::Raw(rest), &[][..]),
+        };
+        let (service_to_respond_to, rest) = match service_len {
+            Some(service_len) => {
+                let len = service_len(rest).ok_or(Error::IncorrectMessageLengthOrInvalidFormat)?;
+                take(rest, len)?
+            }
+            None => (rest, &[][..]),
+        };
+        Ok((
+            Self::new(
+                store_event,
+                event_type,
+                Some(EventWindowTime::from(event_window_time)),
+                event_type_record,
+                service_to_respond_to,
+            ),
+            rest,
+        ))
+    }
+}
+
+/// Request to set up, start, stop, clear or report events the server responds to.
+///
+/// See ISO-14229-1:2020, Section 10.11.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ResponseOnEventRequest<'a> {
+    /// Whether a positive response should be suppressed.
+    pub suppress_positive_response: bool,
+    /// The event to set up, or the control action to take.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub event: EventConfiguration<'a>,
+}
+
+impl<'a> ResponseOnEventRequest<'a> {
+    /// Create a new request from an [`EventConfiguration`].
+    #[must_use]
+    pub const fn new(suppress_positive_response: bool, event: EventConfiguration<'a>) -> Self {
+        Self {
+            suppress_positive_response,
+            event,
+        }
+    }
+
+    const fn control(suppress_positive_response: bool, event_type: EventType) -> Self {
+        Self::new(
+            suppress_positive_response,
+            EventConfiguration::new(false, event_type, None, EventTypeRecord::None, &[]),
+        )
+    }
+
+    /// Stop responding to the set-up events.
+    #[must_use]
+    pub const fn stop(suppress_positive_response: bool) -> Self {
+        Self::control(suppress_positive_response, EventType::StopResponseOnEvent)
+    }
+
+    /// Start responding to the set-up events.
+    #[must_use]
+    pub const fn start(suppress_positive_response: bool) -> Self {
+        Self::control(suppress_positive_response, EventType::StartResponseOnEvent)
+    }
+
+    /// Clear all set-up events.
+    #[must_use]
+    pub const fn clear(suppress_positive_response: bool) -> Self {
+        Self::control(suppress_positive_response, EventType::ClearResponseOnEvent)
+    }
+
+    /// Ask the server which events are active.
+    #[must_use]
+    pub const fn report_activated_events(suppress_positive_response: bool) -> Self {
+        Self::control(suppress_positive_response, EventType::ReportActivatedEvents)
+    }
+
+    /// Run `service_to_respond_to` whenever the status of a DTC matching `mask` changes.
+    #[must_use]
+    pub const fn on_dtc_status_change(
+        suppress_positive_response: bool,
+        store_event: bool,
+        event_window_time: EventWindowTime,
+        mask: DtcStatusMask,
+        service_to_respond_to: &'a [u8],
+    ) -> Self {
+        Self::new(
+            suppress_positive_response,
+            EventConfiguration::new(
+                store_event,
+                EventType::OnDtcStatusChange,
+                Some(event_window_time),
+                EventTypeRecord::DtcStatusMask(mask),
+                service_to_respond_to,
+            ),
+        )
+    }
+
+    /// Run `service_to_respond_to` whenever the value of `identifier` changes.
+    #[must_use]
+    pub const fn on_change_of_data_identifier(
+        suppress_positive_response: bool,
+        store_event: bool,
+        event_window_time: EventWindowTime,
+        identifier: UdsIdentifier,
+        service_to_respond_to: &'a [u8],
+    ) -> Self {
+        Self::new(
+            suppress_positive_response,
+            EventConfiguration::new(
+                store_event,
+                EventType::OnChangeOfDataIdentifier,
+                Some(event_window_time),
+                EventTypeRecord::DataIdentifier(identifier),
+                service_to_respond_to,
+            ),
+        )
+    }
+
+    /// Run `service_to_respond_to` whenever the comparison in `record` holds.
+    #[must_use]
+    pub const fn on_comparison_of_values(
+        suppress_positive_response: bool,
+        store_event: bool,
+        event_window_time: EventWindowTime,
+        record: ComparisonOfValuesRecord,
+        service_to_respond_to: &'a [u8],
+    ) -> Self {
+        Self::new(
+            suppress_positive_response,
+            EventConfiguration::new(
+                store_event,
+                EventType::OnComparisonOfValues,
+                Some(event_window_time),
+                EventTypeRecord::ComparisonOfValues(record),
+                service_to_respond_to,
+            ),
+        )
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &RESPONSE_ON_EVENT_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for ResponseOnEventRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let sub_function = SuppressablePositiveResponse::new(
+            self.suppress_positive_response,
+            self.event.event_type_and_storage(),
+        );
+        let written = write_u8(writer, u8::from(sub_function)).map_err(Error::io)?;
+        Ok(written + self.event.encode_parameters(writer)?)
+    }
+}
+
+impl<'a> Decode<'a> for ResponseOnEventRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (sub_function, rest) = read_u8(buf)?;
+        let sub_function =
+            SuppressablePositiveResponse::<EventTypeAndStorage>::try_from(sub_function)?;
+        let (event, rest) =
+            EventConfiguration::decode_parameters(sub_function.value(), rest, None)?;
+        Ok((
+            Self {
+                suppress_positive_response: sub_function.suppress_positive_response(),
+                event,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Iterator over the events listed in a `reportActivatedEvents` response.
+///
+/// The `serviceToRespondToRecord` of each event is not length-prefixed, so the caller supplies
+/// `service_len`: given the bytes starting at that record, it returns the record's length
+/// (or `None` if it cannot tell). Events whose `eventTypeRecord` length this crate does not
+/// know yield an error. The iterator stops after the first error.
+#[derive(Clone)]
+pub struct ActivatedEventIter<'a, F> {
+    remaining: &'a [u8],
+    service_len: F,
+}
+
+// Not derived: `service_len` is usually a closure, which has no `Debug` impl.
+impl<F> core::fmt::Debug for ActivatedEventIter<'_, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ActivatedEventIter")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, F: FnMut(&'a [u8]) -> Option<usize>> Iterator for ActivatedEventIter<'a, F> {
+    type Item = Result<EventConfiguration<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        step(&mut self.remaining, |buf| {
+            let (sub_function, rest) = read_u8(buf)?;
+            EventConfiguration::decode_parameters(
+                EventTypeAndStorage::try_from(sub_function)?,
+                rest,
+                Some(&mut self.service_len),
+            )
+        })
+    }
+}
+
+/// Positive response to `ResponseOnEvent`
+///
+/// The response to an event firing is not a `ResponseOnEvent` response: it is the positive
+/// response of the `serviceToRespondToRecord`.
+///
+/// See ISO-14229-1:2020, Section 10.11.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResponseOnEventResponse<'a> {
+    /// Response to setting up an event, or to start, stop or clear: echoes the request.
+    Event {
+        /// `numberOfIdentifiedEvents`
+        number_of_identified_events: u8,
+        /// The event echoed from the request.
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        event: EventConfiguration<'a>,
+    },
+    /// Response to `reportActivatedEvents`.
+    ActivatedEvents {
+        /// `numberOfActivatedEvents`
+        number_of_activated_events: u8,
+        /// The raw activated event records; see
+        /// [`activated_events`](ResponseOnEventResponse::activated_events).
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        records: &'a [u8],
+    },
+}
+
+impl<'a> ResponseOnEventResponse<'a> {
+    /// Iterate the events of a `reportActivatedEvents` response.
+    ///
+    /// `None` for any other response. See [`ActivatedEventIter`] for `service_len`.
+    pub fn activated_events<F>(&self, service_len: F) -> Option<ActivatedEventIter<'a, F>>
+    where
+        F: FnMut(&'a [u8]) -> Option<usize>,
+    {
+        match *self {
+            Self::ActivatedEvents { records, .. } => Some(ActivatedEventIter {
+                remaining: records,
+                service_len,
+            }),
+            Self::Event { .. } => None,
+        }
+    }
+}
+
+impl Encode for ResponseOnEventResponse<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        match self {
+            Self::Event {
+                number_of_identified_events,
+                event,
+            } => {
+                let written = write_all(
+                    writer,
+                    &[
+                        u8::from(event.event_type_and_storage()),
+                        *number_of_identified_events,
+                    ],
+                )
+                .map_err(Error::io)?;
+                Ok(written + event.encode_parameters(writer)?)
+            }
+            Self::ActivatedEvents {
+                number_of_activated_events,
+                records,
+            } => {
+                let written = write_all(
+                    writer,
+                    &[
+                        u8::from(EventType::ReportActivatedEvents),
+                        *number_of_activated_events,
+                    ],
+                )
+                .map_err(Error::io)?;
+                Ok(written + write_all(writer, records).map_err(Error::io)?)
+            }
+        }
+    }
+}
+
+impl<'a> Decode<'a> for ResponseOnEventResponse<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (sub_function, rest) = read_u8(buf)?;
+        let event_type_and_storage = EventTypeAndStorage::try_from(sub_function)?;
+        let (number_of_events, rest) = read_u8(rest)?;
+        if event_type_and_storage.event_type == EventType::ReportActivatedEvents {
+            if event_type_and_storage.store_event {
+                return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+            }
+            return Ok((
+                Self::ActivatedEvents {
+                    number_of_activated_events: number_of_events,
+                    records: rest,
+                },
+                &[],
+            ));
+        }
+        let (event, rest) =
+            EventConfiguration::decode_parameters(event_type_and_storage, rest, None)?;
+        Ok((
+            Self::Event {
+                number_of_identified_events: number_of_events,
+                event,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    /// `serviceToRespondToRecord` lengths for the services used in these tests.
+    fn service_len(buf: &[u8]) -> Option<usize> {
+        match buf.first()? {
+            0x19 => Some(2),
+            0x22 => Some(3),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn event_type_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            match EventType::try_from(i) {
+                Ok(value) => assert_eq!(u8::from(value), i),
+                Err(Error::InvalidEventType(value)) => {
+                    assert_eq!(value, i);
+                    assert!(i >= 0x40);
+                }
+                _ => panic!("Invalid error type"),
+            }
+        }
+    }
+
+    #[test]
+    fn window_time_and_comparison_logic_round_trip_all_values() {
+        for i in 0..=u8::MAX {
+            assert_eq!(u8::from(EventWindowTime::from(i)), i);
+            assert_eq!(u8::from(ComparisonLogic::from(i)), i);
+        }
+    }
+
+    #[test]
+    fn on_dtc_status_change_roundtrip() {
+        // storeEvent + onDTCStatusChange, infinite window, mask 0x01, respond with 0x19 0x0E
+        let wire = [0x41, 0x02, 0x01, 0x19, 0x0E];
+        let req = ResponseOnEventRequest::on_dtc_status_change(
+            false,
+            true,
+            EventWindowTime::InfiniteTimeToResponse,
+            DtcStatusMask::TestFailed,
+            &wire[3..],
+        );
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) = <ResponseOnEventRequest as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        let service = decoded
+            .event
+            .service_to_respond_to_request()
+            .unwrap()
+            .unwrap();
+        assert!(matches!(service, Request::ReadDtcInfo(_)));
+    }
+
+    #[test]
+    fn on_change_of_data_identifier_roundtrip() {
+        let wire = [0x83, 0x03, 0x01, 0x0D, 0x22, 0x01, 0x0D];
+        let (decoded, _) = <ResponseOnEventRequest as Decode>::decode(&wire).unwrap();
+        assert!(decoded.suppress_positive_response);
+        assert!(!decoded.event.store_event);
+        assert_eq!(
+            decoded.event.event_type_record,
+            EventTypeRecord::DataIdentifier(UdsIdentifier::from(0x010D))
+        );
+        assert!(matches!(
+            decoded.event.service_to_respond_to_request().unwrap(),
+            Some(Request::ReadDataByIdentifier(_))
+        ));
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&decoded, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn on_comparison_of_values_roundtrip() {
+        let record = ComparisonOfValuesRecord::new(
+            UdsIdentifier::from(0x0105),
+            ComparisonLogic::LargerThan,
+            0x0000_0064,
+            0x0A,
+            0x8000 | (8 << 10) | 16,
+        );
+        assert!(record.is_signed());
+        assert_eq!(record.value_length(), 8);
+        assert_eq!(record.bit_offset(), 16);
+        let service = [0x22, 0x01, 0x05];
+        let req = ResponseOnEventRequest::on_comparison_of_values(
+            false,
+            false,
+            EventWindowTime::IgnitionWindowTime,
+            record,
+            &service,
+        );
+        let wire = [
+            0x07, 0x07, // onComparisonOfValues, ignition window
+            0x01, 0x05, 0x02, 0x00, 0x00, 0x00, 0x64, 0x0A, 0xA0, 0x10, // record
+            0x22, 0x01, 0x05, // serviceToRespondToRecord
+        ];
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+        let (decoded, _) = <ResponseOnEventRequest as Decode>::decode(&wire).unwrap();
+        assert_eq!(decoded, req);
+    }
+
+    #[test]
+    fn control_requests_with_and_without_window() {
+        let mut buf = [0u8; 4];
+        let written = Encode::encode(
+            &ResponseOnEventRequest::start(false),
+            &mut buf.as_mut_slice(),
+        )
+        .unwrap();
+        assert_eq!(&buf[..written], &[0x05]);
+
+        let (decoded, rest) = <ResponseOnEventRequest as Decode>::decode(&[0x80, 0x02]).unwrap();
+        assert!(rest.is_empty());
+        assert!(decoded.suppress_positive_response);
+        assert_eq!(decoded.event.event_type, EventType::StopResponseOnEvent);
+        assert_eq!(
+            decoded.event.event_window_time,
+            Some(EventWindowTime::InfiniteTimeToResponse)
+        );
+        assert!(
+            decoded
+                .event
+                .service_to_respond_to_request()
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn request_short_buffers() {
+        assert!(matches!(
+            <ResponseOnEventRequest as Decode>::decode(&[]),
+            Err(Error::InsufficientData(_))
+        ));
+        assert!(matches!(
+            <ResponseOnEventRequest as Decode>::decode(&[0x01]),
+            Err(Error::InsufficientData(_))
+        ));
+        assert!(matches!(
+            <ResponseOnEventRequest as Decode>::decode(&[0x07, 0x02, 0x01, 0x05]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn reserved_event_type_keeps_raw_record() {
+        let wire = [0x0A, 0x02, 0xAA, 0xBB];
+        let (decoded, _) = <ResponseOnEventRequest as Decode>::decode(&wire).unwrap();
+        assert_eq!(decoded.event.event_type, EventType::IsoSaeReserved(0x0A));
+        assert_eq!(
+            decoded.event.event_type_record,
+            EventTypeRecord::Raw(&[0xAA, 0xBB])
+        );
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&decoded, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn response_echoes_event() {
+        let wire = [0x01, 0x01, 0x02, 0x01, 0x19, 0x0E];
+        let (response, rest) = <ResponseOnEventResponse as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        let ResponseOnEventResponse::Event {
+            number_of_identified_events,
+            event,
+        } = response
+        else {
+            panic!("expected an event response");
+        };
+        assert_eq!(number_of_identified_events, 1);
+        assert_eq!(event.event_type, EventType::OnDtcStatusChange);
+        assert!(response.activated_events(service_len).is_none());
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn report_activated_events_iterates_events() {
+        let wire = [
+            0x04, 0x02, // reportActivatedEvents, two events
+            0x41, 0x02, 0x01, 0x19, 0x0E, // stored onDTCStatusChange
+            0x03, 0x03, 0x01, 0x0D, 0x22, 0x01, 0x0D, // onChangeOfDataIdentifier
+        ];
+        let (response, _) = <ResponseOnEventResponse as Decode>::decode(&wire).unwrap();
+        let events: [_; 2] = core::array::from_fn({
+            let mut iter = response.activated_events(service_len).unwrap();
+            move |_| iter.next().unwrap().unwrap()
+        });
+        assert!(events[0].store_event);
+        assert_eq!(
+            events[0].event_type_record,
+            EventTypeRecord::DtcStatusMask(DtcStatusMask::TestFailed)
+        );
+        assert_eq!(events[1].event_type, EventType::OnChangeOfDataIdentifier);
+        assert_eq!(events[1].service_to_respond_to, &[0x22, 0x01, 0x0D]);
+        assert!(
+            response
+                .activated_events(service_len)
+                .unwrap()
+                .all(|event| event.is_ok())
+        );
+
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn activated_event_with_unknown_service_errors_once() {
+        let (response, _) =
+            <ResponseOnEventResponse as Decode>::decode(&[0x04, 0x01, 0x01, 0x02, 0x01, 0x31])
+                .unwrap();
+        let mut iter = response.activated_events(service_len).unwrap();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<ResponseOnEventRequest<'static>>();
+        assert_impl_eq::<ResponseOnEventResponse<'static>>();
+        assert_impl_eq::<EventConfiguration<'static>>();
+        assert_impl_eq::<ComparisonOfValuesRecord>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<ResponseOnEventRequest<'static>>();
+            assert_impl_serde::<ResponseOnEventResponse<'static>>();
+            assert_impl_serde::<EventConfiguration<'static>>();
+            assert_impl_serde::<ComparisonOfValuesRecord>();
+        }
+    }
+}
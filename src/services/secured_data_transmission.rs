@@ -0,0 +1,414 @@
+//! `SecuredDataTransmission` (0x84) service implementation
+use crate::{Decode, Encode, Error, NegativeResponseCode, Request, Response};
+use automotive_wire_codec::{ensure_len, read_u8, read_u16_be, write_all, write_u8, write_u16_be};
+
+/// `incorrectMessageLengthOrInvalidFormat` plus the `0x38..=0x4F` range reserved for the
+/// extended data link security document (ISO 15764).
+const SECURED_DATA_TRANSMISSION_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 25] = {
+    let mut codes = [NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat; 25];
+    let mut index = 1;
+    let mut value = 0x38;
+    while index < codes.len() {
+        codes[index] = NegativeResponseCode::ExtendedDataLinkSecurityReserved(value);
+        index += 1;
+        value += 1;
+    }
+    codes
+};
+
+/// `Administrative Parameter` of a secured data transmission (ISO 15764), a 16-bit field of
+/// flags describing how the message is protected
+///
+/// See ISO-14229-1:2020, Section 10.9.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AdministrativeParameter(pub u16);
+
+impl AdministrativeParameter {
+    /// Bit 0: the message is a request.
+    pub const REQUEST: u16 = 0x0001;
+    /// Bit 3: a pre-established key is used.
+    pub const PRE_ESTABLISHED_KEY: u16 = 0x0008;
+    /// Bit 4: the message is encrypted.
+    pub const ENCRYPTED: u16 = 0x0010;
+    /// Bit 5: the message is signed.
+    pub const SIGNED: u16 = 0x0020;
+    /// Bit 6: a signature on the response is requested.
+    pub const SIGNED_RESPONSE_REQUESTED: u16 = 0x0040;
+
+    /// Whether the message is a request.
+    #[must_use]
+    pub const fn is_request(self) -> bool {
+        self.0 & Self::REQUEST != 0
+    }
+
+    /// Whether a pre-established key is used.
+    #[must_use]
+    pub const fn is_pre_established_key(self) -> bool {
+        self.0 & Self::PRE_ESTABLISHED_KEY != 0
+    }
+
+    /// Whether the message is encrypted.
+    ///
+    /// The internal service identifier and its parameters are then ciphertext and must be
+    /// decrypted before they can be decoded.
+    #[must_use]
+    pub const fn is_encrypted(self) -> bool {
+        self.0 & Self::ENCRYPTED != 0
+    }
+
+    /// Whether the message is signed.
+    #[must_use]
+    pub const fn is_signed(self) -> bool {
+        self.0 & Self::SIGNED != 0
+    }
+
+    /// Whether a signature on the response is requested.
+    #[must_use]
+    pub const fn is_signed_response_requested(self) -> bool {
+        self.0 & Self::SIGNED_RESPONSE_REQUESTED != 0
+    }
+}
+
+impl From<u16> for AdministrativeParameter {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl From<AdministrativeParameter> for u16 {
+    fn from(value: AdministrativeParameter) -> Self {
+        value.0
+    }
+}
+
+/// The ISO 15764 record shared by secured requests and responses.
+///
+/// Everything from the internal service identifier up to the signature is kept as one
+/// `payload` slice so it can be handed to the [`Request`]/[`Response`] decoders unchanged.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct SecuredRecord<'a> {
+    administrative_parameter: AdministrativeParameter,
+    signature_encryption_calculation: u8,
+    anti_replay_counter: u16,
+    payload: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl Encode for SecuredRecord<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        if self.payload.is_empty() {
+            return Err(Error::IncorrectMessageLengthOrInvalidFormat);
+        }
+        let signature_length = u16::try_from(self.signature.len())
+            .map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+        let mut written =
+            write_u16_be(writer, u16::from(self.administrative_parameter)).map_err(Error::io)?;
+        written += write_u8(writer, self.signature_encryption_calculation).map_err(Error::io)?;
+        written += write_u16_be(writer, signature_length).map_err(Error::io)?;
+        written += write_u16_be(writer, self.anti_replay_counter).map_err(Error::io)?;
+        written += write_all(writer, self.payload).map_err(Error::io)?;
+        written += write_all(writer, self.signature).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for SecuredRecord<'a> {
+    type Error = crate::Error;
+
+    /// The record has no end marker: the signature is taken from the end of `buf` and the
+    /// payload is everything in between, so this consumes the whole buffer.
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (administrative_parameter, rest) = read_u16_be(buf)?;
+        let (signature_encryption_calculation, rest) = read_u8(rest)?;
+        let (signature_length, rest) = read_u16_be(rest)?;
+        let (anti_replay_counter, rest) = read_u16_be(rest)?;
+        // At least the internal service identifier precedes the signature.
+        ensure_len(rest, usize::from(signature_length) + 1)?;
+        let (payload, signature) = rest.split_at(rest.len() - usize::from(signature_length));
+        Ok((
+            Self {
+                administrative_parameter: AdministrativeParameter(administrative_parameter),
+                signature_encryption_calculation,
+                anti_replay_counter,
+                payload,
+                signature,
+            },
+            &[],
+        ))
+    }
+}
+
+/// Request wrapping another diagnostic request in ISO 15764 secured framing.
+///
+/// [`payload`](Self::payload) holds the internal service identifier followed by its service-specific
+/// parameters; unless the message is [encrypted](AdministrativeParameter::is_encrypted) it
+/// decodes as a plain request through [`inner_request`](Self::inner_request).
+///
+/// See ISO-14229-1:2020, Section 10.9.2.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SecuredDataTransmissionRequest<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    record: SecuredRecord<'a>,
+}
+
+/// Positive response wrapping another diagnostic response in ISO 15764 secured framing.
+///
+/// [`payload`](Self::payload) holds the internal response service identifier followed by its
+/// response-specific parameters; unless the message is
+/// [encrypted](AdministrativeParameter::is_encrypted) it decodes as a plain response through
+/// [`inner_response`](Self::inner_response).
+///
+/// See ISO-14229-1:2020, Section 10.9.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SecuredDataTransmissionResponse<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    record: SecuredRecord<'a>,
+}
+
+/// Accessors shared by the secured request and response.
+macro_rules! secured_record_accessors {
+    ($ty:ident) => {
+        impl<'a> $ty<'a> {
+            /// Create a new secured message.
+            ///
+            /// `payload` is the internal service identifier followed by its parameters (the
+            /// encoded inner message without framing); `signature` is the signature/MAC, empty
+            /// if the message is not signed.
+            #[must_use]
+            pub const fn new(
+                administrative_parameter: AdministrativeParameter,
+                signature_encryption_calculation: u8,
+                anti_replay_counter: u16,
+                payload: &'a [u8],
+                signature: &'a [u8],
+            ) -> Self {
+                Self {
+                    record: SecuredRecord {
+                        administrative_parameter,
+                        signature_encryption_calculation,
+                        anti_replay_counter,
+                        payload,
+                        signature,
+                    },
+                }
+            }
+
+            /// The `Administrative Parameter` flags.
+            #[must_use]
+            pub const fn administrative_parameter(&self) -> AdministrativeParameter {
+                self.record.administrative_parameter
+            }
+
+            /// The `Signature/Encryption Calculation`: which manufacturer-defined algorithm
+            /// protects the message.
+            #[must_use]
+            pub const fn signature_encryption_calculation(&self) -> u8 {
+                self.record.signature_encryption_calculation
+            }
+
+            /// The `Anti-replay Counter`.
+            #[must_use]
+            pub const fn anti_replay_counter(&self) -> u16 {
+                self.record.anti_replay_counter
+            }
+
+            /// The internal service identifier followed by its parameters.
+            #[must_use]
+            pub const fn payload(&self) -> &'a [u8] {
+                self.record.payload
+            }
+
+            /// The internal service identifier, `None` only if built with an empty payload.
+            #[must_use]
+            pub const fn internal_service_id(&self) -> Option<u8> {
+                match self.record.payload {
+                    [sid, ..] => Some(*sid),
+                    [] => None,
+                }
+            }
+
+            /// The service-specific parameters following the internal service identifier.
+            #[must_use]
+            pub fn service_specific_parameters(&self) -> &'a [u8] {
+                self.record.payload.get(1..).unwrap_or_default()
+            }
+
+            /// The signature/MAC, empty if the message is not signed.
+            #[must_use]
+            pub const fn signature(&self) -> &'a [u8] {
+                self.record.signature
+            }
+        }
+
+        impl Encode for $ty<'_> {
+            type Error = crate::Error;
+
+            fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+                self.record.encode(writer)
+            }
+        }
+
+        impl<'a> Decode<'a> for $ty<'a> {
+            type Error = crate::Error;
+
+            fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+                let (record, rest) = SecuredRecord::decode(buf)?;
+                Ok((Self { record }, rest))
+            }
+        }
+    };
+}
+
+secured_record_accessors!(SecuredDataTransmissionRequest);
+secured_record_accessors!(SecuredDataTransmissionResponse);
+
+impl<'a> SecuredDataTransmissionRequest<'a> {
+    /// Decode the wrapped request.
+    ///
+    /// # Errors
+    /// Returns any error from decoding the inner request; an encrypted payload will usually
+    /// fail to decode or decode to nonsense.
+    pub fn inner_request(&self) -> Result<Request<'a>, Error> {
+        Request::decode_exact(self.record.payload)
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &SECURED_DATA_TRANSMISSION_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl<'a> SecuredDataTransmissionResponse<'a> {
+    /// Decode the wrapped response (which may be a negative response of the inner service).
+    ///
+    /// # Errors
+    /// Returns any error from decoding the inner response; an encrypted payload will usually
+    /// fail to decode or decode to nonsense.
+    pub fn inner_response(&self) -> Result<Response<'a>, Error> {
+        Response::decode_exact(self.record.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn administrative_parameter_flags() {
+        let parameter = AdministrativeParameter(
+            AdministrativeParameter::REQUEST
+                | AdministrativeParameter::SIGNED
+                | AdministrativeParameter::SIGNED_RESPONSE_REQUESTED,
+        );
+        assert!(parameter.is_request());
+        assert!(parameter.is_signed());
+        assert!(parameter.is_signed_response_requested());
+        assert!(!parameter.is_encrypted());
+        assert!(!parameter.is_pre_established_key());
+        assert_eq!(u16::from(parameter), 0x0061);
+    }
+
+    #[test]
+    fn signed_request_roundtrip() {
+        // Wraps ReadDataByIdentifier 0xF190, signed with a 4-byte MAC.
+        let wire = [
+            0x00, 0x61, // administrative parameter
+            0x01, // signature/encryption calculation
+            0x00, 0x04, // signature length
+            0x01, 0x24, // anti-replay counter
+            0x22, 0xF1, 0x90, // internal request
+            0xDE, 0xAD, 0xBE, 0xEF, // signature/MAC
+        ];
+        let req = SecuredDataTransmissionRequest::new(
+            AdministrativeParameter(0x0061),
+            0x01,
+            0x0124,
+            &wire[7..10],
+            &wire[10..],
+        );
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+        assert_encode_size_agrees(&req);
+
+        let (decoded, rest) = <SecuredDataTransmissionRequest as Decode>::decode(&wire).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.internal_service_id(), Some(0x22));
+        assert_eq!(decoded.service_specific_parameters(), &[0xF1, 0x90]);
+        assert_eq!(decoded.signature(), &[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert!(matches!(
+            decoded.inner_request(),
+            Ok(Request::ReadDataByIdentifier(_))
+        ));
+    }
+
+    #[test]
+    fn unsigned_response_wraps_negative_response() {
+        let wire = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x7F, 0x22, 0x31];
+        let (decoded, _) = <SecuredDataTransmissionResponse as Decode>::decode(&wire).unwrap();
+        assert!(decoded.signature().is_empty());
+        assert_eq!(decoded.anti_replay_counter(), 0x0007);
+        assert!(matches!(
+            decoded.inner_response(),
+            Ok(Response::NegativeResponse(_))
+        ));
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&decoded, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+    }
+
+    #[test]
+    fn short_buffers() {
+        assert!(matches!(
+            <SecuredDataTransmissionRequest as Decode>::decode(&[0x00, 0x01, 0x00]),
+            Err(Error::InsufficientData(_))
+        ));
+        // Signature length leaves no room for the internal service identifier.
+        assert!(matches!(
+            <SecuredDataTransmissionRequest as Decode>::decode(&[
+                0x00, 0x21, 0x00, 0x00, 0x02, 0x00, 0x01, 0xAA, 0xBB
+            ]),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn encode_rejects_empty_payload() {
+        let req = SecuredDataTransmissionRequest::new(AdministrativeParameter(1), 0, 0, &[], &[]);
+        let mut buf = [0u8; 16];
+        assert!(matches!(
+            Encode::encode(&req, &mut buf.as_mut_slice()),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<SecuredDataTransmissionRequest<'static>>();
+        assert_impl_eq::<SecuredDataTransmissionResponse<'static>>();
+        assert_impl_eq::<AdministrativeParameter>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<SecuredDataTransmissionRequest<'static>>();
+            assert_impl_serde::<SecuredDataTransmissionResponse<'static>>();
+            assert_impl_serde::<AdministrativeParameter>();
+        }
+    }
+}
@@ -1,5 +1,5 @@
 //! `WriteDataByIdentifier` (0x2E) service implementation
-use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use crate::{Decode, DidCodec, Encode, Error, Incomplete, NegativeResponseCode};
 use automotive_wire_codec::{write_all, write_u16_be};
 
 const WRITE_DID_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 5] = [
@@ -36,6 +36,20 @@ impl<'d> WriteDataByIdentifierRequest<'d> {
         Self { identifier, data }
     }
 
+    /// Encode a typed data record into `buf` and build a request writing it to
+    /// `T::IDENTIFIER`.
+    ///
+    /// # Errors
+    /// Returns the record's encode error, including an IO error if `buf` is too small.
+    pub fn encode_record<'r, T>(record: &T, buf: &'d mut [u8]) -> Result<Self, Error>
+    where
+        T: DidCodec<'r> + Encode<Error = Error>,
+    {
+        let len = record.encode(&mut &mut buf[..])?;
+        let buf: &'d [u8] = buf;
+        Ok(Self::new(T::IDENTIFIER, &buf[..len]))
+    }
+
     /// Get the allowed [`NegativeResponseCode`] variants for this request.
     #[must_use]
     pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
@@ -0,0 +1,312 @@
+//! `WriteMemoryByAddress` (0x3D) service implementation
+
+use crate::shared::MemoryFormatIdentifier;
+use crate::{Decode, Encode, Error, Incomplete, NegativeResponseCode};
+use automotive_wire_codec::write_all;
+
+const WRITE_MEMORY_BY_ADDRESS_NEGATIVE_RESPONSE_CODES: [NegativeResponseCode; 6] = [
+    NegativeResponseCode::IncorrectMessageLengthOrInvalidFormat,
+    NegativeResponseCode::ConditionsNotCorrect,
+    NegativeResponseCode::RequestOutOfRange,
+    NegativeResponseCode::SecurityAccessDenied,
+    NegativeResponseCode::AuthenticationRequired,
+    NegativeResponseCode::GeneralProgrammingFailure,
+];
+
+/// A request to the server to write `data` into its memory starting at `memory_address`
+///
+/// A positive response to this request ([`WriteMemoryByAddressResponse`]) echoes the
+/// address and size that were written.
+///
+/// This is a variable length Request, determined by the `address_and_length_format_identifier` value
+/// See ISO-14229-1:2020, Section 11.8.2.1 and Table H.1 for format information
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct WriteMemoryByAddressRequest<'d> {
+    /// 7-4: length (# of bytes) of `memory_size` param, 3-0: length (# of bytes) of `memory_address` param
+    address_and_length_format_identifier: MemoryFormatIdentifier,
+    /// Starting address of the server memory. The on-wire byte width is derived from this
+    /// value (max 5 bytes), so it is private to keep it in sync with the format identifier.
+    memory_address: u64,
+    /// Number of bytes to write. Always equal to `data.len()`; private so the two cannot disagree.
+    memory_size: u32,
+    /// The data record written to the server memory.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    data: &'d [u8],
+}
+
+impl<'d> WriteMemoryByAddressRequest<'d> {
+    /// Create a request to write `data` at `memory_address`. The `memorySize` parameter is
+    /// taken from `data.len()`.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidMemoryAddress`] if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    /// - [`Error::IncorrectMessageLengthOrInvalidFormat`] if `data` is longer than `u32::MAX` bytes.
+    pub fn new(memory_address: u64, data: &'d [u8]) -> Result<Self, Error> {
+        let memory_size =
+            u32::try_from(data.len()).map_err(|_| Error::IncorrectMessageLengthOrInvalidFormat)?;
+        Ok(Self {
+            address_and_length_format_identifier: MemoryFormatIdentifier::for_address_and_size(
+                memory_address,
+                memory_size,
+            )?,
+            memory_address,
+            memory_size,
+            data,
+        })
+    }
+
+    /// Starting address of the server memory.
+    #[must_use]
+    pub const fn memory_address(&self) -> u64 {
+        self.memory_address
+    }
+
+    /// Number of bytes to write.
+    #[must_use]
+    pub const fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+
+    /// The data record written to the server memory.
+    #[must_use]
+    pub const fn data(&self) -> &'d [u8] {
+        self.data
+    }
+
+    /// Build the positive response for this request, echoing its address and size with the
+    /// same `addressAndLengthFormatIdentifier`.
+    #[must_use]
+    pub const fn positive_response(&self) -> WriteMemoryByAddressResponse {
+        WriteMemoryByAddressResponse {
+            address_and_length_format_identifier: self.address_and_length_format_identifier,
+            memory_address: self.memory_address,
+            memory_size: self.memory_size,
+        }
+    }
+
+    /// Get the allowed [`NegativeResponseCode`] variants for this request
+    #[must_use]
+    pub fn allowed_nack_codes() -> &'static [NegativeResponseCode] {
+        &WRITE_MEMORY_BY_ADDRESS_NEGATIVE_RESPONSE_CODES
+    }
+}
+
+impl Encode for WriteMemoryByAddressRequest<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = self.address_and_length_format_identifier.encode_with(
+            writer,
+            self.memory_address,
+            self.memory_size,
+        )?;
+        written += write_all(writer, self.data).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+impl<'a> Decode<'a> for WriteMemoryByAddressRequest<'a> {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (address_and_length_format_identifier, memory_address, memory_size, rest) =
+            MemoryFormatIdentifier::decode_with(buf)?;
+        // The data record is exactly `memory_size` bytes; anything beyond it is left for
+        // the caller (and rejected as trailing bytes by `decode_exact`).
+        // A 4-byte `memorySize` can exceed `usize` headroom on 32-bit targets.
+        let size = memory_size as usize;
+        if rest.len() < size {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: (buf.len() - rest.len()).saturating_add(size),
+                available: buf.len(),
+            }));
+        }
+        Ok((
+            Self {
+                address_and_length_format_identifier,
+                memory_address,
+                memory_size,
+                data: &rest[..size],
+            },
+            &rest[size..],
+        ))
+    }
+}
+
+/// Positive response to a [`WriteMemoryByAddressRequest`]: echoes the address and size written.
+///
+/// See ISO-14229-1:2020, Section 11.8.3.1
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct WriteMemoryByAddressResponse {
+    /// 7-4: length (# of bytes) of `memory_size` param, 3-0: length (# of bytes) of `memory_address` param
+    address_and_length_format_identifier: MemoryFormatIdentifier,
+    /// Echo of the starting address that was written.
+    memory_address: u64,
+    /// Echo of the number of bytes that were written.
+    memory_size: u32,
+}
+
+impl WriteMemoryByAddressResponse {
+    /// Create a new `WriteMemoryByAddressResponse`
+    ///
+    /// Prefer [`WriteMemoryByAddressRequest::positive_response`] when answering a decoded
+    /// request, since it also echoes the request's field widths.
+    ///
+    /// # Errors
+    /// Returns an error if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    pub fn new(memory_address: u64, memory_size: u32) -> Result<Self, Error> {
+        Ok(Self {
+            address_and_length_format_identifier: MemoryFormatIdentifier::for_address_and_size(
+                memory_address,
+                memory_size,
+            )?,
+            memory_address,
+            memory_size,
+        })
+    }
+
+    /// Echo of the starting address that was written.
+    #[must_use]
+    pub const fn memory_address(&self) -> u64 {
+        self.memory_address
+    }
+
+    /// Echo of the number of bytes that were written.
+    #[must_use]
+    pub const fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+}
+
+impl Encode for WriteMemoryByAddressResponse {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        self.address_and_length_format_identifier.encode_with(
+            writer,
+            self.memory_address,
+            self.memory_size,
+        )
+    }
+}
+
+impl<'a> Decode<'a> for WriteMemoryByAddressResponse {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (address_and_length_format_identifier, memory_address, memory_size, rest) =
+            MemoryFormatIdentifier::decode_with(buf)?;
+        Ok((
+            Self {
+                address_and_length_format_identifier,
+                memory_address,
+                memory_size,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_encode_size_agrees;
+
+    #[test]
+    fn request_roundtrip() {
+        let data = [0x00, 0x8C, 0x00];
+        let req = WriteMemoryByAddressRequest::new(0x2048_1390, &data).unwrap();
+        assert_eq!(req.memory_size(), 3);
+        let mut buf = [0u8; 16];
+        let written = Encode::encode(&req, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(
+            &buf[..written],
+            &[0x14, 0x20, 0x48, 0x13, 0x90, 0x03, 0x00, 0x8C, 0x00]
+        );
+        let (decoded, rest) =
+            <WriteMemoryByAddressRequest as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, req);
+        assert_eq!(decoded.data(), &data);
+        assert_encode_size_agrees(&req);
+    }
+
+    #[test]
+    fn request_rejects_data_shorter_than_memory_size() {
+        // memorySize says 3 bytes, only 2 follow
+        let err = <WriteMemoryByAddressRequest as Decode>::decode(&[0x11, 0x40, 0x03, 0xAA, 0xBB]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 6 && i.available == 5)
+        );
+    }
+
+    #[test]
+    fn request_rejects_maximum_memory_size_without_overflow() {
+        let err = <WriteMemoryByAddressRequest as Decode>::decode(&[
+            0x41, 0x40, 0xFF, 0xFF, 0xFF, 0xFF, 0xAA,
+        ]);
+        assert!(matches!(err, Err(Error::InsufficientData(i)) if i.available == 7));
+    }
+
+    #[test]
+    fn request_leaves_bytes_beyond_memory_size() {
+        let (decoded, rest) =
+            <WriteMemoryByAddressRequest as Decode>::decode(&[0x11, 0x40, 0x01, 0xAA, 0xBB])
+                .unwrap();
+        assert_eq!(decoded.data(), &[0xAA]);
+        assert_eq!(rest, &[0xBB]);
+    }
+
+    #[test]
+    fn positive_response_echoes_request_widths() {
+        // A 2-byte address field carrying a 1-byte value must be echoed at 2 bytes.
+        let wire = [0x12, 0x00, 0x40, 0x01, 0xAA];
+        let (req, _) = <WriteMemoryByAddressRequest as Decode>::decode(&wire).unwrap();
+        let resp = req.positive_response();
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&resp, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire[..4]);
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let response = WriteMemoryByAddressResponse::new(0x2048_1390, 3).unwrap();
+        let mut buf = [0u8; 8];
+        let written = Encode::encode(&response, &mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &[0x14, 0x20, 0x48, 0x13, 0x90, 0x03]);
+        let (decoded, rest) =
+            <WriteMemoryByAddressResponse as Decode>::decode(&buf[..written]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded, response);
+        assert_eq!(decoded.memory_address(), 0x2048_1390);
+        assert_eq!(decoded.memory_size(), 3);
+        assert_encode_size_agrees(&response);
+    }
+
+    #[test]
+    fn response_rejects_short_buffer() {
+        let err = <WriteMemoryByAddressResponse as Decode>::decode(&[0x14, 0x20, 0x48]);
+        assert!(
+            matches!(err, Err(Error::InsufficientData(i)) if i.needed == 6 && i.available == 3)
+        );
+    }
+
+    #[test]
+    fn derive_contract() {
+        use crate::test_util::assert_impl_eq;
+        assert_impl_eq::<WriteMemoryByAddressRequest<'static>>();
+        assert_impl_eq::<WriteMemoryByAddressResponse>();
+        #[cfg(feature = "serde")]
+        {
+            use crate::test_util::assert_impl_serde;
+            assert_impl_serde::<WriteMemoryByAddressRequest<'static>>();
+            assert_impl_serde::<WriteMemoryByAddressResponse>();
+        }
+    }
+}
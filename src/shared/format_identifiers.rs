@@ -1,4 +1,5 @@
-use crate::Error;
+use crate::{Error, Incomplete};
+use automotive_wire_codec::{read_be_uint_into, read_u8, write_all, write_be_uint, write_u8};
 
 const LOW_NIBBLE_MASK: u8 = 0b0000_1111;
 const HIGH_NIBBLE_MASK: u8 = 0b1111_0000;
@@ -28,6 +29,132 @@ pub(crate) struct MemoryFormatIdentifier {
     pub memory_address_length: u8,
 }
 
+impl MemoryFormatIdentifier {
+    /// Derive the minimal on-wire widths needed to carry `memory_address` and `memory_size`.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidMemoryAddress`] if `memory_address` exceeds 5 bytes (> `0xFF_FFFF_FFFF`).
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn for_address_and_size(
+        memory_address: u64,
+        memory_size: u32,
+    ) -> Result<Self, Error> {
+        if memory_address > 0xFF_FFFF_FFFF {
+            return Err(Error::InvalidMemoryAddress(memory_address));
+        }
+        // A length of 0 produces an invalid `MemoryFormatIdentifier` (the nibbles
+        // must be >=1 per ISO-14229), so clamp to at least one byte even when the
+        // address or size is 0.
+        let memory_address_length =
+            ((u64::BITS - memory_address.leading_zeros()).div_ceil(8) as u8).max(1);
+        let memory_size_length =
+            ((u32::BITS - memory_size.leading_zeros()).div_ceil(8) as u8).max(1);
+        Ok(Self {
+            memory_size_length,
+            memory_address_length,
+        })
+    }
+
+    /// Total number of bytes taken by the `memoryAddress` and `memorySize` fields on the wire.
+    pub(crate) const fn parameter_len(self) -> usize {
+        self.memory_address_length as usize + self.memory_size_length as usize
+    }
+
+    /// Write this identifier followed by `memory_address` and `memory_size` at the widths it declares.
+    pub(crate) fn encode_with(
+        self,
+        writer: &mut impl embedded_io::Write,
+        memory_address: u64,
+        memory_size: u32,
+    ) -> Result<usize, Error> {
+        let written = write_u8(writer, self.into()).map_err(Error::io)?;
+        Ok(written + self.encode_parameters(writer, memory_address, memory_size)?)
+    }
+
+    /// Write `memory_address` and `memory_size` at the widths this identifier declares,
+    /// without the identifier byte itself.
+    pub(crate) fn encode_parameters(
+        self,
+        writer: &mut impl embedded_io::Write,
+        memory_address: u64,
+        memory_size: u32,
+    ) -> Result<usize, Error> {
+        let mut written = write_be_uint(
+            writer,
+            u128::from(memory_address),
+            self.memory_address_length as usize,
+        )?;
+        written += write_be_uint(
+            writer,
+            u128::from(memory_size),
+            self.memory_size_length as usize,
+        )?;
+        Ok(written)
+    }
+
+    /// Read an identifier and the `memoryAddress`/`memorySize` fields it describes.
+    ///
+    /// Returns the identifier, the address, the size and the unconsumed remainder of `buf`.
+    pub(crate) fn decode_with(buf: &[u8]) -> Result<(Self, u64, u32, &[u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let format = Self::try_from(buf[0])?;
+        let total = 1 + format.parameter_len();
+        if buf.len() < total {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: total,
+                available: buf.len(),
+            }));
+        }
+        let (memory_address, memory_size, rest) = format.decode_parameters(&buf[1..])?;
+        Ok((format, memory_address, memory_size, rest))
+    }
+
+    /// Read a `dataFormatIdentifier` followed by an identifier and the
+    /// `memoryAddress`/`memorySize` fields it describes, as in `RequestDownload` and
+    /// `RequestUpload` requests.
+    ///
+    /// Returns the data format, the identifier, the address, the size and the unconsumed
+    /// remainder of `buf`. Lengths in errors count the data format byte.
+    pub(crate) fn decode_with_data_format(
+        buf: &[u8],
+    ) -> Result<(DataFormatIdentifier, Self, u64, u32, &[u8]), Error> {
+        let (data_format_identifier, rest) = read_u8(buf)?;
+        let (format, memory_address, memory_size, rest) =
+            Self::decode_with(rest).map_err(|e| match e {
+                Error::InsufficientData(Incomplete { needed, .. }) => {
+                    Error::InsufficientData(Incomplete {
+                        needed: needed + 1,
+                        available: buf.len(),
+                    })
+                }
+                e => e,
+            })?;
+        Ok((
+            DataFormatIdentifier::from(data_format_identifier),
+            format,
+            memory_address,
+            memory_size,
+            rest,
+        ))
+    }
+
+    /// Read the `memoryAddress`/`memorySize` fields this identifier describes, without a
+    /// leading identifier byte.
+    ///
+    /// Returns the address, the size and the unconsumed remainder of `buf`.
+    pub(crate) fn decode_parameters(self, buf: &[u8]) -> Result<(u64, u32, &[u8]), Error> {
+        let (memory_address, rest) =
+            read_be_uint_into::<u64>(buf, self.memory_address_length as usize)?;
+        let (memory_size, rest) = read_be_uint_into::<u32>(rest, self.memory_size_length as usize)?;
+        Ok((memory_address, memory_size, rest))
+    }
+}
+
 impl TryFrom<u8> for MemoryFormatIdentifier {
     type Error = Error;
     // NRC::RequestOutOfRange if address_and_length_format_identifier is not valid
@@ -36,11 +163,11 @@ impl TryFrom<u8> for MemoryFormatIdentifier {
         let memory_address_length = value & MEMORY_ADDRESS_NIBBLE_MASK;
 
         match memory_size_length {
-            1..4 => (),
+            1..=4 => (),
             _ => return Err(Error::IncorrectMessageLengthOrInvalidFormat),
         }
         match memory_address_length {
-            1..5 => (),
+            1..=5 => (),
             _ => return Err(Error::IncorrectMessageLengthOrInvalidFormat),
         }
         Ok(Self {
@@ -72,6 +199,58 @@ pub(crate) struct LengthFormatIdentifier {
     pub max_number_of_block_length: u8,
 }
 
+impl LengthFormatIdentifier {
+    /// Build the identifier for a `maxNumberOfBlockLength` field of `len` bytes.
+    ///
+    /// # Errors
+    /// Returns [`Error::IncorrectMessageLengthOrInvalidFormat`] if `len` does not fit in a nibble.
+    pub(crate) fn for_block_length(len: usize) -> Result<Self, Error> {
+        // The block-length field width is carried in a single nibble, so the slice
+        // can be at most 0x0F bytes long.
+        u8::try_from(len)
+            .ok()
+            .filter(|n| *n <= 0x0F)
+            .map(|max_number_of_block_length| Self {
+                max_number_of_block_length,
+            })
+            .ok_or(Error::IncorrectMessageLengthOrInvalidFormat)
+    }
+
+    /// Write the identifier for `max_number_of_block_length` followed by the field itself.
+    ///
+    /// # Errors
+    /// Returns [`Error::IncorrectMessageLengthOrInvalidFormat`] if the field is longer than
+    /// `0x0F` bytes.
+    pub(crate) fn encode_block_length(
+        writer: &mut impl embedded_io::Write,
+        max_number_of_block_length: &[u8],
+    ) -> Result<usize, Error> {
+        let format = Self::for_block_length(max_number_of_block_length.len())?;
+        let written = write_u8(writer, format.into()).map_err(Error::io)?;
+        Ok(written + write_all(writer, max_number_of_block_length).map_err(Error::io)?)
+    }
+
+    /// Read an identifier and the `maxNumberOfBlockLength` field it describes.
+    ///
+    /// Returns the field and the unconsumed remainder of `buf`.
+    pub(crate) fn decode_block_length(buf: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+        if buf.is_empty() {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: 1,
+                available: buf.len(),
+            }));
+        }
+        let total = 1 + Self::from(buf[0]).max_number_of_block_length as usize;
+        if buf.len() < total {
+            return Err(Error::InsufficientData(Incomplete {
+                needed: total,
+                available: buf.len(),
+            }));
+        }
+        Ok((&buf[1..total], &buf[total..]))
+    }
+}
+
 impl From<u8> for LengthFormatIdentifier {
     fn from(value: u8) -> Self {
         Self {
@@ -159,6 +338,22 @@ mod tests {
         assert_eq!(u8::from(memory_format_identifier), 0x23);
     }
 
+    #[test]
+    fn memory_format_identifier_maximum_widths() {
+        let memory_format_identifier = MemoryFormatIdentifier::try_from(0x45).unwrap();
+        assert_eq!(memory_format_identifier.memory_size_length, 4);
+        assert_eq!(memory_format_identifier.memory_address_length, 5);
+
+        assert!(matches!(
+            MemoryFormatIdentifier::try_from(0x51),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+        assert!(matches!(
+            MemoryFormatIdentifier::try_from(0x16),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
     #[test]
     fn failed_memory_format_identifier() {
         let memory_format_identifier = MemoryFormatIdentifier::try_from(0x00);
@@ -217,8 +412,8 @@ mod tests {
 
             #[test]
             fn prop_memory_format_identifier_roundtrip(
-                size_len in 1u8..=3,
-                addr_len in 1u8..=4,
+                size_len in 1u8..=4,
+                addr_len in 1u8..=5,
             ) {
                 let byte = (size_len << 4) | addr_len;
                 let mfi = MemoryFormatIdentifier::try_from(byte).unwrap();
@@ -0,0 +1,664 @@
+//! Typed data records for the ISO 14229-1 identification DIDs (`0xF180`–`0xF19E`)
+//!
+//! Each type implements [`DidCodec`], so it can be read from a
+//! [`ReadDataByIdentifierResponse`](crate::ReadDataByIdentifierResponse) with
+//! [`decode_record`](crate::ReadDataByIdentifierResponse::decode_record) and written with
+//! [`WriteDataByIdentifierRequest::encode_record`](crate::WriteDataByIdentifierRequest::encode_record).
+//! Identification DIDs whose content is server specific, such as part numbers and fingerprints,
+//! are left raw; read them with
+//! [`find_record`](crate::ReadDataByIdentifierResponse::find_record).
+use core::{fmt, str::FromStr};
+
+use crate::{Decode, DiagnosticSessionType, DidCodec, Encode, Error};
+use automotive_wire_codec::{read_u8, take, write_all, write_u8};
+
+/// Number of characters in a VIN.
+const VIN_LEN: usize = 17;
+
+/// Vehicle Identification Number (`0xF190`)
+///
+/// Validated on construction, deserialization included: 17 ASCII characters from `0-9` and
+/// `A-Z`, excluding `I`, `O` and `Q` (ISO 3779).
+///
+/// ```
+/// use uds_protocol::Vin;
+/// let vin: Vin = "1M8GDM9AXKP042788".parse().unwrap();
+/// assert_eq!(vin.as_str(), "1M8GDM9AXKP042788");
+/// assert!("1M8GDM9AXKP04278O".parse::<Vin>().is_err());
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "[u8; VIN_LEN]"))]
+// Deserialization goes through `Vin::new` via `try_from`, which upholds `as_str`'s invariant.
+#[cfg_attr(feature = "serde", allow(clippy::unsafe_derive_deserialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Vin([u8; VIN_LEN]);
+
+impl Vin {
+    /// Create a VIN from its 17 ASCII bytes.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidVin`] if `bytes` is not 17 valid VIN characters.
+    pub fn new(bytes: &[u8]) -> Result<Self, Error> {
+        let vin: [u8; VIN_LEN] = bytes.try_into().map_err(|_| Error::InvalidVin)?;
+        if !vin
+            .iter()
+            .all(|c| (c.is_ascii_digit() || c.is_ascii_uppercase()) && !b"IOQ".contains(c))
+        {
+            return Err(Error::InvalidVin);
+        }
+        Ok(Self(vin))
+    }
+
+    /// The VIN as a string slice.
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        // SAFETY: every constructor, deserialization included, goes through `new`, which only
+        // accepts ASCII digits and uppercase letters, so the bytes are valid UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+
+    /// The VIN as its 17 ASCII bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; VIN_LEN] {
+        &self.0
+    }
+}
+
+impl TryFrom<[u8; VIN_LEN]> for Vin {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; VIN_LEN]) -> Result<Self, Error> {
+        Self::new(&bytes)
+    }
+}
+
+impl FromStr for Vin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(s.as_bytes())
+    }
+}
+
+impl fmt::Display for Vin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Encode for Vin {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        write_all(writer, &self.0).map_err(Error::io)
+    }
+}
+
+impl<'a> Decode<'a> for Vin {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (vin, rest) = take(buf, VIN_LEN)?;
+        Ok((Self::new(vin)?, rest))
+    }
+}
+
+impl DidCodec<'_> for Vin {
+    const IDENTIFIER: u16 = 0xF190;
+
+    fn from_record(record: &[u8]) -> Result<Self, Error> {
+        Self::decode_exact(record)
+    }
+}
+
+/// Wire encoding of an [`IdentificationDate`]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DateEncoding {
+    /// 3 bytes, one BCD byte each for `YY`, `MM` and `DD`
+    Bcd,
+    /// 6 ASCII digits, `YYMMDD`
+    Ascii,
+}
+
+/// A `YYMMDD` date as used by the identification date DIDs
+///
+/// The encoding is only known from the record length, 3 bytes for BCD and 6 for ASCII, so a
+/// date is decoded from a whole record with [`from_record`](Self::from_record) rather than
+/// [`Decode`]. The encoding is kept, so a decoded date re-encodes unchanged.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "IdentificationDateFields"))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdentificationDate {
+    year: u8,
+    month: u8,
+    day: u8,
+    encoding: DateEncoding,
+}
+
+/// Unvalidated [`IdentificationDate`] fields, so deserialization goes through
+/// [`IdentificationDate::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct IdentificationDateFields {
+    year: u8,
+    month: u8,
+    day: u8,
+    encoding: DateEncoding,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<IdentificationDateFields> for IdentificationDate {
+    type Error = Error;
+
+    fn try_from(fields: IdentificationDateFields) -> Result<Self, Error> {
+        Self::new(fields.year, fields.month, fields.day, fields.encoding)
+    }
+}
+
+impl IdentificationDate {
+    /// Create a date from a two-digit year, month and day.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidDate`] if `year` exceeds 99, `month` is not 1–12 or `day` is
+    /// not 1–31.
+    pub fn new(year: u8, month: u8, day: u8, encoding: DateEncoding) -> Result<Self, Error> {
+        if year > 99 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(Error::InvalidDate);
+        }
+        Ok(Self {
+            year,
+            month,
+            day,
+            encoding,
+        })
+    }
+
+    /// Two-digit year, 0–99.
+    #[must_use]
+    pub const fn year(&self) -> u8 {
+        self.year
+    }
+
+    /// Month, 1–12.
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month, 1–31.
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The wire encoding of this date.
+    #[must_use]
+    pub const fn encoding(&self) -> DateEncoding {
+        self.encoding
+    }
+
+    /// Decode a whole date record, choosing the encoding by its length.
+    ///
+    /// # Errors
+    /// Returns [`Error::IncorrectMessageLengthOrInvalidFormat`] if `record` is not 3 or 6
+    /// bytes long, and [`Error::InvalidDate`] if it does not hold a valid `YYMMDD` date.
+    pub fn from_record(record: &[u8]) -> Result<Self, Error> {
+        let mut fields = [0u8; 3];
+        let encoding = match record.len() {
+            3 => {
+                for (field, &b) in fields.iter_mut().zip(record) {
+                    let (hi, lo) = (b >> 4, b & 0x0F);
+                    if hi > 9 || lo > 9 {
+                        return Err(Error::InvalidDate);
+                    }
+                    *field = hi * 10 + lo;
+                }
+                DateEncoding::Bcd
+            }
+            6 => {
+                for (field, pair) in fields.iter_mut().zip(record.chunks_exact(2)) {
+                    if !pair.iter().all(u8::is_ascii_digit) {
+                        return Err(Error::InvalidDate);
+                    }
+                    *field = (pair[0] - b'0') * 10 + (pair[1] - b'0');
+                }
+                DateEncoding::Ascii
+            }
+            _ => return Err(Error::IncorrectMessageLengthOrInvalidFormat),
+        };
+        let [year, month, day] = fields;
+        Self::new(year, month, day, encoding)
+    }
+}
+
+impl fmt::Display for IdentificationDate {
+    /// Render as `YYMMDD`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Encode for IdentificationDate {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let fields = [self.year, self.month, self.day];
+        match self.encoding {
+            DateEncoding::Bcd => {
+                write_all(writer, &fields.map(|v| ((v / 10) << 4) | (v % 10))).map_err(Error::io)
+            }
+            DateEncoding::Ascii => {
+                let mut digits = [0u8; 6];
+                for (pair, v) in digits.chunks_exact_mut(2).zip(fields) {
+                    pair[0] = b'0' + v / 10;
+                    pair[1] = b'0' + v % 10;
+                }
+                write_all(writer, &digits).map_err(Error::io)
+            }
+        }
+    }
+}
+
+macro_rules! identification_date {
+    ($name:ident, $did:literal, $doc:literal) => {
+        #[doc = $doc]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $name(pub IdentificationDate);
+
+        impl Encode for $name {
+            type Error = crate::Error;
+
+            fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+                self.0.encode(writer)
+            }
+        }
+
+        impl DidCodec<'_> for $name {
+            const IDENTIFIER: u16 = $did;
+
+            fn from_record(record: &[u8]) -> Result<Self, Error> {
+                IdentificationDate::from_record(record).map(Self)
+            }
+        }
+    };
+}
+
+identification_date!(
+    EcuManufacturingDate,
+    0xF18B,
+    "ECU manufacturing date (`0xF18B`)"
+);
+identification_date!(
+    ProgrammingDate,
+    0xF199,
+    "Date the ECU was last programmed (`0xF199`)"
+);
+identification_date!(CalibrationDate, 0xF19B, "Calibration date (`0xF19B`)");
+identification_date!(
+    EcuInstallationDate,
+    0xF19D,
+    "ECU installation date (`0xF19D`)"
+);
+
+/// Active diagnostic session (`0xF186`)
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ActiveDiagnosticSession(pub DiagnosticSessionType);
+
+impl Encode for ActiveDiagnosticSession {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        write_u8(writer, u8::from(self.0)).map_err(Error::io)
+    }
+}
+
+impl<'a> Decode<'a> for ActiveDiagnosticSession {
+    type Error = crate::Error;
+
+    fn decode(buf: &'a [u8]) -> Result<(Self, &'a [u8]), Error> {
+        let (session, rest) = read_u8(buf)?;
+        Ok((Self(DiagnosticSessionType::try_from(session)?), rest))
+    }
+}
+
+impl DidCodec<'_> for ActiveDiagnosticSession {
+    const IDENTIFIER: u16 = 0xF186;
+
+    fn from_record(record: &[u8]) -> Result<Self, Error> {
+        Self::decode_exact(record)
+    }
+}
+
+/// A software identification record: a module count followed by one identification per module
+///
+/// The length of each module's identification is server specific and may differ between
+/// modules, so the identifications are kept as one raw slice; split them with
+/// [`entries`](Self::entries) or [`entries_with`](Self::entries_with). For the same reason
+/// the list ends with its record and is decoded with [`from_record`](Self::from_record)
+/// rather than [`Decode`].
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SoftwareIdentificationList<'a> {
+    number_of_modules: u8,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    raw_entries: &'a [u8],
+}
+
+impl<'a> SoftwareIdentificationList<'a> {
+    /// Create a list from the module count and the concatenated module identifications.
+    #[must_use]
+    pub const fn new(number_of_modules: u8, raw_entries: &'a [u8]) -> Self {
+        Self {
+            number_of_modules,
+            raw_entries,
+        }
+    }
+
+    /// Decode a whole record; everything after the count byte is taken as module entries.
+    ///
+    /// # Errors
+    /// Returns [`Error::InsufficientData`] if `record` is empty.
+    pub fn from_record(record: &'a [u8]) -> Result<Self, Error> {
+        let (number_of_modules, raw_entries) = read_u8(record)?;
+        Ok(Self::new(number_of_modules, raw_entries))
+    }
+
+    /// The number of modules.
+    #[must_use]
+    pub const fn number_of_modules(&self) -> u8 {
+        self.number_of_modules
+    }
+
+    /// The concatenated module identifications.
+    #[must_use]
+    pub const fn raw_entries(&self) -> &'a [u8] {
+        self.raw_entries
+    }
+
+    /// Iterate the identification of each module, where every module's identification is
+    /// `entry_len` bytes long.
+    ///
+    /// See [`entries_with`](Self::entries_with) for the errors yielded.
+    pub fn entries(&self, entry_len: usize) -> impl Iterator<Item = Result<&'a [u8], Error>> {
+        self.entries_with(move |_| Some(entry_len))
+    }
+
+    /// Iterate the identification of each module, where `module_len` returns the length of
+    /// the identification of the module at a given index.
+    ///
+    /// Yields [`Error::IncorrectMessageLengthOrInvalidFormat`] if `module_len` returns `None`,
+    /// if an identification runs past the end of the record, or if bytes are left over after
+    /// the last module. The iterator stops after the first error.
+    pub fn entries_with<F: FnMut(u8) -> Option<usize>>(
+        &self,
+        mut module_len: F,
+    ) -> impl Iterator<Item = Result<&'a [u8], Error>> {
+        let mut remaining = self.raw_entries;
+        let mut modules = 0..self.number_of_modules;
+        let mut failed = false;
+        core::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let entry = match modules.next() {
+                Some(index) => module_len(index)
+                    .and_then(|len| take(remaining, len).ok())
+                    .map(|(entry, rest)| {
+                        remaining = rest;
+                        entry
+                    }),
+                None if remaining.is_empty() => return None,
+                None => None,
+            };
+            failed = entry.is_none();
+            Some(entry.ok_or(Error::IncorrectMessageLengthOrInvalidFormat))
+        })
+    }
+}
+
+impl Encode for SoftwareIdentificationList<'_> {
+    type Error = crate::Error;
+
+    fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+        let mut written = write_u8(writer, self.number_of_modules).map_err(Error::io)?;
+        written += write_all(writer, self.raw_entries).map_err(Error::io)?;
+        Ok(written)
+    }
+}
+
+macro_rules! software_identification {
+    ($name:ident, $did:literal, $doc:literal) => {
+        #[doc = $doc]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $name<'a>(
+            #[cfg_attr(feature = "serde", serde(borrow))] pub SoftwareIdentificationList<'a>,
+        );
+
+        impl Encode for $name<'_> {
+            type Error = crate::Error;
+
+            fn encode(&self, writer: &mut impl embedded_io::Write) -> Result<usize, Error> {
+                self.0.encode(writer)
+            }
+        }
+
+        impl<'a> DidCodec<'a> for $name<'a> {
+            const IDENTIFIER: u16 = $did;
+
+            fn from_record(record: &'a [u8]) -> Result<Self, Error> {
+                SoftwareIdentificationList::from_record(record).map(Self)
+            }
+        }
+    };
+}
+
+software_identification!(
+    BootSoftwareIdentification,
+    0xF180,
+    "Boot software identification of each module (`0xF180`)"
+);
+software_identification!(
+    ApplicationSoftwareIdentification,
+    0xF181,
+    "Application software identification of each module (`0xF181`)"
+);
+software_identification!(
+    ApplicationDataIdentification,
+    0xF182,
+    "Application data identification of each module (`0xF182`)"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadDataByIdentifierResponse, WriteDataByIdentifierRequest};
+    use alloc::string::ToString;
+
+    #[test]
+    fn vin_validates_characters_and_length() {
+        assert!(Vin::new(b"WVWZZZ1JZXW000001").is_ok());
+        assert!(matches!(
+            Vin::new(b"WVWZZZ1JZXW00000"),
+            Err(Error::InvalidVin)
+        ));
+        assert!(matches!(
+            Vin::new(b"WVWZZZ1JZXW00000I"),
+            Err(Error::InvalidVin)
+        ));
+        assert!(matches!(
+            Vin::new(b"wvwzzz1jzxw000001"),
+            Err(Error::InvalidVin)
+        ));
+        assert!(matches!(Vin::new(&[0xFF; 17]), Err(Error::InvalidVin)));
+        assert!(matches!(Vin::try_from([0xFF; 17]), Err(Error::InvalidVin)));
+        assert_eq!(
+            Vin::try_from(*b"WVWZZZ1JZXW000001").unwrap().as_str(),
+            "WVWZZZ1JZXW000001"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_dates_are_validated() {
+        let fields = |year, month| IdentificationDateFields {
+            year,
+            month,
+            day: 1,
+            encoding: DateEncoding::Bcd,
+        };
+        assert!(IdentificationDate::try_from(fields(24, 3)).is_ok());
+        assert!(matches!(
+            IdentificationDate::try_from(fields(100, 3)),
+            Err(Error::InvalidDate)
+        ));
+        assert!(matches!(
+            IdentificationDate::try_from(fields(24, 13)),
+            Err(Error::InvalidDate)
+        ));
+    }
+
+    #[test]
+    fn dates_round_trip_in_both_encodings() {
+        let bcd = IdentificationDate::from_record(&[0x24, 0x03, 0x15]).unwrap();
+        assert_eq!((bcd.year(), bcd.month(), bcd.day()), (24, 3, 15));
+        assert_eq!(bcd.encoding(), DateEncoding::Bcd);
+        assert_eq!(bcd.to_string(), "240315");
+        let mut buf = [0u8; 6];
+        let n = bcd.encode(&mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..n], &[0x24, 0x03, 0x15]);
+
+        let ascii = IdentificationDate::from_record(b"991231").unwrap();
+        assert_eq!((ascii.year(), ascii.month(), ascii.day()), (99, 12, 31));
+        let n = ascii.encode(&mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..n], b"991231");
+    }
+
+    #[test]
+    fn dates_reject_bad_digits_and_lengths() {
+        assert!(matches!(
+            IdentificationDate::from_record(&[0x24, 0x0A, 0x15]),
+            Err(Error::InvalidDate)
+        ));
+        assert!(matches!(
+            IdentificationDate::from_record(&[0x24, 0x13, 0x15]),
+            Err(Error::InvalidDate)
+        ));
+        assert!(matches!(
+            IdentificationDate::from_record(b"24 315"),
+            Err(Error::InvalidDate)
+        ));
+        assert!(matches!(
+            IdentificationDate::from_record(&[0x24, 0x03]),
+            Err(Error::IncorrectMessageLengthOrInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn software_identification_splits_modules() {
+        let list = SoftwareIdentificationList::from_record(&[2, b'A', b'1', b'B', b'2']).unwrap();
+        assert_eq!(list.number_of_modules(), 2);
+        let mut entries = list.entries(2).map(Result::unwrap);
+        assert_eq!(entries.next(), Some(b"A1".as_slice()));
+        assert_eq!(entries.next(), Some(b"B2".as_slice()));
+        assert_eq!(entries.next(), None);
+        assert_eq!(
+            SoftwareIdentificationList::from_record(&[0])
+                .unwrap()
+                .entries(4)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn software_identification_modules_may_differ_in_length() {
+        let wire = [2, b'B', b'O', b'O', b'T', b'A', b'1'];
+        let list = BootSoftwareIdentification::from_record(&wire).unwrap();
+        assert_eq!(list.0.raw_entries(), &wire[1..]);
+        let mut entries = list
+            .0
+            .entries_with(|module| Some(if module == 0 { 4 } else { 2 }))
+            .map(Result::unwrap);
+        assert_eq!(entries.next(), Some(b"BOOT".as_slice()));
+        assert_eq!(entries.next(), Some(b"A1".as_slice()));
+        assert_eq!(entries.next(), None);
+
+        let mut buf = [0u8; 8];
+        let written = list.encode(&mut buf.as_mut_slice()).unwrap();
+        assert_eq!(&buf[..written], &wire);
+
+        // The second module runs past the end of the record.
+        let mut entries = list.0.entries(4);
+        assert_eq!(entries.next().unwrap().unwrap(), b"BOOT");
+        assert!(matches!(
+            entries.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+        assert!(entries.next().is_none());
+
+        // Bytes are left over after the last module.
+        let mut entries = list.0.entries(1);
+        assert_eq!(entries.next().unwrap().unwrap(), b"B");
+        assert_eq!(entries.next().unwrap().unwrap(), b"O");
+        assert!(matches!(
+            entries.next(),
+            Some(Err(Error::IncorrectMessageLengthOrInvalidFormat))
+        ));
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn typed_dids_decode_from_read_response() {
+        static DIDS: [(u16, usize); 3] = [(0xF190, 17), (0xF186, 1), (0xF199, 3)];
+        let mut frame = [0u8; 28];
+        frame[..2].copy_from_slice(&[0xF1, 0x90]);
+        frame[2..19].copy_from_slice(b"WVWZZZ1JZXW000001");
+        frame[19..22].copy_from_slice(&[0xF1, 0x86, 0x03]);
+        frame[22..25].copy_from_slice(&[0xF1, 0x99, 0x24]);
+        frame[25..].copy_from_slice(&[0x03, 0x15, 0x00]);
+        let resp = ReadDataByIdentifierResponse::new(&frame[..27]);
+
+        let vin = resp.decode_record::<Vin, _>(&DIDS).unwrap().unwrap();
+        assert_eq!(vin.as_str(), "WVWZZZ1JZXW000001");
+        assert_eq!(
+            resp.decode_record::<ActiveDiagnosticSession, _>(&DIDS)
+                .unwrap(),
+            Some(ActiveDiagnosticSession(
+                DiagnosticSessionType::ExtendedDiagnosticSession
+            ))
+        );
+        let date = resp
+            .decode_record::<ProgrammingDate, _>(&DIDS)
+            .unwrap()
+            .unwrap();
+        assert_eq!(date.0.to_string(), "240315");
+        assert_eq!(
+            resp.decode_record::<CalibrationDate, _>(&DIDS).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn typed_dids_encode_into_write_request() {
+        let vin: Vin = "WVWZZZ1JZXW000001".parse().unwrap();
+        let mut buf = [0u8; 32];
+        let req = WriteDataByIdentifierRequest::encode_record(&vin, &mut buf).unwrap();
+        assert_eq!(req.identifier, 0xF190);
+        assert_eq!(req.data, b"WVWZZZ1JZXW000001");
+
+        let mut small = [0u8; 4];
+        assert!(WriteDataByIdentifierRequest::encode_record(&vin, &mut small).is_err());
+    }
+}
@@ -1,11 +1,18 @@
 mod diagnostic_identifier;
 pub use diagnostic_identifier::{UdsIdentifier, UdsRoutineIdentifier};
 
+mod identification_data;
+pub use identification_data::{
+    ActiveDiagnosticSession, ApplicationDataIdentification, ApplicationSoftwareIdentification,
+    BootSoftwareIdentification, CalibrationDate, DateEncoding, EcuInstallationDate,
+    EcuManufacturingDate, IdentificationDate, ProgrammingDate, SoftwareIdentificationList, Vin,
+};
+
 mod negative_response_code;
 pub use negative_response_code::NegativeResponseCode;
 
 mod suppressable_positive_response;
-pub(crate) use suppressable_positive_response::SuppressablePositiveResponse;
+pub(crate) use suppressable_positive_response::{SPRMIB_VALUE_MASK, SuppressablePositiveResponse};
 
 mod format_identifiers;
 pub use format_identifiers::DataFormatIdentifier;
@@ -64,6 +64,34 @@ pub enum NegativeResponseCode {
     /// Reserved by ISO 15764
     #[cfg_attr(feature = "clap", clap(skip))]
     ExtendedDataLinkSecurityReserved(u8),
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate's validity period has expired or has not started yet.
+    CertificateVerificationFailedInvalidTimePeriod,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate's signature could not be verified.
+    CertificateVerificationFailedInvalidSignature,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate could not be verified against the server's chain of trust.
+    CertificateVerificationFailedInvalidChainOfTrust,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate is not of the type expected by the server.
+    CertificateVerificationFailedInvalidType,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate is not in the format expected by the server.
+    CertificateVerificationFailedInvalidFormat,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate content (e.g. a role or an extension) is not accepted by the server.
+    CertificateVerificationFailedInvalidContent,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate's scope does not cover the server.
+    CertificateVerificationFailedInvalidScope,
+    /// This response code indicates that the certificate verification in the `Authentication` service failed because the certificate is invalid, e.g. because it has been revoked.
+    CertificateVerificationFailedInvalidCertificate,
+    /// This response code indicates that the client failed to prove ownership of the certificate's private key.
+    OwnershipVerificationFailed,
+    /// This response code indicates that the server could not calculate the challenge for the `Authentication` service.
+    ChallengeCalculationFailed,
+    /// This response code indicates that the server could not set the access rights granted by a successful authentication.
+    SettingAccessRightsFailed,
+    /// This response code indicates that the server could not create or derive a session key.
+    SessionKeyCreationDerivationFailed,
+    /// This response code indicates that the server could not use the requested authentication configuration data.
+    ConfigurationDataUsageFailed,
+    /// This response code indicates that the de-authentication of the client was not successful.
+    DeAuthenticationFailed,
     /// This response code indicates that an attempt to upload/download to a server's memory cannot be accomplished due to some fault conditions.
     UploadDownloadNotAccepted,
     /// This response code indicates that a data transfer operation was halted due to some fault.
@@ -170,6 +198,20 @@ impl From<NegativeResponseCode> for u8 {
             NegativeResponseCode::ExceedNumberOfAttempts => 0x36,
             NegativeResponseCode::RequiredTimeDelayNotExpired => 0x37,
             NegativeResponseCode::ExtendedDataLinkSecurityReserved(value) => value,
+            NegativeResponseCode::CertificateVerificationFailedInvalidTimePeriod => 0x50,
+            NegativeResponseCode::CertificateVerificationFailedInvalidSignature => 0x51,
+            NegativeResponseCode::CertificateVerificationFailedInvalidChainOfTrust => 0x52,
+            NegativeResponseCode::CertificateVerificationFailedInvalidType => 0x53,
+            NegativeResponseCode::CertificateVerificationFailedInvalidFormat => 0x54,
+            NegativeResponseCode::CertificateVerificationFailedInvalidContent => 0x55,
+            NegativeResponseCode::CertificateVerificationFailedInvalidScope => 0x56,
+            NegativeResponseCode::CertificateVerificationFailedInvalidCertificate => 0x57,
+            NegativeResponseCode::OwnershipVerificationFailed => 0x58,
+            NegativeResponseCode::ChallengeCalculationFailed => 0x59,
+            NegativeResponseCode::SettingAccessRightsFailed => 0x5A,
+            NegativeResponseCode::SessionKeyCreationDerivationFailed => 0x5B,
+            NegativeResponseCode::ConfigurationDataUsageFailed => 0x5C,
+            NegativeResponseCode::DeAuthenticationFailed => 0x5D,
             NegativeResponseCode::UploadDownloadNotAccepted => 0x70,
             NegativeResponseCode::TransferDataSuspended => 0x71,
             NegativeResponseCode::GeneralProgrammingFailure => 0x72,
@@ -225,7 +267,21 @@ impl From<u8> for NegativeResponseCode {
             0x36 => Self::ExceedNumberOfAttempts,
             0x37 => Self::RequiredTimeDelayNotExpired,
             0x38..=0x4F => Self::ExtendedDataLinkSecurityReserved(value),
-            0x50..=0x6F => Self::IsoSaeReserved(value),
+            0x50 => Self::CertificateVerificationFailedInvalidTimePeriod,
+            0x51 => Self::CertificateVerificationFailedInvalidSignature,
+            0x52 => Self::CertificateVerificationFailedInvalidChainOfTrust,
+            0x53 => Self::CertificateVerificationFailedInvalidType,
+            0x54 => Self::CertificateVerificationFailedInvalidFormat,
+            0x55 => Self::CertificateVerificationFailedInvalidContent,
+            0x56 => Self::CertificateVerificationFailedInvalidScope,
+            0x57 => Self::CertificateVerificationFailedInvalidCertificate,
+            0x58 => Self::OwnershipVerificationFailed,
+            0x59 => Self::ChallengeCalculationFailed,
+            0x5A => Self::SettingAccessRightsFailed,
+            0x5B => Self::SessionKeyCreationDerivationFailed,
+            0x5C => Self::ConfigurationDataUsageFailed,
+            0x5D => Self::DeAuthenticationFailed,
+            0x5E..=0x6F => Self::IsoSaeReserved(value),
             0x70 => Self::UploadDownloadNotAccepted,
             0x71 => Self::TransferDataSuspended,
             0x72 => Self::GeneralProgrammingFailure,